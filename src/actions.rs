@@ -2,72 +2,130 @@
 //! Handles special queries like calculator, web search, and system commands
 
 use std::path::PathBuf;
+use crate::bangs::BangList;
+use crate::calc_session::{self, CalcEntry, CalcSession};
+use crate::config::CalculatorConfig;
+use crate::currency::CurrencyConversion;
+use crate::exact;
+use crate::system_commands;
+use num_rational::BigRational;
 use crate::SearchResultData;
 
 /// Check for special query prefixes
 pub fn check_special_query(query: &str) -> Option<SearchResultData> {
-    let query_lower = query.to_lowercase().trim().to_string();
-    
-    // System commands
-    match query_lower.as_str() {
-        "lock" => Some(SearchResultData {
-            name: "Lock Computer".to_string(),
-            description: "Lock your workstation".to_string(),
-            path: PathBuf::from("lock"),
-            result_type: "action".to_string(),
-        }),
-        "sleep" => Some(SearchResultData {
-            name: "Sleep".to_string(),
-            description: "Put computer to sleep".to_string(),
-            path: PathBuf::from("sleep"),
-            result_type: "action".to_string(),
-        }),
-        "restart" | "reboot" => Some(SearchResultData {
-            name: "Restart".to_string(),
-            description: "Restart your computer".to_string(),
-            path: PathBuf::from("restart"),
-            result_type: "action".to_string(),
-        }),
-        "shutdown" | "shut down" => Some(SearchResultData {
-            name: "Shutdown".to_string(),
-            description: "Shut down your computer".to_string(),
-            path: PathBuf::from("shutdown"),
-            result_type: "action".to_string(),
-        }),
-        "logout" | "sign out" | "logoff" => Some(SearchResultData {
-            name: "Sign Out".to_string(),
-            description: "Sign out of your account".to_string(),
-            path: PathBuf::from("logout"),
-            result_type: "action".to_string(),
-        }),
-        "empty trash" | "empty recycle bin" => Some(SearchResultData {
-            name: "Empty Recycle Bin".to_string(),
-            description: "Permanently delete items in Recycle Bin".to_string(),
-            path: PathBuf::from("emptytrash"),
-            result_type: "action".to_string(),
-        }),
-        _ => None,
-    }
+    // Delayed system commands ("shutdown in 30m")
+    system_commands::parse_delayed(query).map(system_commands::delayed_result)
 }
 
 /// Try to evaluate a mathematical expression
 pub fn try_calculate(query: &str) -> Option<SearchResultData> {
+    try_calculate_with(query, &CalcSession::new())
+}
+
+/// Try to evaluate a mathematical expression using the session's variables and `ans`.
+/// Supports assignments (`x = 42`) and a leading `=` to force calculator mode.
+pub fn try_calculate_with(query: &str, session: &CalcSession) -> Option<SearchResultData> {
+    let query = query.trim();
+    let (forced, query) = match query.strip_prefix('=') {
+        Some(rest) => (true, rest.trim()),
+        None => (false, query),
+    };
+
     // Skip if query doesn't look like math
     if query.is_empty() {
         return None;
     }
+
+    let (variable, expression) = match calc_session::parse_assignment(query) {
+        Some((name, expression)) => (Some(name), expression),
+        None => (None, query),
+    };
+
+    let (result_str, value, exact, description) =
+        evaluate_expression(expression, session, forced || variable.is_some())?;
+
+    let name = match variable {
+        Some(variable) => format!("{} = {}", variable, result_str),
+        None => format!("= {}", result_str),
+    };
+
+    Some(SearchResultData {
+        name,
+        description,
+        path: PathBuf::from(&result_str),
+        result_type: "calc".to_string(),
+        calc: Some(CalcEntry {
+            expression: expression.to_string(),
+            value,
+            display: result_str,
+            exact,
+            variable: variable.map(str::to_string),
+        }),
+        ..Default::default()
+    })
+}
+
+/// Evaluate an expression into (formatted result, numeric value, exact value, description)
+fn evaluate_expression(
+    query: &str,
+    session: &CalcSession,
+    forced: bool,
+) -> Option<(String, f64, Option<BigRational>, String)> {
+    let normalized;
+    let query = if session.decimal_comma() {
+        normalized = exact::normalize_decimal_comma(query);
+        normalized.as_str()
+    } else {
+        query
+    };
+
+    // Unit conversions: "12 km in mi", "350F to C"
+    if let Some(conversion) = crate::units::parse_conversion(query) {
+        let result_str = conversion.result_text();
+        let description = format!("{} = {} ({})", conversion.source_text(), result_str, conversion.from.dimension);
+        return Some((result_str, conversion.result, None, description));
+    }
+
+    // Programmer mode: "0xFF | 0x100", "1 << 20", "255 in hex", "-1 as u32"
+    if let Some(value) = crate::programmer::evaluate(query) {
+        let numeric = value.decimal().parse().unwrap_or(f64::NAN);
+        return Some((value.formatted(), numeric, None, value.representations()));
+    }
     
-    // Check if it contains math-like characters
+    // Check if it contains math-like characters or session variables
     let has_math = query.chars().any(|c| {
-        matches!(c, '+' | '-' | '*' | '/' | '^' | '(' | ')' | '%')
+        matches!(c, '+' | '-' | '*' | '/' | '^' | '(' | ')' | '%' | '!')
     }) || query.contains("sqrt") || query.contains("sin") || query.contains("cos");
     
-    if !has_math {
+    if !has_math && !forced && !session.references_variable(query) {
         return None;
     }
+
+    // Exact rational arithmetic by default: "0.1+0.2", "1/3 + 1/6", "30!"
+    let variable = |name: &str| {
+        session
+            .exact(name)
+            .cloned()
+            .or_else(|| session.get(name).and_then(exact::from_f64))
+    };
+    if let Some(value) = exact::evaluate(query, variable) {
+        let result_str = value.display();
+        let relation = if value.is_rounded() { "≈" } else { "=" };
+        let mut description = format!("{} {} {}", query, relation, result_str);
+        if let Some(note) = value.decimal_note() {
+            description = format!("{} {}", description, note);
+        }
+        return Some((result_str, value.to_f64(), Some(value.value), description));
+    }
+
+    // Floating point for functions and constants: "sqrt(2)", "sin(pi/4)"
+    let mut context = meval::Context::new();
+    for (name, value) in session.variables() {
+        context.var(name, value);
+    }
     
     // Try to evaluate
-    match meval::eval_str(query) {
+    match meval::eval_str_with_context(query, &context) {
         Ok(result) => {
             // Format the result nicely
             let result_str = if result.fract() == 0.0 && result.abs() < 1e15 {
@@ -75,70 +133,134 @@ pub fn try_calculate(query: &str) -> Option<SearchResultData> {
             } else {
                 format!("{:.6}", result).trim_end_matches('0').trim_end_matches('.').to_string()
             };
-            
-            Some(SearchResultData {
-                name: format!("= {}", result_str),
-                description: format!("{} = {}", query, result_str),
-                path: PathBuf::from(result_str),
-                result_type: "calc".to_string(),
-            })
+            let description = format!("{} = {}", query, result_str);
+            Some((result_str, result, None, description))
         }
         Err(_) => None,
     }
 }
 
-/// Check for web search shortcuts
-pub fn check_web_search(query: &str) -> Option<SearchResultData> {
-    let query_lower = query.to_lowercase();
-    
-    // Google search: "g query" or "google query"
-    if let Some(search_term) = query_lower.strip_prefix("g ").or_else(|| query_lower.strip_prefix("google ")) {
-        if !search_term.is_empty() {
-            let url = format!("https://www.google.com/search?q={}", urlencoding(search_term));
-            return Some(SearchResultData {
-                name: format!("Search Google: {}", search_term),
-                description: "Open Google search in browser".to_string(),
-                path: PathBuf::from(url),
-                result_type: "web".to_string(),
-            });
-        }
+/// Past calculations for the bare `=` prefix, most recent first
+pub fn calc_history(session: &CalcSession) -> Vec<SearchResultData> {
+    let results: Vec<SearchResultData> = session
+        .history()
+        .map(|entry| {
+            let assignment = entry
+                .variable
+                .as_ref()
+                .map(|variable| format!("{} = ", variable))
+                .unwrap_or_default();
+            SearchResultData {
+                name: format!("{}{}", assignment, entry.display),
+                description: format!("{}{} = {}", assignment, entry.expression, entry.display),
+                path: PathBuf::from(&entry.display),
+                result_type: "calc".to_string(),
+                calc: Some(entry.clone()),
+                ..Default::default()
+            }
+        })
+        .collect();
+
+    if results.is_empty() {
+        return vec![SearchResultData {
+            name: "No calculations yet".to_string(),
+            description: "Results you run with Enter appear here; reuse them with ans or variables".to_string(),
+            result_type: "info".to_string(),
+            ..Default::default()
+        }];
     }
-    
-    // YouTube search: "yt query"
-    if let Some(search_term) = query_lower.strip_prefix("yt ").or_else(|| query_lower.strip_prefix("youtube ")) {
-        if !search_term.is_empty() {
-            let url = format!("https://www.youtube.com/results?search_query={}", urlencoding(search_term));
-            return Some(SearchResultData {
-                name: format!("Search YouTube: {}", search_term),
-                description: "Open YouTube search in browser".to_string(),
-                path: PathBuf::from(url),
-                result_type: "web".to_string(),
-            });
-        }
+
+    results
+}
+
+/// Web search shortcuts: (keywords, site name, URL prefix the encoded query is appended to)
+const WEB_SEARCHES: &[(&[&str], &str, &str)] = &[
+    (&["g", "google"], "Google", "https://www.google.com/search?q="),
+    (&["yt", "youtube"], "YouTube", "https://www.youtube.com/results?search_query="),
+    (&["gh", "github"], "GitHub", "https://github.com/search?q="),
+    (&["wiki", "wikipedia"], "Wikipedia", "https://en.wikipedia.org/wiki/Special:Search?search="),
+];
+
+/// Try to convert between currencies using the local exchange rates file
+pub fn try_currency(query: &str, config: &CalculatorConfig) -> Option<SearchResultData> {
+    crate::currency::try_convert(query, config.rates_max_age_days).map(|c| currency_result(&c))
+}
+
+/// Build the calculator result for a currency conversion
+fn currency_result(conversion: &CurrencyConversion) -> SearchResultData {
+    let result_str = conversion.result_text();
+    let mut description = format!(
+        "{} = {} · rates of {}",
+        conversion.source_text(),
+        result_str,
+        conversion.rates_date.format("%Y-%m-%d")
+    );
+    if let Some(days) = conversion.stale_days {
+        description.push_str(&format!(" · ⚠ rates are {} days old", days));
     }
-    
-    // GitHub search: "gh query"
-    if let Some(search_term) = query_lower.strip_prefix("gh ").or_else(|| query_lower.strip_prefix("github ")) {
-        if !search_term.is_empty() {
-            let url = format!("https://github.com/search?q={}", urlencoding(search_term));
-            return Some(SearchResultData {
-                name: format!("Search GitHub: {}", search_term),
-                description: "Open GitHub search in browser".to_string(),
-                path: PathBuf::from(url),
-                result_type: "web".to_string(),
-            });
-        }
+
+    SearchResultData {
+        name: format!("= {}", result_str),
+        description,
+        path: PathBuf::from(&result_str),
+        result_type: "calc".to_string(),
+        calc: Some(CalcEntry {
+            expression: format!("{} in {}", conversion.source_text(), conversion.to),
+            value: conversion.result,
+            display: result_str,
+            exact: None,
+            variable: None,
+        }),
+        ..Default::default()
     }
-    
-    // Wikipedia search: "wiki query"
-    if let Some(search_term) = query_lower.strip_prefix("wiki ").or_else(|| query_lower.strip_prefix("wikipedia ")) {
-        if !search_term.is_empty() {
-            let url = format!("https://en.wikipedia.org/wiki/Special:Search?search={}", urlencoding(search_term));
+}
+
+/// Try to answer a date/time query ("now + 3 days", "time in tokyo", "unix 1760000000")
+pub fn try_datetime(query: &str) -> Option<SearchResultData> {
+    crate::datetime::evaluate(query, &crate::datetime::Clock::system()).map(|answer| SearchResultData {
+        name: answer.title,
+        description: answer.detail,
+        path: PathBuf::from(answer.value),
+        result_type: "calc".to_string(),
+        ..Default::default()
+    })
+}
+
+/// Check for web search shortcuts
+pub fn check_web_search(query: &str) -> Option<SearchResultData> {
+    check_web_search_with(query, crate::bangs::global())
+}
+
+/// Check for web search shortcuts against a specific bang list
+pub fn check_web_search_with(query: &str, bangs: &BangList) -> Option<SearchResultData> {
+    let query = query.trim();
+
+    // Bang shortcuts: "!mdn flexbox" or "flexbox !mdn"
+    if let Some(bang) = bangs.resolve(query) {
+        let name = if bang.terms.is_empty() {
+            format!("Open {}", bang.site)
+        } else {
+            format!("Search {}: {}", bang.site, bang.terms)
+        };
+        return Some(SearchResultData {
+            name,
+            description: format!("Open {} in browser", bang.site),
+            path: PathBuf::from(bang.url),
+            result_type: "web".to_string(),
+            ..Default::default()
+        });
+    }
+
+    // Keyword shortcuts: "g query", "yt query", ...
+    for (keywords, site, url_prefix) in WEB_SEARCHES {
+        if let Some(search_term) = keywords.iter().find_map(|k| strip_keyword(query, k)) {
+            let url = format!("{}{}", url_prefix, percent_encode(search_term));
             return Some(SearchResultData {
-                name: format!("Search Wikipedia: {}", search_term),
-                description: "Open Wikipedia search in browser".to_string(),
+                name: format!("Search {}: {}", site, search_term),
+                description: format!("Open {} search in browser", site),
                 path: PathBuf::from(url),
                 result_type: "web".to_string(),
+                ..Default::default()
             });
         }
     }
@@ -146,72 +268,40 @@ pub fn check_web_search(query: &str) -> Option<SearchResultData> {
     // Direct URL detection
     if query.starts_with("http://") || query.starts_with("https://") {
         return Some(SearchResultData {
-            name: format!("Open URL"),
+            name: "Open URL".to_string(),
             description: query.to_string(),
             path: PathBuf::from(query),
             result_type: "web".to_string(),
+            ..Default::default()
         });
     }
     
     None
 }
 
-/// Simple URL encoding for search queries
-fn urlencoding(s: &str) -> String {
-    s.chars()
-        .map(|c| match c {
-            ' ' => "%20".to_string(),
-            '&' => "%26".to_string(),
-            '?' => "%3F".to_string(),
-            '=' => "%3D".to_string(),
-            '#' => "%23".to_string(),
-            '+' => "%2B".to_string(),
-            _ if c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | '~') => c.to_string(),
-            _ => format!("%{:02X}", c as u32),
-        })
-        .collect()
+/// Strip a case-insensitive `keyword ` prefix, returning the rest with its original case
+fn strip_keyword<'a>(query: &'a str, keyword: &str) -> Option<&'a str> {
+    let head = query.get(..keyword.len())?;
+    let rest = query[keyword.len()..].strip_prefix(' ')?;
+    if head.eq_ignore_ascii_case(keyword) && !rest.trim().is_empty() {
+        Some(rest.trim())
+    } else {
+        None
+    }
 }
 
-/// Execute a system action
-pub fn execute_system_action(action: &str) {
-    use std::process::Command;
-    
-    match action.to_lowercase().as_str() {
-        "lock computer" => {
-            let _ = Command::new("rundll32.exe")
-                .args(["user32.dll,LockWorkStation"])
-                .spawn();
-        }
-        "sleep" => {
-            let _ = Command::new("rundll32.exe")
-                .args(["powrprof.dll,SetSuspendState", "0", "1", "0"])
-                .spawn();
-        }
-        "restart" => {
-            let _ = Command::new("shutdown")
-                .args(["/r", "/t", "0"])
-                .spawn();
-        }
-        "shutdown" => {
-            let _ = Command::new("shutdown")
-                .args(["/s", "/t", "0"])
-                .spawn();
-        }
-        "sign out" => {
-            let _ = Command::new("shutdown")
-                .args(["/l"])
-                .spawn();
-        }
-        "empty recycle bin" => {
-            // Uses PowerShell to empty recycle bin
-            let _ = Command::new("powershell")
-                .args(["-Command", "Clear-RecycleBin", "-Force", "-ErrorAction", "SilentlyContinue"])
-                .spawn();
-        }
-        _ => {
-            log::warn!("Unknown system action: {}", action);
+/// Percent-encode a string per RFC 3986, keeping only unreserved characters.
+/// Non-ASCII characters are encoded byte by byte from their UTF-8 form.
+pub fn percent_encode(s: &str) -> String {
+    let mut encoded = String::with_capacity(s.len());
+    for byte in s.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'_' | b'.' | b'~') {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{:02X}", byte));
         }
     }
+    encoded
 }
 
 #[cfg(test)]
@@ -231,6 +321,101 @@ mod tests {
         assert!(result.is_none());
     }
 
+    #[test]
+    fn test_calculate_exact() {
+        assert_eq!(try_calculate("0.1+0.2").unwrap().name, "= 0.3");
+        assert_eq!(try_calculate("20!").unwrap().name, "= 2432902008176640000");
+
+        let result = try_calculate("1/3 + 1/6").unwrap();
+        assert_eq!(result.name, "= 1/2");
+        assert_eq!(result.description, "1/3 + 1/6 = 1/2 = 0.5");
+        assert_eq!(result.calc.as_ref().unwrap().value, 0.5);
+
+        // Transcendental functions still use floating point
+        assert_eq!(try_calculate("sqrt(2)").unwrap().name, "= 1.414214");
+    }
+
+    #[test]
+    fn test_calculate_exact_across_session() {
+        let mut session = CalcSession::with_decimal_comma(true);
+        session.commit(try_calculate_with("third = 1/3", &session).unwrap().calc.as_ref().unwrap());
+        assert_eq!(try_calculate_with("third * 3", &session).unwrap().name, "= 1");
+        assert_eq!(try_calculate_with("0,1 + 0,2", &session).unwrap().name, "= 0.3");
+    }
+
+    #[test]
+    fn test_calculate_unit_conversion() {
+        let result = try_calculate("12 km in mi").unwrap();
+        assert_eq!(result.name, "= 7.456454 mi");
+        assert_eq!(result.description, "12 km = 7.456454 mi (Length)");
+        assert_eq!(result.result_type, "calc");
+    }
+
+    #[test]
+    fn test_calculate_with_session() {
+        let mut session = CalcSession::new();
+
+        let assignment = try_calculate_with("x = 42", &session).unwrap();
+        assert_eq!(assignment.name, "x = 42");
+        session.commit(assignment.calc.as_ref().unwrap());
+
+        let result = try_calculate_with("ans * 2", &session).unwrap();
+        assert_eq!(result.name, "= 84");
+        assert_eq!(result.calc.as_ref().unwrap().value, 84.0);
+        session.commit(result.calc.as_ref().unwrap());
+
+        // A bare variable name evaluates once it is defined
+        assert_eq!(try_calculate_with("x", &session).unwrap().name, "= 42");
+        assert!(try_calculate_with("y", &session).is_none());
+
+        // A leading "=" forces calculator mode
+        assert_eq!(try_calculate_with("= 7", &session).unwrap().name, "= 7");
+    }
+
+    #[test]
+    fn test_calc_history() {
+        let mut session = CalcSession::new();
+        assert_eq!(calc_history(&session)[0].result_type, "info");
+
+        session.commit(try_calculate_with("2+2", &session).unwrap().calc.as_ref().unwrap());
+        session.commit(try_calculate_with("y = ans + 1", &session).unwrap().calc.as_ref().unwrap());
+
+        let history = calc_history(&session);
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].name, "y = 5");
+        assert_eq!(history[0].description, "y = ans + 1 = 5");
+        assert_eq!(history[1].description, "2+2 = 4");
+    }
+
+    #[test]
+    fn test_calculate_programmer_mode() {
+        let result = try_calculate("0xFF | 0x100").unwrap();
+        assert_eq!(result.name, "= 0x1FF");
+        assert_eq!(result.description, "i64 · dec 511 · hex 0x1FF · oct 0o777 · bin 0b1_1111_1111");
+
+        // Integers beyond f64 precision stay exact
+        let result = try_calculate("(1 << 62) + 1 in dec").unwrap();
+        assert_eq!(result.name, "= 4611686018427387905");
+    }
+
+    #[test]
+    fn test_currency_result_description() {
+        let conversion = CurrencyConversion {
+            amount: 100.0,
+            from: "USD".to_string(),
+            result: 80.0,
+            to: "EUR".to_string(),
+            rates_date: chrono::NaiveDate::from_ymd_opt(2026, 10, 10).unwrap(),
+            stale_days: Some(20),
+        };
+        let result = currency_result(&conversion);
+        assert_eq!(result.name, "= 80.00 EUR");
+        assert_eq!(
+            result.description,
+            "100.00 USD = 80.00 EUR · rates of 2026-10-10 · ⚠ rates are 20 days old"
+        );
+    }
+
     #[test]
     fn test_web_search() {
         let result = check_web_search("g rust programming");
@@ -240,4 +425,37 @@ mod tests {
         let result = check_web_search("yt music");
         assert!(result.is_some());
     }
+
+    #[test]
+    fn test_web_search_preserves_case() {
+        let bangs = BangList::builtin();
+        let result = check_web_search_with("G Rust Traits", &bangs).unwrap();
+        assert_eq!(result.name, "Search Google: Rust Traits");
+        assert_eq!(
+            result.path.to_string_lossy(),
+            "https://www.google.com/search?q=Rust%20Traits"
+        );
+    }
+
+    #[test]
+    fn test_web_search_bang() {
+        let bangs = BangList::builtin();
+        let result = check_web_search_with("!mdn flexbox", &bangs).unwrap();
+        assert_eq!(
+            result.path.to_string_lossy(),
+            "https://developer.mozilla.org/en-US/search?q=flexbox"
+        );
+
+        // Bangs take precedence over keyword shortcuts
+        let result = check_web_search_with("g !yt lofi", &bangs).unwrap();
+        assert!(result.path.to_string_lossy().contains("youtube.com"));
+    }
+
+    #[test]
+    fn test_percent_encode() {
+        assert_eq!(percent_encode("a b&c=d"), "a%20b%26c%3Dd");
+        assert_eq!(percent_encode("café"), "caf%C3%A9");
+        assert_eq!(percent_encode("東京"), "%E6%9D%B1%E4%BA%AC");
+        assert_eq!(percent_encode("Keep-Case_1.0~"), "Keep-Case_1.0~");
+    }
 }
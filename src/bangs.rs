@@ -0,0 +1,220 @@
+//! Bang shortcuts module
+//! DuckDuckGo-style `!bang` shortcuts for web queries, loaded from a local bang list
+
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use crate::config::AppConfig;
+
+/// File name of the bang list inside the configuration directory
+pub const BANGS_FILE_NAME: &str = "bangs.json";
+
+/// Placeholder replaced by the encoded search terms in a bang URL template
+const QUERY_PLACEHOLDER: &str = "{{{s}}}";
+
+/// A single bang entry, using the field names of DuckDuckGo's `bang.json`
+/// so their published list can be dropped in as-is
+#[derive(Debug, Clone, Deserialize)]
+pub struct Bang {
+    /// Trigger without the leading `!` (e.g. "mdn")
+    #[serde(rename = "t")]
+    pub trigger: String,
+
+    /// Human readable site name
+    #[serde(rename = "s")]
+    pub site: String,
+
+    /// URL template containing `{{{s}}}` where the query goes
+    #[serde(rename = "u")]
+    pub url: String,
+
+    /// Site domain, opened when the bang is used without search terms
+    #[serde(rename = "d", default)]
+    pub domain: String,
+}
+
+/// Bang list indexed by lowercase trigger
+#[derive(Debug, Default)]
+pub struct BangList {
+    bangs: HashMap<String, Bang>,
+}
+
+/// A bang resolved against a query
+#[derive(Debug, Clone, PartialEq)]
+pub struct BangMatch {
+    pub site: String,
+    pub terms: String,
+    pub url: String,
+}
+
+impl BangList {
+    /// Build a list from entries; later entries override earlier ones with the same trigger
+    pub fn from_bangs(bangs: Vec<Bang>) -> Self {
+        let bangs = bangs
+            .into_iter()
+            .map(|bang| (bang.trigger.to_lowercase(), bang))
+            .collect();
+        Self { bangs }
+    }
+
+    /// Parse a bang list from JSON text
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        let bangs: Vec<Bang> = serde_json::from_str(json)?;
+        Ok(Self::from_bangs(bangs))
+    }
+
+    /// Load the bang list from a file, falling back to the built-in list
+    pub fn load_from(path: &Path) -> Self {
+        if !path.exists() {
+            log::debug!("No bang list at {:?}, using built-in bangs", path);
+            return Self::builtin();
+        }
+
+        match fs::read_to_string(path) {
+            Ok(content) => match Self::from_json(&content) {
+                Ok(list) => {
+                    log::info!("Loaded {} bangs from {:?}", list.len(), path);
+                    list
+                }
+                Err(e) => {
+                    log::warn!("Failed to parse bang list: {}. Using built-in bangs.", e);
+                    Self::builtin()
+                }
+            },
+            Err(e) => {
+                log::warn!("Failed to read bang list: {}. Using built-in bangs.", e);
+                Self::builtin()
+            }
+        }
+    }
+
+    /// Small built-in list used when no bang file is present
+    pub fn builtin() -> Self {
+        let entries = [
+            ("g", "Google", "https://www.google.com/search?q={{{s}}}", "www.google.com"),
+            ("ddg", "DuckDuckGo", "https://duckduckgo.com/?q={{{s}}}", "duckduckgo.com"),
+            ("yt", "YouTube", "https://www.youtube.com/results?search_query={{{s}}}", "www.youtube.com"),
+            ("gh", "GitHub", "https://github.com/search?q={{{s}}}", "github.com"),
+            ("w", "Wikipedia", "https://en.wikipedia.org/wiki/Special:Search?search={{{s}}}", "en.wikipedia.org"),
+            ("mdn", "MDN Web Docs", "https://developer.mozilla.org/en-US/search?q={{{s}}}", "developer.mozilla.org"),
+            ("so", "Stack Overflow", "https://stackoverflow.com/search?q={{{s}}}", "stackoverflow.com"),
+            ("rs", "Rust std docs", "https://doc.rust-lang.org/std/?search={{{s}}}", "doc.rust-lang.org"),
+            ("crates", "crates.io", "https://crates.io/search?q={{{s}}}", "crates.io"),
+            ("npm", "npm", "https://www.npmjs.com/search?q={{{s}}}", "www.npmjs.com"),
+            ("a", "Amazon", "https://www.amazon.com/s?k={{{s}}}", "www.amazon.com"),
+            ("maps", "Google Maps", "https://www.google.com/maps/search/{{{s}}}", "www.google.com/maps"),
+        ];
+
+        Self::from_bangs(
+            entries
+                .iter()
+                .map(|(trigger, site, url, domain)| Bang {
+                    trigger: trigger.to_string(),
+                    site: site.to_string(),
+                    url: url.to_string(),
+                    domain: domain.to_string(),
+                })
+                .collect(),
+        )
+    }
+
+    /// Number of known bangs
+    pub fn len(&self) -> usize {
+        self.bangs.len()
+    }
+
+    /// Look up a bang by trigger (case-insensitive, without the `!`)
+    pub fn get(&self, trigger: &str) -> Option<&Bang> {
+        self.bangs.get(&trigger.to_lowercase())
+    }
+
+    /// Find the first known `!bang` anywhere in the query and build its URL.
+    /// The remaining words, with their original case, become the search terms.
+    pub fn resolve(&self, query: &str) -> Option<BangMatch> {
+        let words: Vec<&str> = query.split_whitespace().collect();
+
+        let (position, bang) = words.iter().enumerate().find_map(|(i, word)| {
+            let trigger = word.strip_prefix('!')?;
+            self.get(trigger).map(|bang| (i, bang))
+        })?;
+
+        let terms = words
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| *i != position)
+            .map(|(_, word)| *word)
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        let url = if terms.is_empty() && !bang.domain.is_empty() {
+            format!("https://{}", bang.domain)
+        } else {
+            bang.url.replace(QUERY_PLACEHOLDER, &crate::actions::percent_encode(&terms))
+        };
+
+        Some(BangMatch {
+            site: bang.site.clone(),
+            terms,
+            url,
+        })
+    }
+}
+
+/// Path of the bang list file in the configuration directory
+pub fn bangs_path() -> Option<PathBuf> {
+    AppConfig::config_dir(crate::single_instance::detect_portable_mode())
+        .map(|dir| dir.join(BANGS_FILE_NAME))
+}
+
+/// Shared bang list, loaded on first use
+pub fn global() -> &'static BangList {
+    static BANGS: OnceLock<BangList> = OnceLock::new();
+    BANGS.get_or_init(|| match bangs_path() {
+        Some(path) => BangList::load_from(&path),
+        None => BangList::builtin(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_bang_anywhere() {
+        let bangs = BangList::builtin();
+
+        let front = bangs.resolve("!mdn flexbox").unwrap();
+        assert_eq!(front.url, "https://developer.mozilla.org/en-US/search?q=flexbox");
+        assert_eq!(front.site, "MDN Web Docs");
+
+        let back = bangs.resolve("CSS Grid !MDN").unwrap();
+        assert_eq!(back.terms, "CSS Grid");
+        assert_eq!(back.url, "https://developer.mozilla.org/en-US/search?q=CSS%20Grid");
+
+        assert!(bangs.resolve("hello !unknownbang").is_none());
+        assert!(bangs.resolve("no bangs here").is_none());
+    }
+
+    #[test]
+    fn test_bang_without_terms_opens_site() {
+        let bangs = BangList::builtin();
+        let result = bangs.resolve("!gh").unwrap();
+        assert_eq!(result.url, "https://github.com");
+    }
+
+    #[test]
+    fn test_load_ddg_format() {
+        let json = r#"[
+            {"t": "mdn", "s": "MDN", "u": "https://developer.mozilla.org/search?q={{{s}}}", "d": "developer.mozilla.org", "r": 0, "c": "Tech"},
+            {"t": "wt", "s": "Wiktionary", "u": "https://en.wiktionary.org/wiki/{{{s}}}"}
+        ]"#;
+        let bangs = BangList::from_json(json).unwrap();
+        assert_eq!(bangs.len(), 2);
+
+        let result = bangs.resolve("!wt café").unwrap();
+        assert_eq!(result.url, "https://en.wiktionary.org/wiki/caf%C3%A9");
+    }
+}
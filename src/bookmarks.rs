@@ -0,0 +1,390 @@
+//! Bookmarks module
+//! Reads browser bookmarks into the search catalog: the `Bookmarks` JSON file of
+//! Chromium browsers (Chrome, Edge, Brave) and `places.sqlite` of Firefox. Profiles
+//! are found automatically or set in the configuration, and re-read when their files
+//! change.
+
+use serde::Deserialize;
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::SystemTime;
+
+use crate::config::BookmarksConfig;
+use crate::{AppEntry, AppType};
+
+/// Bookmarks file of a Chromium profile
+const CHROMIUM_FILE_NAME: &str = "Bookmarks";
+
+/// Bookmarks database of a Firefox profile
+const FIREFOX_FILE_NAME: &str = "places.sqlite";
+
+/// A bookmarked page
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bookmark {
+    pub title: String,
+    pub url: String,
+
+    /// Folder the bookmark is in ("Bookmarks bar", "Work")
+    pub folder: String,
+}
+
+/// A bookmarks file of one browser profile
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum BookmarkSource {
+    /// `Bookmarks` JSON file of Chrome, Edge or Brave
+    Chromium(PathBuf),
+    /// `places.sqlite` of Firefox
+    Firefox(PathBuf),
+}
+
+impl BookmarkSource {
+    /// Source for a profile folder or a bookmarks file, if it holds bookmarks
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let file = if path.is_dir() {
+            [CHROMIUM_FILE_NAME, FIREFOX_FILE_NAME]
+                .iter()
+                .map(|name| path.join(name))
+                .find(|file| file.is_file())?
+        } else {
+            path.to_path_buf()
+        };
+        match file.file_name()?.to_str()? {
+            CHROMIUM_FILE_NAME => Some(BookmarkSource::Chromium(file)),
+            FIREFOX_FILE_NAME => Some(BookmarkSource::Firefox(file)),
+            _ => None,
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        match self {
+            BookmarkSource::Chromium(path) | BookmarkSource::Firefox(path) => path,
+        }
+    }
+
+    /// Browser name shown in results, guessed from the profile location
+    pub fn browser(&self) -> &'static str {
+        let path = self.path().to_string_lossy().to_lowercase();
+        match self {
+            BookmarkSource::Firefox(_) => "Firefox",
+            _ if path.contains("edge") => "Edge",
+            _ if path.contains("brave") => "Brave",
+            _ if path.contains("chrome") => "Chrome",
+            _ => "Chromium",
+        }
+    }
+
+    pub fn read(&self) -> Result<Vec<Bookmark>, Box<dyn Error>> {
+        match self {
+            BookmarkSource::Chromium(path) => parse_chromium(&fs::read_to_string(path)?),
+            BookmarkSource::Firefox(path) => read_firefox(path),
+        }
+    }
+
+    /// Modification times of the files the bookmarks are read from; Firefox keeps
+    /// recent changes in a write-ahead log next to the database
+    fn modified(&self) -> Vec<Option<SystemTime>> {
+        let mut files = vec![self.path().to_path_buf()];
+        if let BookmarkSource::Firefox(path) = self {
+            files.push(path.with_extension("sqlite-wal"));
+        }
+        files
+            .iter()
+            .map(|file| fs::metadata(file).and_then(|m| m.modified()).ok())
+            .collect()
+    }
+}
+
+/// Profiles to read: the configured ones, or every profile of the known browsers
+pub fn sources(config: &BookmarksConfig) -> Vec<BookmarkSource> {
+    if !config.enabled {
+        return Vec::new();
+    }
+    if !config.profiles.is_empty() {
+        return config
+            .profiles
+            .iter()
+            .filter_map(|path| {
+                let source = BookmarkSource::from_path(path);
+                if source.is_none() {
+                    log::warn!("No bookmarks found in {:?}", path);
+                }
+                source
+            })
+            .collect();
+    }
+
+    let mut roots = Vec::new();
+    if let Some(local) = dirs::data_local_dir() {
+        for [vendor, browser] in [["Google", "Chrome"], ["Microsoft", "Edge"], ["BraveSoftware", "Brave-Browser"]] {
+            roots.push(local.join(vendor).join(browser).join("User Data"));
+        }
+    }
+    if let Some(roaming) = dirs::data_dir() {
+        roots.push(roaming.join("Mozilla").join("Firefox").join("Profiles"));
+    }
+
+    // Each browser keeps one folder per profile ("Default", "Profile 1", "abcd.default-release")
+    roots
+        .iter()
+        .filter_map(|root| fs::read_dir(root).ok())
+        .flat_map(|entries| entries.flatten())
+        .filter_map(|entry| BookmarkSource::from_path(&entry.path()))
+        .collect()
+}
+
+#[derive(Deserialize)]
+struct ChromiumFile {
+    roots: HashMap<String, serde_json::Value>,
+}
+
+#[derive(Deserialize)]
+struct ChromiumNode {
+    #[serde(default)]
+    name: String,
+    #[serde(rename = "type", default)]
+    node_type: String,
+    #[serde(default)]
+    url: String,
+    #[serde(default)]
+    children: Vec<ChromiumNode>,
+}
+
+/// Bookmarks of a Chromium `Bookmarks` file, in the order of its roots
+pub fn parse_chromium(json: &str) -> Result<Vec<Bookmark>, Box<dyn Error>> {
+    let file: ChromiumFile = serde_json::from_str(json)?;
+    let mut bookmarks = Vec::new();
+    for root in ["bookmark_bar", "other", "synced"] {
+        // Roots are parsed one by one so an unexpected one can't hide the others
+        if let Some(node) = file.roots.get(root) {
+            let node: ChromiumNode = serde_json::from_value(node.clone())?;
+            collect_chromium(&node, &node.name, &mut bookmarks);
+        }
+    }
+    Ok(bookmarks)
+}
+
+fn collect_chromium(node: &ChromiumNode, folder: &str, bookmarks: &mut Vec<Bookmark>) {
+    match node.node_type.as_str() {
+        "url" => bookmarks.push(Bookmark {
+            title: if node.name.is_empty() { node.url.clone() } else { node.name.clone() },
+            url: node.url.clone(),
+            folder: folder.to_string(),
+        }),
+        "folder" => {
+            for child in &node.children {
+                collect_chromium(child, &node.name, bookmarks);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Bookmarks of a Firefox `places.sqlite`. Firefox keeps the database locked while it
+/// runs, so it is copied (with its write-ahead log) and the copy is read.
+pub fn read_firefox(path: &Path) -> Result<Vec<Bookmark>, Box<dyn Error>> {
+    use rusqlite::{Connection, OpenFlags};
+
+    // One folder per read, so concurrent reads never remove each other's copy
+    static READS: AtomicUsize = AtomicUsize::new(0);
+    let copy_dir = std::env::temp_dir().join(format!(
+        "nexus_places_{}_{}",
+        std::process::id(),
+        READS.fetch_add(1, Ordering::Relaxed)
+    ));
+    fs::create_dir_all(&copy_dir)?;
+    let copy = copy_dir.join(FIREFOX_FILE_NAME);
+    fs::copy(path, &copy)?;
+    let wal = path.with_extension("sqlite-wal");
+    if wal.exists() {
+        fs::copy(&wal, copy.with_extension("sqlite-wal"))?;
+    }
+
+    let bookmarks = (|| -> Result<Vec<Bookmark>, Box<dyn Error>> {
+        // Write access lets SQLite apply the copied log; only the copy is touched
+        let connection = Connection::open_with_flags(&copy, OpenFlags::SQLITE_OPEN_READ_WRITE)?;
+        let mut statement = connection.prepare(
+            "SELECT COALESCE(NULLIF(b.title, ''), p.title, p.url), p.url, COALESCE(f.title, '')
+             FROM moz_bookmarks b
+             JOIN moz_places p ON p.id = b.fk
+             LEFT JOIN moz_bookmarks f ON f.id = b.parent
+             WHERE b.type = 1 AND p.url NOT LIKE 'place:%'
+             ORDER BY b.parent, b.position",
+        )?;
+        let rows = statement.query_map([], |row| {
+            let folder: String = row.get(2)?;
+            Ok(Bookmark {
+                title: row.get(0)?,
+                url: row.get(1)?,
+                folder: firefox_folder_name(&folder).to_string(),
+            })
+        })?;
+        Ok(rows.collect::<Result<Vec<_>, _>>()?)
+    })();
+
+    let _ = fs::remove_dir_all(&copy_dir);
+    bookmarks
+}
+
+/// Display names of Firefox's built-in root folders
+fn firefox_folder_name(title: &str) -> &str {
+    match title {
+        "menu" => "Bookmarks Menu",
+        "toolbar" => "Bookmarks Toolbar",
+        "unfiled" => "Other Bookmarks",
+        "mobile" => "Mobile Bookmarks",
+        _ => title,
+    }
+}
+
+/// Catalog entries for bookmarks, once per URL
+pub fn to_entries(bookmarks: Vec<(&'static str, Bookmark)>) -> Vec<AppEntry> {
+    let mut seen = HashSet::new();
+    bookmarks
+        .into_iter()
+        .filter(|(_, bookmark)| seen.insert(bookmark.url.clone()))
+        .map(|(browser, bookmark)| {
+            let location = if bookmark.folder.is_empty() {
+                browser.to_string()
+            } else {
+                format!("{} › {}", browser, bookmark.folder)
+            };
+            AppEntry {
+                name: bookmark.title,
+                description: format!("{} · {}", location, bookmark.url),
+                path: PathBuf::from(bookmark.url),
+                app_type: AppType::Bookmark,
+            }
+        })
+        .collect()
+}
+
+/// Bookmarks of a set of profiles, re-read when any of their files changes
+#[derive(Debug, Default)]
+pub struct BookmarkIndex {
+    sources: Vec<BookmarkSource>,
+    modified: HashMap<BookmarkSource, Vec<Option<SystemTime>>>,
+}
+
+impl BookmarkIndex {
+    pub fn new(sources: Vec<BookmarkSource>) -> Self {
+        Self {
+            sources,
+            modified: HashMap::new(),
+        }
+    }
+
+    /// All bookmarks when a file changed since the last call (always on the first call)
+    pub fn refresh_if_changed(&mut self) -> Option<Vec<AppEntry>> {
+        let modified: HashMap<_, _> = self
+            .sources
+            .iter()
+            .map(|source| (source.clone(), source.modified()))
+            .collect();
+        if !self.modified.is_empty() && modified == self.modified {
+            return None;
+        }
+        self.modified = modified;
+
+        let mut bookmarks = Vec::new();
+        for source in &self.sources {
+            match source.read() {
+                Ok(read) => bookmarks.extend(read.into_iter().map(|bookmark| (source.browser(), bookmark))),
+                Err(e) => log::warn!("Failed to read bookmarks from {:?}: {}", source.path(), e),
+            }
+        }
+        Some(to_entries(bookmarks))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixture(name: &str) -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/bookmarks").join(name)
+    }
+
+    fn titles(bookmarks: &[Bookmark]) -> Vec<&str> {
+        bookmarks.iter().map(|b| b.title.as_str()).collect()
+    }
+
+    #[test]
+    fn test_parse_chromium() {
+        let bookmarks = parse_chromium(&fs::read_to_string(fixture("Bookmarks")).unwrap()).unwrap();
+        assert_eq!(
+            titles(&bookmarks),
+            ["Build dashboard", "Team wiki", "https://status.example.com/", "Rust docs"]
+        );
+        assert_eq!(bookmarks[0].folder, "Bookmarks bar");
+        assert_eq!(bookmarks[1].folder, "Work");
+        assert_eq!(bookmarks[1].url, "https://wiki.example.com/team");
+        assert!(parse_chromium("not json").is_err());
+    }
+
+    #[test]
+    fn test_read_firefox() {
+        let bookmarks = read_firefox(&fixture("places.sqlite")).unwrap();
+        assert_eq!(titles(&bookmarks), ["Mail", "Sprint board", "CI dashboard"]);
+        assert_eq!(bookmarks[0].folder, "Bookmarks Menu");
+        assert_eq!(bookmarks[1].folder, "Bookmarks Toolbar");
+        assert_eq!(bookmarks[2].folder, "Ops");
+    }
+
+    #[test]
+    fn test_sources() {
+        let dir = fixture("");
+        assert_eq!(
+            BookmarkSource::from_path(&dir),
+            Some(BookmarkSource::Chromium(fixture("Bookmarks")))
+        );
+        assert_eq!(
+            BookmarkSource::from_path(&fixture("places.sqlite")).map(|s| s.browser()),
+            Some("Firefox")
+        );
+        assert_eq!(BookmarkSource::from_path(&fixture("missing.json")), None);
+
+        let config = BookmarksConfig {
+            enabled: true,
+            profiles: vec![fixture("Bookmarks"), fixture("places.sqlite")],
+        };
+        assert_eq!(sources(&config).len(), 2);
+        assert!(sources(&BookmarksConfig { enabled: false, ..config }).is_empty());
+    }
+
+    #[test]
+    fn test_index_dedupes_and_refreshes() {
+        let dir = std::env::temp_dir().join(format!("nexus_bookmarks_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let chrome = dir.join("Bookmarks");
+        fs::copy(fixture("Bookmarks"), &chrome).unwrap();
+
+        let mut index = BookmarkIndex::new(vec![
+            BookmarkSource::Chromium(chrome.clone()),
+            BookmarkSource::Firefox(fixture("places.sqlite")),
+        ]);
+        let entries = index.refresh_if_changed().unwrap();
+
+        // The CI dashboard is bookmarked in both browsers but listed once
+        assert_eq!(entries.len(), 6);
+        assert_eq!(entries[0].name, "Build dashboard");
+        assert_eq!(entries[0].app_type, AppType::Bookmark);
+        assert_eq!(
+            entries[1].description,
+            "Chromium › Work · https://wiki.example.com/team"
+        );
+        assert!(index.refresh_if_changed().is_none());
+
+        // Rewriting the file with a later timestamp triggers a reload
+        let json = fs::read_to_string(&chrome).unwrap().replace("Team wiki", "Team handbook");
+        fs::write(&chrome, json).unwrap();
+        let later = SystemTime::now() + std::time::Duration::from_secs(5);
+        fs::File::options().write(true).open(&chrome).unwrap().set_modified(later).unwrap();
+        let entries = index.refresh_if_changed().unwrap();
+        assert_eq!(entries[1].name, "Team handbook");
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
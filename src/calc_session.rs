@@ -0,0 +1,211 @@
+//! Calculator session module
+//! Keeps variables, the last answer (`ans`) and a history of calculations for the
+//! lifetime of the launcher process
+
+use num_rational::BigRational;
+use std::collections::{HashMap, VecDeque};
+
+/// Maximum number of calculations kept in the history
+const MAX_HISTORY: usize = 50;
+
+/// Names that cannot be assigned because the evaluator already defines them
+const RESERVED_NAMES: &[&str] = &[
+    "ans", "pi", "e", "sqrt", "exp", "ln", "abs", "sin", "cos", "tan", "asin", "acos",
+    "atan", "atan2", "sinh", "cosh", "tanh", "asinh", "acosh", "atanh", "floor", "ceil",
+    "round", "signum", "max", "min",
+];
+
+/// A calculator result that can be committed to the session
+#[derive(Debug, Clone, PartialEq)]
+pub struct CalcEntry {
+    /// Expression as typed, without any assignment target
+    pub expression: String,
+
+    /// Numeric value of the result
+    pub value: f64,
+
+    /// Formatted result as displayed
+    pub display: String,
+
+    /// Exact value when the result came from rational arithmetic
+    pub exact: Option<BigRational>,
+
+    /// Variable the result is assigned to (`x = 42`)
+    pub variable: Option<String>,
+}
+
+/// Variables, last answer and history of the current session
+#[derive(Debug, Default)]
+pub struct CalcSession {
+    variables: HashMap<String, f64>,
+    ans: Option<f64>,
+    history: VecDeque<CalcEntry>,
+
+    /// Exact values of variables and `ans`, kept so fractions survive reuse
+    exact: HashMap<String, BigRational>,
+
+    /// Whether `2,5` is read as a decimal number
+    decimal_comma: bool,
+}
+
+impl CalcSession {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Session that reads `2,5` as a decimal and `;` as the argument separator
+    pub fn with_decimal_comma(decimal_comma: bool) -> Self {
+        Self {
+            decimal_comma,
+            ..Self::default()
+        }
+    }
+
+    pub fn decimal_comma(&self) -> bool {
+        self.decimal_comma
+    }
+
+    /// Value of a variable, including `ans`
+    pub fn get(&self, name: &str) -> Option<f64> {
+        if name == "ans" {
+            self.ans
+        } else {
+            self.variables.get(name).copied()
+        }
+    }
+
+    /// Exact value of a variable or `ans`, when it was computed exactly
+    pub fn exact(&self, name: &str) -> Option<&BigRational> {
+        self.exact.get(name)
+    }
+
+    /// All variables available to expressions, including `ans` once set
+    pub fn variables(&self) -> impl Iterator<Item = (&str, f64)> {
+        self.variables
+            .iter()
+            .map(|(name, value)| (name.as_str(), *value))
+            .chain(self.ans.map(|value| ("ans", value)))
+    }
+
+    /// Whether an expression references a session variable or `ans`
+    pub fn references_variable(&self, expression: &str) -> bool {
+        identifiers(expression).any(|word| self.get(word).is_some())
+    }
+
+    /// Commit an activated result: it becomes `ans`, is stored in its variable and added to history
+    pub fn commit(&mut self, entry: &CalcEntry) {
+        self.ans = Some(entry.value);
+        let names = std::iter::once("ans").chain(entry.variable.as_deref());
+        for name in names {
+            match &entry.exact {
+                Some(exact) => self.exact.insert(name.to_string(), exact.clone()),
+                None => self.exact.remove(name),
+            };
+        }
+        if let Some(variable) = &entry.variable {
+            self.variables.insert(variable.clone(), entry.value);
+        }
+
+        // Re-running a calculation moves it to the top instead of duplicating it
+        self.history.retain(|e| e != entry);
+        self.history.push_front(entry.clone());
+        self.history.truncate(MAX_HISTORY);
+    }
+
+    /// Past calculations, most recent first
+    pub fn history(&self) -> impl Iterator<Item = &CalcEntry> {
+        self.history.iter()
+    }
+}
+
+/// Split "x = 42 * 2" into ("x", "42 * 2") when the left side is an assignable name
+pub fn parse_assignment(query: &str) -> Option<(&str, &str)> {
+    let (name, expression) = query.split_once('=')?;
+    let name = name.trim();
+    let expression = expression.trim();
+
+    let valid_name = name.chars().next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && !RESERVED_NAMES.contains(&name);
+
+    if valid_name && !expression.is_empty() && !expression.contains('=') {
+        Some((name, expression))
+    } else {
+        None
+    }
+}
+
+/// Identifiers appearing in an expression
+fn identifiers(expression: &str) -> impl Iterator<Item = &str> {
+    expression
+        .split(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+        .filter(|word| word.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(expression: &str, value: f64, variable: Option<&str>) -> CalcEntry {
+        CalcEntry {
+            expression: expression.to_string(),
+            value,
+            display: value.to_string(),
+            exact: None,
+            variable: variable.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_parse_assignment() {
+        assert_eq!(parse_assignment("x = 42"), Some(("x", "42")));
+        assert_eq!(parse_assignment("rate_2=ans*2"), Some(("rate_2", "ans*2")));
+        assert_eq!(parse_assignment("pi = 3"), None);
+        assert_eq!(parse_assignment("2x = 3"), None);
+        assert_eq!(parse_assignment("x = "), None);
+        assert_eq!(parse_assignment("x == 2"), None);
+        assert_eq!(parse_assignment("2 + 2"), None);
+    }
+
+    #[test]
+    fn test_commit_sets_ans_and_variables() {
+        let mut session = CalcSession::new();
+        assert_eq!(session.get("ans"), None);
+
+        session.commit(&entry("42", 42.0, Some("x")));
+        assert_eq!(session.get("x"), Some(42.0));
+        assert_eq!(session.get("ans"), Some(42.0));
+
+        session.commit(&entry("x * 2", 84.0, None));
+        assert_eq!(session.get("ans"), Some(84.0));
+        assert_eq!(session.get("x"), Some(42.0));
+        assert!(session.references_variable("ans / x"));
+        assert!(!session.references_variable("y + 1"));
+
+        let mut third = entry("1/3", 1.0 / 3.0, Some("t"));
+        third.exact = Some(BigRational::new(1.into(), 3.into()));
+        session.commit(&third);
+        assert_eq!(session.exact("t"), third.exact.as_ref());
+        assert_eq!(session.exact("ans"), third.exact.as_ref());
+
+        session.commit(&entry("0.5", 0.5, None));
+        assert_eq!(session.exact("ans"), None);
+        assert!(session.exact("t").is_some());
+    }
+
+    #[test]
+    fn test_history_order_and_limit() {
+        let mut session = CalcSession::new();
+        session.commit(&entry("1+1", 2.0, None));
+        session.commit(&entry("2+2", 4.0, None));
+        session.commit(&entry("1+1", 2.0, None));
+
+        let expressions: Vec<_> = session.history().map(|e| e.expression.as_str()).collect();
+        assert_eq!(expressions, ["1+1", "2+2"]);
+
+        for i in 0..(MAX_HISTORY + 10) {
+            session.commit(&entry(&i.to_string(), i as f64, None));
+        }
+        assert_eq!(session.history().count(), MAX_HISTORY);
+    }
+}
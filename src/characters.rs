@@ -0,0 +1,354 @@
+//! Characters module
+//! Emoji and Unicode character picker: `:smile` searches emoji by shortcode and name,
+//! `u arrow right` searches Unicode character names. Activation copies the character.
+
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+use emojis::{Emoji, SkinTone};
+
+use crate::SearchResultData;
+
+/// Prefix that searches emoji
+const EMOJI_PREFIX: char = ':';
+
+/// Keyword that searches Unicode character names
+const UNICODE_KEYWORD: &str = "u";
+
+/// Most recently used characters kept in the settings
+pub const MAX_RECENT: usize = 20;
+
+/// Results listed for one query
+const MAX_RESULTS: usize = 30;
+
+/// Skin tones offered for single-person emoji, in picker order
+const SKIN_TONES: &[SkinTone] = &[
+    SkinTone::Light,
+    SkinTone::MediumLight,
+    SkinTone::Medium,
+    SkinTone::MediumDark,
+    SkinTone::Dark,
+];
+
+/// A character query
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CharacterQuery<'a> {
+    Emoji(&'a str),
+    Unicode(&'a str),
+}
+
+/// Parse `:smile` or `u arrow right`
+pub fn parse_query(query: &str) -> Option<CharacterQuery<'_>> {
+    let query = query.trim_start();
+    if let Some(terms) = query.strip_prefix(EMOJI_PREFIX) {
+        // "::" and ":)" are not emoji searches
+        if terms.is_empty() || terms.starts_with(|c: char| c.is_alphanumeric()) {
+            return Some(CharacterQuery::Emoji(terms.trim()));
+        }
+        return None;
+    }
+
+    let rest = query
+        .get(..UNICODE_KEYWORD.len())
+        .filter(|keyword| keyword.eq_ignore_ascii_case(UNICODE_KEYWORD))
+        .map(|_| &query[UNICODE_KEYWORD.len()..])?;
+    if rest.starts_with(char::is_whitespace) {
+        Some(CharacterQuery::Unicode(rest.trim()))
+    } else {
+        None
+    }
+}
+
+/// Skin tone from the settings ("medium-dark")
+pub fn skin_tone_from_name(name: &str) -> Option<SkinTone> {
+    match name.trim().to_lowercase().replace(['_', ' '], "-").as_str() {
+        "light" => Some(SkinTone::Light),
+        "medium-light" => Some(SkinTone::MediumLight),
+        "medium" => Some(SkinTone::Medium),
+        "medium-dark" => Some(SkinTone::MediumDark),
+        "dark" => Some(SkinTone::Dark),
+        _ => None,
+    }
+}
+
+/// "U+1F44B", or "U+1F44B U+1F3FD" for sequences
+pub fn code_points(text: &str) -> String {
+    text.chars()
+        .filter(|&c| c != '\u{FE0F}')
+        .map(|c| format!("U+{:04X}", c as u32))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Emoji matching the terms: exact shortcodes first, then shortcode prefixes,
+/// then names with a word starting with every term
+fn search_emoji(terms: &str) -> Vec<&'static Emoji> {
+    let terms: Vec<String> = terms
+        .split_whitespace()
+        .map(|term| term.trim_matches(':').to_lowercase())
+        .filter(|term| !term.is_empty())
+        .collect();
+    let joined = terms.join("_");
+
+    let mut matches: Vec<(u8, &'static Emoji)> = emojis::iter()
+        .filter_map(|emoji| {
+            let rank = if emoji.shortcodes().any(|code| code == joined) {
+                0
+            } else if emoji.shortcodes().any(|code| code.starts_with(&joined)) {
+                1
+            } else if terms.iter().all(|term| {
+                emoji.name().split(|c: char| !c.is_alphanumeric()).any(|word| word.starts_with(term.as_str()))
+            }) {
+                2
+            } else {
+                return None;
+            };
+            Some((rank, emoji))
+        })
+        .collect();
+
+    // Stable sort keeps the Unicode ordering within each rank
+    matches.sort_by_key(|(rank, _)| *rank);
+    matches.into_iter().map(|(_, emoji)| emoji).collect()
+}
+
+/// Named characters, built once by `preload_unicode_names`
+static UNICODE_NAMES: OnceLock<Vec<(char, String)>> = OnceLock::new();
+
+/// Build the Unicode name table on a background thread, so the first `u` query
+/// doesn't stall the UI while ~200k names are collected
+pub fn preload_unicode_names() {
+    std::thread::spawn(|| {
+        log::debug!("Indexed {} Unicode character names", unicode_names().len());
+    });
+}
+
+/// Named characters, excluding ideographs whose names are just their code point
+fn unicode_names() -> &'static [(char, String)] {
+    UNICODE_NAMES.get_or_init(|| {
+        (0x20..=0x2FFFF)
+            .filter_map(char::from_u32)
+            .filter_map(|c| Some((c, unicode_names2::name(c)?.to_string().to_lowercase())))
+            .filter(|(_, name)| !name.contains(" ideograph-"))
+            .collect()
+    })
+}
+
+/// Characters whose name contains every term, or the character with a given code point.
+/// `None` while the name table is still being built.
+fn search_unicode(terms: &str) -> Option<Vec<(char, String)>> {
+    let terms = terms.to_lowercase();
+
+    // "u+2192" or "2192" looks up a code point directly; "add" or "face" are names
+    let hex = match terms.strip_prefix("u+") {
+        Some(hex) => Some(hex),
+        None => Some(terms.as_str()).filter(|hex| hex.contains(|c: char| c.is_ascii_digit())),
+    };
+    if let Some(c) = hex
+        .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+        .and_then(char::from_u32)
+    {
+        if let Some(name) = unicode_names2::name(c) {
+            return Some(vec![(c, name.to_string().to_lowercase())]);
+        }
+    }
+
+    let words: Vec<&str> = terms.split_whitespace().collect();
+    if words.is_empty() {
+        return Some(Vec::new());
+    }
+    let mut matches: Vec<&(char, String)> = UNICODE_NAMES
+        .get()?
+        .iter()
+        .filter(|(_, name)| words.iter().all(|word| name.contains(word)))
+        .collect();
+
+    // Names with fewer words are the more basic characters ("rightwards arrow")
+    matches.sort_by_key(|(_, name)| (name.split_whitespace().count(), name.len()));
+    Some(matches.into_iter().take(MAX_RESULTS).cloned().collect())
+}
+
+fn emoji_result(emoji: &Emoji) -> SearchResultData {
+    // Skin tone variants share the shortcode of the default emoji
+    let shortcode = emoji
+        .shortcode()
+        .or_else(|| emoji.skin_tones()?.next()?.shortcode())
+        .map(|code| format!(" · :{}:", code))
+        .unwrap_or_default();
+    SearchResultData {
+        name: format!("{}  {}", emoji.as_str(), emoji.name()),
+        description: format!("{}{} · Enter to copy", code_points(emoji.as_str()), shortcode),
+        path: PathBuf::from(emoji.as_str()),
+        result_type: "character".to_string(),
+        ..Default::default()
+    }
+}
+
+fn character_result(text: &str) -> SearchResultData {
+    if let Some(emoji) = emojis::get(text) {
+        return emoji_result(emoji);
+    }
+    let name = text
+        .chars()
+        .next()
+        .and_then(unicode_names2::name)
+        .map(|name| name.to_string().to_lowercase())
+        .unwrap_or_default();
+    SearchResultData {
+        name: format!("{}  {}", text, name),
+        description: format!("{} · Enter to copy", code_points(text)),
+        path: PathBuf::from(text),
+        result_type: "character".to_string(),
+        ..Default::default()
+    }
+}
+
+/// Results for a character query. An empty query lists recently used characters;
+/// an exact emoji shortcode also lists the emoji's skin tones.
+pub fn search_results(query: CharacterQuery, recent: &[String], skin_tone: Option<SkinTone>) -> Vec<SearchResultData> {
+    let terms = match query {
+        CharacterQuery::Emoji(terms) | CharacterQuery::Unicode(terms) => terms,
+    };
+    if terms.is_empty() {
+        if recent.is_empty() {
+            let hint = match query {
+                CharacterQuery::Emoji(_) => "Type a name or shortcode, e.g. :smile or :thumbsup",
+                CharacterQuery::Unicode(_) => "Type a character name or code point, e.g. u arrow right or u 2192",
+            };
+            return vec![SearchResultData {
+                name: "No recently used characters".to_string(),
+                description: hint.to_string(),
+                result_type: "info".to_string(),
+                ..Default::default()
+            }];
+        }
+        return recent.iter().map(|text| character_result(text)).collect();
+    }
+
+    let mut results: Vec<SearchResultData> = match query {
+        CharacterQuery::Emoji(terms) => {
+            let matches = search_emoji(terms);
+            let mut results: Vec<SearchResultData> = Vec::new();
+            for (i, emoji) in matches.iter().take(MAX_RESULTS).enumerate() {
+                let toned = skin_tone.and_then(|tone| emoji.with_skin_tone(tone)).unwrap_or(emoji);
+                results.push(emoji_result(toned));
+
+                // List every tone for an exact shortcode match ("wave")
+                let exact = i == 0 && emoji.shortcodes().any(|code| code == terms.trim_matches(':'));
+                if exact && emoji.skin_tones().is_some_and(|tones| tones.count() == SKIN_TONES.len() + 1) {
+                    results.extend(
+                        SKIN_TONES
+                            .iter()
+                            .filter(|&&tone| Some(tone) != skin_tone)
+                            .filter_map(|&tone| emoji.with_skin_tone(tone))
+                            .map(emoji_result),
+                    );
+                }
+            }
+            results
+        }
+        CharacterQuery::Unicode(terms) => match search_unicode(terms) {
+            Some(matches) => matches
+                .into_iter()
+                .map(|(c, name)| SearchResultData {
+                    name: format!("{}  {}", c, name),
+                    description: format!("{} · Enter to copy", code_points(&c.to_string())),
+                    path: PathBuf::from(c.to_string()),
+                    result_type: "character".to_string(),
+                    ..Default::default()
+                })
+                .collect(),
+            None => {
+                return vec![SearchResultData {
+                    name: "Loading character names…".to_string(),
+                    description: "Unicode names are still being indexed, try again in a moment".to_string(),
+                    result_type: "info".to_string(),
+                    ..Default::default()
+                }];
+            }
+        },
+    };
+
+    // Recently used characters first, keeping the match order otherwise
+    results.sort_by_key(|result| {
+        recent
+            .iter()
+            .position(|text| result.path.as_os_str() == text.as_str())
+            .unwrap_or(usize::MAX)
+    });
+
+    if results.is_empty() {
+        results.push(SearchResultData {
+            name: "No matching characters".to_string(),
+            description: format!("Nothing found for \"{}\"", terms),
+            result_type: "info".to_string(),
+            ..Default::default()
+        });
+    }
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn characters(results: &[SearchResultData]) -> Vec<String> {
+        results.iter().map(|r| r.path.to_string_lossy().into_owned()).collect()
+    }
+
+    #[test]
+    fn test_parse_query() {
+        assert_eq!(parse_query(":smile"), Some(CharacterQuery::Emoji("smile")));
+        assert_eq!(parse_query(":"), Some(CharacterQuery::Emoji("")));
+        assert_eq!(parse_query(":)"), None);
+        assert_eq!(parse_query("u arrow right"), Some(CharacterQuery::Unicode("arrow right")));
+        assert_eq!(parse_query("U "), Some(CharacterQuery::Unicode("")));
+        assert_eq!(parse_query("update"), None);
+    }
+
+    #[test]
+    fn test_emoji_search() {
+        let results = search_results(CharacterQuery::Emoji("smile"), &[], None);
+        assert_eq!(results[0].path, PathBuf::from("😄"));
+        assert_eq!(results[0].description, "U+1F604 · :smile: · Enter to copy");
+
+        let hearts = search_results(CharacterQuery::Emoji("red heart"), &[], None);
+        assert!(characters(&hearts).contains(&"❤️".to_string()));
+    }
+
+    #[test]
+    fn test_skin_tones() {
+        let wave = characters(&search_results(CharacterQuery::Emoji("wave"), &[], None));
+        assert_eq!(wave[..6], ["👋", "👋🏻", "👋🏼", "👋🏽", "👋🏾", "👋🏿"]);
+
+        let toned = search_results(CharacterQuery::Emoji("wave"), &[], skin_tone_from_name("Medium Dark"));
+        assert_eq!(toned[0].path, PathBuf::from("👋🏾"));
+        assert_eq!(toned[0].description, "U+1F44B U+1F3FE · :wave: · Enter to copy");
+    }
+
+    #[test]
+    fn test_unicode_search() {
+        // Code points resolve before the name table is built
+        assert_eq!(characters(&search_results(CharacterQuery::Unicode("U+2192"), &[], None)), ["→"]);
+
+        unicode_names();
+        let arrows = search_results(CharacterQuery::Unicode("arrow right"), &[], None);
+        assert_eq!(arrows[0].name, "→  rightwards arrow");
+        assert_eq!(arrows[0].description, "U+2192 · Enter to copy");
+
+        let by_code = search_results(CharacterQuery::Unicode("U+00E9"), &[], None);
+        assert_eq!(characters(&by_code), ["é"]);
+        assert_eq!(characters(&search_results(CharacterQuery::Unicode("2192"), &[], None)), ["→"]);
+        assert_ne!(characters(&search_results(CharacterQuery::Unicode("face"), &[], None)), ["\u{FACE}"]);
+    }
+
+    #[test]
+    fn test_recent_characters() {
+        let recent = vec!["→".to_string(), "😄".to_string()];
+        let listed = search_results(CharacterQuery::Emoji(""), &recent, None);
+        assert_eq!(characters(&listed), ["→", "😄"]);
+
+        let ranked = search_results(CharacterQuery::Emoji("smil"), &recent, None);
+        assert_eq!(ranked[0].path, PathBuf::from("😄"));
+    }
+}
@@ -0,0 +1,444 @@
+//! Clipboard module
+//! Copies result values (calculator output, file paths, URLs) to the clipboard through a
+//! swappable backend: the Windows clipboard in the app, an in-memory one in tests
+
+use std::error::Error;
+#[cfg(any(test, not(windows)))]
+use std::sync::{Arc, Mutex};
+
+use crate::SearchResultData;
+
+/// Text read from the clipboard
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClipboardContent {
+    pub text: String,
+
+    /// The source asked clipboard managers not to record this content
+    /// (password managers set this flag on copied secrets)
+    pub excluded: bool,
+}
+
+/// Storage the clipboard service reads from and writes to
+pub trait ClipboardBackend: Send {
+    /// Replace the clipboard with text; `excluded` asks clipboard managers and the
+    /// Windows clipboard history not to record it
+    fn set_text(&mut self, text: &str, excluded: bool) -> Result<(), Box<dyn Error>>;
+
+    /// Current text content, if the clipboard holds text
+    fn read(&mut self) -> Result<Option<ClipboardContent>, Box<dyn Error>>;
+
+    /// Counter that changes whenever the clipboard contents change
+    fn change_count(&mut self) -> u32;
+}
+
+#[cfg(any(test, not(windows)))]
+#[derive(Debug, Default)]
+struct MemoryState {
+    content: Option<ClipboardContent>,
+    changes: u32,
+}
+
+/// Clipboard kept in memory, used in tests and on platforms without a native backend.
+/// Clones share the same contents.
+#[cfg(any(test, not(windows)))]
+#[derive(Debug, Default, Clone)]
+pub struct MemoryClipboard {
+    state: Arc<Mutex<MemoryState>>,
+}
+
+#[cfg(any(test, not(windows)))]
+impl MemoryClipboard {
+    /// Simulate another application copying text
+    #[cfg(test)]
+    pub fn copy_from_app(&self, text: &str, excluded: bool) {
+        let mut state = self.state.lock().unwrap();
+        state.content = Some(ClipboardContent {
+            text: text.to_string(),
+            excluded,
+        });
+        state.changes += 1;
+    }
+}
+
+#[cfg(any(test, not(windows)))]
+impl ClipboardBackend for MemoryClipboard {
+    fn set_text(&mut self, text: &str, excluded: bool) -> Result<(), Box<dyn Error>> {
+        let mut state = self.state.lock().map_err(|_| "Clipboard lock poisoned")?;
+        state.content = Some(ClipboardContent {
+            text: text.to_string(),
+            excluded,
+        });
+        state.changes += 1;
+        Ok(())
+    }
+
+    fn read(&mut self) -> Result<Option<ClipboardContent>, Box<dyn Error>> {
+        let state = self.state.lock().map_err(|_| "Clipboard lock poisoned")?;
+        Ok(state.content.clone())
+    }
+
+    fn change_count(&mut self) -> u32 {
+        self.state.lock().map(|state| state.changes).unwrap_or_default()
+    }
+}
+
+/// The system clipboard via the Win32 clipboard API
+#[cfg(windows)]
+pub struct WindowsClipboard;
+
+#[cfg(windows)]
+impl WindowsClipboard {
+    /// Standard clipboard format for UTF-16 text
+    const CF_UNICODETEXT: u32 = 13;
+
+    /// Open the clipboard, retrying briefly since other applications may hold it
+    fn open() -> Result<(), Box<dyn Error>> {
+        use windows::Win32::Foundation::HWND;
+        use windows::Win32::System::DataExchange::OpenClipboard;
+
+        let mut last_error = None;
+        for _ in 0..5 {
+            match unsafe { OpenClipboard(HWND::default()) } {
+                Ok(()) => return Ok(()),
+                Err(e) => last_error = Some(e),
+            }
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+        Err(format!("Failed to open clipboard: {:?}", last_error).into())
+    }
+
+    /// Whether the clipboard holds a registered format, e.g. a clipboard manager flag
+    fn has_format(name: &str) -> bool {
+        use windows::core::PCWSTR;
+        use windows::Win32::System::DataExchange::{IsClipboardFormatAvailable, RegisterClipboardFormatW};
+
+        let wide: Vec<u16> = name.encode_utf16().chain(std::iter::once(0)).collect();
+        unsafe {
+            let format = RegisterClipboardFormatW(PCWSTR(wide.as_ptr()));
+            format != 0 && IsClipboardFormatAvailable(format).is_ok()
+        }
+    }
+
+    /// DWORD held in a registered flag format on the open clipboard
+    unsafe fn flag_value(name: &str) -> Option<u32> {
+        use windows::core::PCWSTR;
+        use windows::Win32::Foundation::HGLOBAL;
+        use windows::Win32::System::DataExchange::{GetClipboardData, RegisterClipboardFormatW};
+        use windows::Win32::System::Memory::{GlobalLock, GlobalSize, GlobalUnlock};
+
+        let wide: Vec<u16> = name.encode_utf16().chain(std::iter::once(0)).collect();
+        let format = RegisterClipboardFormatW(PCWSTR(wide.as_ptr()));
+        if format == 0 {
+            return None;
+        }
+        let memory = HGLOBAL(GetClipboardData(format).ok()?.0);
+        if GlobalSize(memory) < std::mem::size_of::<u32>() {
+            return None;
+        }
+        let source = GlobalLock(memory) as *const u32;
+        if source.is_null() {
+            return None;
+        }
+        let value = source.read_unaligned();
+        let _ = GlobalUnlock(memory);
+        Some(value)
+    }
+
+    /// Whether the source opted out of clipboard history and monitoring, with the
+    /// clipboard open. The monitoring and viewer flags count by their presence;
+    /// `CanIncludeInClipboardHistory` is a DWORD that excludes only when 0, since apps
+    /// also set it to 1 to explicitly allow history.
+    unsafe fn is_excluded() -> bool {
+        ["ExcludeClipboardContentFromMonitorProcessing", "Clipboard Viewer Ignore"]
+            .iter()
+            .any(|name| Self::has_format(name))
+            || Self::flag_value("CanIncludeInClipboardHistory") == Some(0)
+    }
+
+    /// Put a registered flag format on the open clipboard. Windows reads a DWORD 0 in
+    /// `CanIncludeInClipboardHistory` and `CanUploadToCloudClipboard` as "no"; for
+    /// `ExcludeClipboardContentFromMonitorProcessing` only its presence matters.
+    unsafe fn set_flag(name: &str) -> Result<(), Box<dyn Error>> {
+        use windows::core::PCWSTR;
+        use windows::Win32::Foundation::{GlobalFree, HANDLE};
+        use windows::Win32::System::DataExchange::{RegisterClipboardFormatW, SetClipboardData};
+        use windows::Win32::System::Memory::{GlobalAlloc, GlobalLock, GlobalUnlock, GMEM_MOVEABLE};
+
+        let wide: Vec<u16> = name.encode_utf16().chain(std::iter::once(0)).collect();
+        let format = RegisterClipboardFormatW(PCWSTR(wide.as_ptr()));
+        if format == 0 {
+            return Err(format!("Failed to register clipboard format {}", name).into());
+        }
+
+        let memory = GlobalAlloc(GMEM_MOVEABLE, std::mem::size_of::<u32>())?;
+        let target = GlobalLock(memory) as *mut u32;
+        if target.is_null() {
+            let _ = GlobalFree(memory);
+            return Err("Failed to lock clipboard memory".into());
+        }
+        *target = 0;
+        let _ = GlobalUnlock(memory);
+
+        if let Err(e) = SetClipboardData(format, HANDLE(memory.0)) {
+            let _ = GlobalFree(memory);
+            return Err(e.into());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(windows)]
+impl ClipboardBackend for WindowsClipboard {
+    fn set_text(&mut self, text: &str, excluded: bool) -> Result<(), Box<dyn Error>> {
+        use windows::Win32::Foundation::{GlobalFree, HANDLE};
+        use windows::Win32::System::DataExchange::{CloseClipboard, EmptyClipboard, SetClipboardData};
+        use windows::Win32::System::Memory::{GlobalAlloc, GlobalLock, GlobalUnlock, GMEM_MOVEABLE};
+
+        let wide: Vec<u16> = text.encode_utf16().chain(std::iter::once(0)).collect();
+
+        Self::open()?;
+        let result = unsafe {
+            (|| -> Result<(), Box<dyn Error>> {
+                EmptyClipboard()?;
+
+                let memory = GlobalAlloc(GMEM_MOVEABLE, wide.len() * std::mem::size_of::<u16>())?;
+                let target = GlobalLock(memory) as *mut u16;
+                if target.is_null() {
+                    let _ = GlobalFree(memory);
+                    return Err("Failed to lock clipboard memory".into());
+                }
+                std::ptr::copy_nonoverlapping(wide.as_ptr(), target, wide.len());
+                let _ = GlobalUnlock(memory);
+
+                // On success the clipboard owns the memory
+                if let Err(e) = SetClipboardData(Self::CF_UNICODETEXT, HANDLE(memory.0)) {
+                    let _ = GlobalFree(memory);
+                    return Err(e.into());
+                }
+
+                if excluded {
+                    for name in [
+                        "ExcludeClipboardContentFromMonitorProcessing",
+                        "CanIncludeInClipboardHistory",
+                        "CanUploadToCloudClipboard",
+                    ] {
+                        Self::set_flag(name)?;
+                    }
+                }
+                Ok(())
+            })()
+        };
+        unsafe {
+            let _ = CloseClipboard();
+        }
+        result
+    }
+
+    fn read(&mut self) -> Result<Option<ClipboardContent>, Box<dyn Error>> {
+        use windows::Win32::Foundation::HGLOBAL;
+        use windows::Win32::System::DataExchange::{
+            CloseClipboard, GetClipboardData, IsClipboardFormatAvailable,
+        };
+        use windows::Win32::System::Memory::{GlobalLock, GlobalUnlock};
+
+        if unsafe { IsClipboardFormatAvailable(Self::CF_UNICODETEXT) }.is_err() {
+            return Ok(None);
+        }
+
+        Self::open()?;
+        let result = unsafe {
+            (|| -> Result<Option<ClipboardContent>, Box<dyn Error>> {
+                let excluded = Self::is_excluded();
+                let handle = GetClipboardData(Self::CF_UNICODETEXT)?;
+                let memory = HGLOBAL(handle.0);
+                let source = GlobalLock(memory) as *const u16;
+                if source.is_null() {
+                    return Ok(None);
+                }
+                let len = (0..).take_while(|&i| *source.add(i) != 0).count();
+                let text = String::from_utf16_lossy(std::slice::from_raw_parts(source, len));
+                let _ = GlobalUnlock(memory);
+                Ok(Some(ClipboardContent { text, excluded }))
+            })()
+        };
+        unsafe {
+            let _ = CloseClipboard();
+        }
+        result
+    }
+
+    fn change_count(&mut self) -> u32 {
+        unsafe { windows::Win32::System::DataExchange::GetClipboardSequenceNumber() }
+    }
+}
+
+/// Clipboard access for result actions
+pub struct ClipboardService {
+    backend: Box<dyn ClipboardBackend>,
+
+    /// Change counter seen by the last `read_if_changed`
+    last_change: Option<u32>,
+}
+
+impl ClipboardService {
+    pub fn new(backend: Box<dyn ClipboardBackend>) -> Self {
+        Self {
+            backend,
+            last_change: None,
+        }
+    }
+
+    /// Service backed by the system clipboard
+    pub fn system() -> Self {
+        #[cfg(windows)]
+        {
+            Self::new(Box::new(WindowsClipboard))
+        }
+
+        #[cfg(not(windows))]
+        {
+            log::warn!("No system clipboard on this platform, copies stay in memory");
+            Self::new(Box::<MemoryClipboard>::default())
+        }
+    }
+
+    /// Copy text to the clipboard
+    pub fn copy(&mut self, text: &str) -> Result<(), Box<dyn Error>> {
+        self.backend.set_text(text, false)?;
+        log::info!("Copied {} characters to the clipboard", text.chars().count());
+        Ok(())
+    }
+
+    /// Copy a secret such as a generated password: kept out of clipboard history and the log
+    pub fn copy_secret(&mut self, text: &str) -> Result<(), Box<dyn Error>> {
+        self.backend.set_text(text, true)?;
+        log::info!("Copied a secret to the clipboard");
+        Ok(())
+    }
+
+    /// Current clipboard text, if it holds text
+    pub fn text(&mut self) -> Result<Option<String>, Box<dyn Error>> {
+        Ok(self.backend.read()?.map(|content| content.text))
+    }
+
+    /// Clipboard text, when it changed since the previous call
+    pub fn read_if_changed(&mut self) -> Result<Option<ClipboardContent>, Box<dyn Error>> {
+        let change = self.backend.change_count();
+        if self.last_change == Some(change) {
+            return Ok(None);
+        }
+        self.last_change = Some(change);
+        self.backend.read()
+    }
+
+    /// Copy the value a result stands for; returns the copied text, or `None` when the
+    /// result has nothing worth copying
+    pub fn copy_result(&mut self, result: &SearchResultData) -> Result<Option<String>, Box<dyn Error>> {
+        match copy_text(result) {
+            Some(text) => {
+                if result.is_secret() {
+                    self.copy_secret(&text)?;
+                } else {
+                    self.copy(&text)?;
+                }
+                Ok(Some(text))
+            }
+            None => Ok(None),
+        }
+    }
+}
+
+/// Text copied for a result: the calculator value, the file path, the URL or command output
+pub fn copy_text(result: &SearchResultData) -> Option<String> {
+    match result.result_type.as_str() {
+        "calc" => Some(match &result.calc {
+            Some(entry) => entry.display.clone(),
+            None => result.path.to_string_lossy().into_owned(),
+        }),
+        "shell-output" => result.output.clone(),
+        "app" | "file" | "web" | "bookmark" | "clipboard" | "character" | "color" | "toolbox" | "generated"
+        | "password" | "ssh" | "workspace" => Some(result.path.to_string_lossy().into_owned()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn result(result_type: &str, path: &str) -> SearchResultData {
+        SearchResultData {
+            name: "Result".to_string(),
+            path: PathBuf::from(path),
+            result_type: result_type.to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn memory_service() -> (ClipboardService, MemoryClipboard) {
+        let memory = MemoryClipboard::default();
+        (ClipboardService::new(Box::new(memory.clone())), memory)
+    }
+
+    fn contents(memory: &MemoryClipboard) -> Option<String> {
+        memory.clone().read().unwrap().map(|content| content.text)
+    }
+
+    #[test]
+    fn test_copy_calculator_value() {
+        let (mut clipboard, memory) = memory_service();
+        let calc = crate::actions::try_calculate("1/3 + 1/6").unwrap();
+
+        assert_eq!(clipboard.copy_result(&calc).unwrap().as_deref(), Some("1/2"));
+        assert_eq!(contents(&memory).as_deref(), Some("1/2"));
+    }
+
+    #[test]
+    fn test_copy_paths_and_urls() {
+        let (mut clipboard, memory) = memory_service();
+
+        clipboard.copy_result(&result("file", r"C:\Users\me\notes.txt")).unwrap();
+        assert_eq!(contents(&memory).as_deref(), Some(r"C:\Users\me\notes.txt"));
+
+        clipboard.copy_result(&result("web", "https://example.com/?q=a%20b")).unwrap();
+        assert_eq!(contents(&memory).as_deref(), Some("https://example.com/?q=a%20b"));
+    }
+
+    #[test]
+    fn test_nothing_to_copy_keeps_clipboard() {
+        let (mut clipboard, memory) = memory_service();
+        clipboard.copy("kept").unwrap();
+
+        assert_eq!(clipboard.copy_result(&result("action", "shutdown")).unwrap(), None);
+        assert_eq!(contents(&memory).as_deref(), Some("kept"));
+    }
+
+    #[test]
+    fn test_copy_secret() {
+        let (mut clipboard, memory) = memory_service();
+        let password = result("password", "hunter2hunter2");
+
+        assert_eq!(clipboard.copy_result(&password).unwrap().as_deref(), Some("hunter2hunter2"));
+        let content = memory.clone().read().unwrap().unwrap();
+        assert_eq!(content.text, "hunter2hunter2");
+        assert!(content.excluded);
+
+        clipboard.copy_result(&result("generated", "3f2c")).unwrap();
+        assert!(!memory.clone().read().unwrap().unwrap().excluded);
+    }
+
+    #[test]
+    fn test_read_if_changed() {
+        let (mut clipboard, memory) = memory_service();
+        assert_eq!(clipboard.read_if_changed().unwrap(), None);
+
+        memory.copy_from_app("secret", true);
+        let content = clipboard.read_if_changed().unwrap().unwrap();
+        assert_eq!(content.text, "secret");
+        assert!(content.excluded);
+
+        // Unchanged clipboard is not reported again
+        assert_eq!(clipboard.read_if_changed().unwrap(), None);
+    }
+}
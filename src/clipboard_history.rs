@@ -0,0 +1,364 @@
+//! Clipboard history module
+//! Records copied text into a bounded local store, searchable with the `cb` keyword.
+//! The store is encrypted with the user's Windows credentials (DPAPI) and can be
+//! cleared from the launcher with `cb clear`.
+
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::clipboard::ClipboardContent;
+use crate::config::AppConfig;
+use crate::SearchResultData;
+
+/// File name of the clipboard history inside the configuration directory
+pub const HISTORY_FILE_NAME: &str = "clipboard_history.dat";
+
+/// Keyword that searches the clipboard history
+const KEYWORD: &str = "cb";
+
+/// Longer clipboard texts are not recorded
+const MAX_ENTRY_CHARS: usize = 10_000;
+
+/// Characters of an entry shown as the result name
+const PREVIEW_CHARS: usize = 80;
+
+/// A recorded clipboard text
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub text: String,
+
+    /// When the text was copied (Unix timestamp in seconds)
+    pub copied_at: i64,
+}
+
+/// Most recent clipboard texts, newest first
+#[derive(Debug)]
+pub struct ClipboardHistory {
+    entries: VecDeque<HistoryEntry>,
+    max_entries: usize,
+    exclude_sensitive: bool,
+    path: Option<PathBuf>,
+}
+
+impl ClipboardHistory {
+    /// Empty history; it is persisted to `path` when one is given
+    pub fn new(max_entries: usize, exclude_sensitive: bool, path: Option<PathBuf>) -> Self {
+        Self {
+            entries: VecDeque::new(),
+            max_entries,
+            exclude_sensitive,
+            path,
+        }
+    }
+
+    /// Load the history stored at `path`, starting empty when it is missing or unreadable
+    pub fn load(max_entries: usize, exclude_sensitive: bool, path: PathBuf) -> Self {
+        let mut history = Self::new(max_entries, exclude_sensitive, Some(path.clone()));
+        if !path.exists() {
+            return history;
+        }
+
+        match read_entries(&path) {
+            Ok(entries) => {
+                history.entries = entries.into_iter().take(max_entries).collect();
+                log::info!("Loaded {} clipboard history entries", history.entries.len());
+            }
+            Err(e) => log::warn!("Failed to load clipboard history: {}. Starting empty.", e),
+        }
+        history
+    }
+
+    /// Record copied text; returns whether the history changed
+    pub fn record(&mut self, content: &ClipboardContent, copied_at: i64) -> bool {
+        if content.excluded && self.exclude_sensitive {
+            log::debug!("Skipping clipboard content excluded by its source");
+            return false;
+        }
+        if content.text.trim().is_empty() || content.text.chars().count() > MAX_ENTRY_CHARS {
+            return false;
+        }
+        if self.entries.front().is_some_and(|entry| entry.text == content.text) {
+            return false;
+        }
+
+        // Copying a text again moves it to the top instead of duplicating it
+        self.entries.retain(|entry| entry.text != content.text);
+        self.entries.push_front(HistoryEntry {
+            text: content.text.clone(),
+            copied_at,
+        });
+        self.entries.truncate(self.max_entries);
+        true
+    }
+
+    /// Entries containing every search term (case-insensitive), newest first
+    pub fn search(&self, terms: &str) -> Vec<&HistoryEntry> {
+        let terms: Vec<String> = terms.split_whitespace().map(str::to_lowercase).collect();
+        self.entries
+            .iter()
+            .filter(|entry| {
+                let text = entry.text.to_lowercase();
+                terms.iter().all(|term| text.contains(term))
+            })
+            .collect()
+    }
+
+    /// Forget all entries and remove the stored file
+    pub fn clear(&mut self) -> Result<(), Box<dyn Error>> {
+        self.entries.clear();
+        if let Some(path) = &self.path {
+            if path.exists() {
+                fs::remove_file(path)?;
+            }
+        }
+        log::info!("Clipboard history cleared");
+        Ok(())
+    }
+
+    /// Write the history to its file, encrypted
+    pub fn save(&self) -> Result<(), Box<dyn Error>> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+
+        let json = serde_json::to_vec(&self.entries)?;
+        fs::write(path, protect(&json)?)?;
+        Ok(())
+    }
+}
+
+fn read_entries(path: &Path) -> Result<Vec<HistoryEntry>, Box<dyn Error>> {
+    let data = unprotect(&fs::read(path)?)?;
+    Ok(serde_json::from_slice(&data)?)
+}
+
+/// Path of the clipboard history file in the configuration directory
+pub fn history_path() -> Option<PathBuf> {
+    AppConfig::config_dir(crate::single_instance::detect_portable_mode())
+        .map(|dir| dir.join(HISTORY_FILE_NAME))
+}
+
+/// Search terms of a `cb` query ("cb", "cb invoice")
+pub fn parse_query(query: &str) -> Option<&str> {
+    let query = query.trim_start();
+    let rest = query
+        .get(..KEYWORD.len())
+        .filter(|keyword| keyword.eq_ignore_ascii_case(KEYWORD))
+        .map(|_| &query[KEYWORD.len()..])?;
+
+    if rest.is_empty() || rest.starts_with(char::is_whitespace) {
+        Some(rest.trim())
+    } else {
+        None
+    }
+}
+
+/// Results for a `cb` query; `history` is `None` when clipboard history is disabled
+pub fn search_results(history: Option<&ClipboardHistory>, terms: &str, now: i64) -> Vec<SearchResultData> {
+    let Some(history) = history else {
+        return vec![info_result(
+            "Clipboard history is off",
+            "Set clipboard.history_enabled in the settings file to record copied text",
+        )];
+    };
+
+    if terms.eq_ignore_ascii_case("clear") {
+        return vec![SearchResultData {
+            name: "Clear Clipboard History".to_string(),
+            description: format!("Forget {} recorded clipboard entries", history.entries.len()),
+            path: PathBuf::from("clear"),
+            result_type: "clipboard-clear".to_string(),
+            ..Default::default()
+        }];
+    }
+
+    let results: Vec<SearchResultData> = history
+        .search(terms)
+        .into_iter()
+        .map(|entry| SearchResultData {
+            name: preview(&entry.text),
+            description: format!("Copied {} · Enter to copy again", format_age(now - entry.copied_at)),
+            path: PathBuf::from(&entry.text),
+            result_type: "clipboard".to_string(),
+            ..Default::default()
+        })
+        .collect();
+
+    if results.is_empty() {
+        let name = if terms.is_empty() {
+            "Clipboard history is empty"
+        } else {
+            "No matching clipboard entries"
+        };
+        return vec![info_result(name, "Type \"cb clear\" to forget recorded entries")];
+    }
+    results
+}
+
+fn info_result(name: &str, description: &str) -> SearchResultData {
+    SearchResultData {
+        name: name.to_string(),
+        description: description.to_string(),
+        result_type: "info".to_string(),
+        ..Default::default()
+    }
+}
+
+/// First line of an entry, shortened for display
+fn preview(text: &str) -> String {
+    let first_line = text.trim().lines().next().unwrap_or_default();
+    let mut preview: String = first_line.chars().take(PREVIEW_CHARS).collect();
+    if first_line.chars().count() > PREVIEW_CHARS || text.trim().lines().nth(1).is_some() {
+        preview.push('…');
+    }
+    preview
+}
+
+fn format_age(seconds: i64) -> String {
+    match seconds {
+        s if s < 60 => "just now".to_string(),
+        s if s < 3600 => format!("{} min ago", s / 60),
+        s if s < 86400 => format!("{} h ago", s / 3600),
+        s => format!("{} days ago", s / 86400),
+    }
+}
+
+/// Encrypt data for the current Windows user with DPAPI
+#[cfg(windows)]
+fn protect(data: &[u8]) -> Result<Vec<u8>, Box<dyn Error>> {
+    use windows::Win32::Security::Cryptography::{CryptProtectData, CRYPT_INTEGER_BLOB};
+
+    let input = CRYPT_INTEGER_BLOB {
+        cbData: data.len() as u32,
+        pbData: data.as_ptr() as *mut u8,
+    };
+    let mut output = CRYPT_INTEGER_BLOB::default();
+    unsafe {
+        CryptProtectData(&input, None, None, None, None, 0, &mut output)?;
+        Ok(take_blob(output))
+    }
+}
+
+/// Decrypt data written by `protect`
+#[cfg(windows)]
+fn unprotect(data: &[u8]) -> Result<Vec<u8>, Box<dyn Error>> {
+    use windows::Win32::Security::Cryptography::{CryptUnprotectData, CRYPT_INTEGER_BLOB};
+
+    let input = CRYPT_INTEGER_BLOB {
+        cbData: data.len() as u32,
+        pbData: data.as_ptr() as *mut u8,
+    };
+    let mut output = CRYPT_INTEGER_BLOB::default();
+    unsafe {
+        CryptUnprotectData(&input, None, None, None, None, 0, &mut output)?;
+        Ok(take_blob(output))
+    }
+}
+
+/// Copy a DPAPI output blob and release its memory
+#[cfg(windows)]
+unsafe fn take_blob(blob: windows::Win32::Security::Cryptography::CRYPT_INTEGER_BLOB) -> Vec<u8> {
+    use windows::Win32::Foundation::{LocalFree, HLOCAL};
+
+    let bytes = std::slice::from_raw_parts(blob.pbData, blob.cbData as usize).to_vec();
+    let _ = LocalFree(HLOCAL(blob.pbData as *mut _));
+    bytes
+}
+
+/// DPAPI is Windows-only; elsewhere the history is stored as plain JSON
+#[cfg(not(windows))]
+fn protect(data: &[u8]) -> Result<Vec<u8>, Box<dyn Error>> {
+    Ok(data.to_vec())
+}
+
+#[cfg(not(windows))]
+fn unprotect(data: &[u8]) -> Result<Vec<u8>, Box<dyn Error>> {
+    Ok(data.to_vec())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn copied(text: &str) -> ClipboardContent {
+        ClipboardContent {
+            text: text.to_string(),
+            excluded: false,
+        }
+    }
+
+    #[test]
+    fn test_record_bounded_and_deduplicated() {
+        let mut history = ClipboardHistory::new(3, true, None);
+        assert!(history.record(&copied("one"), 1));
+        assert!(history.record(&copied("two"), 2));
+        assert!(!history.record(&copied("two"), 3));
+        assert!(history.record(&copied("one"), 4));
+        assert!(history.record(&copied("three"), 5));
+        assert!(history.record(&copied("four"), 6));
+        assert!(!history.record(&copied("   "), 7));
+
+        let texts: Vec<_> = history.search("").iter().map(|e| e.text.as_str()).collect();
+        assert_eq!(texts, ["four", "three", "one"]);
+    }
+
+    #[test]
+    fn test_excluded_content() {
+        let secret = ClipboardContent {
+            text: "hunter2".to_string(),
+            excluded: true,
+        };
+
+        let mut history = ClipboardHistory::new(10, true, None);
+        assert!(!history.record(&secret, 1));
+
+        let mut history = ClipboardHistory::new(10, false, None);
+        assert!(history.record(&secret, 1));
+    }
+
+    #[test]
+    fn test_parse_query() {
+        assert_eq!(parse_query("cb"), Some(""));
+        assert_eq!(parse_query("CB  invoice 42 "), Some("invoice 42"));
+        assert_eq!(parse_query("cbx"), None);
+        assert_eq!(parse_query("calc"), None);
+    }
+
+    #[test]
+    fn test_search_results() {
+        let mut history = ClipboardHistory::new(10, true, None);
+        history.record(&copied("Invoice #42\nTotal: 100"), 1_000);
+        history.record(&copied("https://example.com"), 1_000);
+
+        let results = search_results(Some(&history), "invoice", 1_120);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].name, "Invoice #42…");
+        assert_eq!(results[0].description, "Copied 2 min ago · Enter to copy again");
+        assert_eq!(results[0].result_type, "clipboard");
+
+        assert_eq!(search_results(Some(&history), "clear", 0)[0].result_type, "clipboard-clear");
+        assert_eq!(search_results(None, "", 0)[0].result_type, "info");
+    }
+
+    #[test]
+    fn test_save_load_and_clear() {
+        let path = std::env::temp_dir().join(format!("nexus_cb_history_{}.dat", std::process::id()));
+
+        let mut history = ClipboardHistory::new(10, true, Some(path.clone()));
+        history.record(&copied("saved text"), 1);
+        history.save().unwrap();
+
+        let mut loaded = ClipboardHistory::load(10, true, path.clone());
+        assert_eq!(loaded.search("saved").len(), 1);
+
+        loaded.clear().unwrap();
+        assert!(!path.exists());
+        assert!(loaded.search("").is_empty());
+    }
+}
@@ -0,0 +1,497 @@
+//! Colors module
+//! Recognizes pasted color codes (`#1e90ff`, `rgb(30,144,255)`, `hsl(210 100% 56%)`, ...)
+//! and lists the color in every common notation, each copied with Enter
+
+use std::path::PathBuf;
+
+use crate::SearchResultData;
+
+/// An sRGB color
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rgb {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+/// Hue in degrees, saturation and lightness (or value) as fractions
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Hsx {
+    pub h: f64,
+    pub s: f64,
+    pub x: f64,
+}
+
+/// Cyan, magenta, yellow and key (black) as fractions
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Cmyk {
+    pub c: f64,
+    pub m: f64,
+    pub y: f64,
+    pub k: f64,
+}
+
+/// Parse a hex, `rgb()`/`rgba()`, `hsl()`/`hsla()`, `hsv()` or `cmyk()` color.
+/// Alpha is accepted but ignored.
+pub fn parse(query: &str) -> Option<Rgb> {
+    let query = query.trim().to_lowercase();
+    if let Some(hex) = query.strip_prefix('#') {
+        return parse_hex(hex);
+    }
+
+    let (function, args) = query.strip_suffix(')')?.split_once('(')?;
+    let args: Vec<&str> = args
+        .split(|c: char| c == ',' || c == '/' || c.is_whitespace())
+        .filter(|arg| !arg.is_empty())
+        .collect();
+    if !(3..=4).contains(&args.len()) {
+        return None;
+    }
+
+    let percent = |arg: &str| -> Option<f64> {
+        let value = arg.strip_suffix('%').unwrap_or(arg).parse::<f64>().ok()?;
+        (0.0..=100.0).contains(&value).then_some(value / 100.0)
+    };
+    let hue = |arg: &str| arg.strip_suffix("deg").unwrap_or(arg).parse::<f64>().ok();
+
+    match function.trim() {
+        "rgb" | "rgba" => {
+            let channel = |arg: &str| -> Option<u8> {
+                let value = match arg.strip_suffix('%') {
+                    Some(percent) => percent.parse::<f64>().ok()? * 2.55,
+                    None => arg.parse::<f64>().ok()?,
+                };
+                (0.0..=255.0).contains(&value).then(|| value.round() as u8)
+            };
+            Some(Rgb {
+                r: channel(args[0])?,
+                g: channel(args[1])?,
+                b: channel(args[2])?,
+            })
+        }
+        "hsl" | "hsla" => Some(from_hsl(Hsx {
+            h: hue(args[0])?,
+            s: percent(args[1])?,
+            x: percent(args[2])?,
+        })),
+        "hsv" | "hsb" => Some(from_hsv(Hsx {
+            h: hue(args[0])?,
+            s: percent(args[1])?,
+            x: percent(args[2])?,
+        })),
+        "cmyk" if args.len() == 4 => Some(from_cmyk(Cmyk {
+            c: percent(args[0])?,
+            m: percent(args[1])?,
+            y: percent(args[2])?,
+            k: percent(args[3])?,
+        })),
+        _ => None,
+    }
+}
+
+/// "1e90ff", "1e90ffcc", "09f" or "09fc"
+fn parse_hex(hex: &str) -> Option<Rgb> {
+    if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    let channel = |digits: &str| u8::from_str_radix(digits, 16).ok();
+    match hex.len() {
+        3 | 4 => {
+            // Each digit is doubled: "09f" is "0099ff"
+            let digit = |i: usize| channel(&hex[i..i + 1]).map(|d| d * 17);
+            Some(Rgb {
+                r: digit(0)?,
+                g: digit(1)?,
+                b: digit(2)?,
+            })
+        }
+        6 | 8 => Some(Rgb {
+            r: channel(&hex[0..2])?,
+            g: channel(&hex[2..4])?,
+            b: channel(&hex[4..6])?,
+        }),
+        _ => None,
+    }
+}
+
+impl Rgb {
+    fn fractions(self) -> (f64, f64, f64) {
+        (self.r as f64 / 255.0, self.g as f64 / 255.0, self.b as f64 / 255.0)
+    }
+
+    /// Hue from the largest channel and the spread between largest and smallest
+    fn hue(self) -> f64 {
+        let (r, g, b) = self.fractions();
+        let max = r.max(g).max(b);
+        let delta = max - r.min(g).min(b);
+        let hue = if delta == 0.0 {
+            0.0
+        } else if max == r {
+            60.0 * (((g - b) / delta).rem_euclid(6.0))
+        } else if max == g {
+            60.0 * ((b - r) / delta + 2.0)
+        } else {
+            60.0 * ((r - g) / delta + 4.0)
+        };
+        hue.rem_euclid(360.0)
+    }
+
+    pub fn to_hsl(self) -> Hsx {
+        let (r, g, b) = self.fractions();
+        let (max, min) = (r.max(g).max(b), r.min(g).min(b));
+        let l = (max + min) / 2.0;
+        let s = if max == min { 0.0 } else { (max - min) / (1.0 - (2.0 * l - 1.0).abs()) };
+        Hsx { h: self.hue(), s, x: l }
+    }
+
+    pub fn to_hsv(self) -> Hsx {
+        let (r, g, b) = self.fractions();
+        let (max, min) = (r.max(g).max(b), r.min(g).min(b));
+        let s = if max == 0.0 { 0.0 } else { (max - min) / max };
+        Hsx { h: self.hue(), s, x: max }
+    }
+
+    pub fn to_cmyk(self) -> Cmyk {
+        let (r, g, b) = self.fractions();
+        let k = 1.0 - r.max(g).max(b);
+        if k >= 1.0 {
+            return Cmyk { c: 0.0, m: 0.0, y: 0.0, k: 1.0 };
+        }
+        Cmyk {
+            c: (1.0 - r - k) / (1.0 - k),
+            m: (1.0 - g - k) / (1.0 - k),
+            y: (1.0 - b - k) / (1.0 - k),
+            k,
+        }
+    }
+
+    /// Closest CSS named color and whether it is an exact match. Distance is weighted
+    /// by the average red level ("redmean"), which tracks perceived difference better
+    /// than plain RGB distance.
+    pub fn nearest_name(self) -> (&'static str, bool) {
+        let distance = |(name, hex): &(&'static str, u32)| {
+            let (r2, g2, b2) = ((hex >> 16) as i64 & 0xff, (hex >> 8) as i64 & 0xff, *hex as i64 & 0xff);
+            let (dr, dg, db) = (self.r as i64 - r2, self.g as i64 - g2, self.b as i64 - b2);
+            let mean = (self.r as i64 + r2) / 2;
+            let weighted = (((512 + mean) * dr * dr) >> 8) + 4 * dg * dg + (((767 - mean) * db * db) >> 8);
+            (weighted, *name)
+        };
+        let (weighted, name) = CSS_NAMES.iter().map(distance).min().unwrap_or((0, "black"));
+        (name, weighted == 0)
+    }
+
+    pub fn hex(self) -> String {
+        format!("#{:02x}{:02x}{:02x}", self.r, self.g, self.b)
+    }
+}
+
+pub fn from_hsl(hsl: Hsx) -> Rgb {
+    let chroma = (1.0 - (2.0 * hsl.x - 1.0).abs()) * hsl.s;
+    from_hue_chroma(hsl.h, chroma, hsl.x - chroma / 2.0)
+}
+
+pub fn from_hsv(hsv: Hsx) -> Rgb {
+    let chroma = hsv.x * hsv.s;
+    from_hue_chroma(hsv.h, chroma, hsv.x - chroma)
+}
+
+pub fn from_cmyk(cmyk: Cmyk) -> Rgb {
+    let channel = |ink: f64| (255.0 * (1.0 - ink) * (1.0 - cmyk.k)).round() as u8;
+    Rgb {
+        r: channel(cmyk.c),
+        g: channel(cmyk.m),
+        b: channel(cmyk.y),
+    }
+}
+
+/// Shared step of HSL and HSV: place the chroma on the hue's sextant, then add the lightness offset
+fn from_hue_chroma(hue: f64, chroma: f64, offset: f64) -> Rgb {
+    let sextant = hue.rem_euclid(360.0) / 60.0;
+    let second = chroma * (1.0 - (sextant.rem_euclid(2.0) - 1.0).abs());
+    let (r, g, b) = match sextant as u32 {
+        0 => (chroma, second, 0.0),
+        1 => (second, chroma, 0.0),
+        2 => (0.0, chroma, second),
+        3 => (0.0, second, chroma),
+        4 => (second, 0.0, chroma),
+        _ => (chroma, 0.0, second),
+    };
+    let channel = |value: f64| ((value + offset) * 255.0).round().clamp(0.0, 255.0) as u8;
+    Rgb {
+        r: channel(r),
+        g: channel(g),
+        b: channel(b),
+    }
+}
+
+fn percent(fraction: f64) -> String {
+    format!("{}%", (fraction * 100.0).round())
+}
+
+/// One result per notation; Enter copies it
+pub fn search_results(color: Rgb) -> Vec<SearchResultData> {
+    let hsl = color.to_hsl();
+    let hsv = color.to_hsv();
+    let cmyk = color.to_cmyk();
+    let (name, exact) = color.nearest_name();
+
+    let notations = [
+        (color.hex(), "Hex".to_string()),
+        (format!("rgb({}, {}, {})", color.r, color.g, color.b), "RGB".to_string()),
+        (
+            format!("hsl({}, {}, {})", hsl.h.round(), percent(hsl.s), percent(hsl.x)),
+            "HSL".to_string(),
+        ),
+        (
+            format!("hsv({}, {}, {})", hsv.h.round(), percent(hsv.s), percent(hsv.x)),
+            "HSV".to_string(),
+        ),
+        (
+            format!(
+                "cmyk({}, {}, {}, {})",
+                percent(cmyk.c),
+                percent(cmyk.m),
+                percent(cmyk.y),
+                percent(cmyk.k)
+            ),
+            "CMYK".to_string(),
+        ),
+        (
+            name.to_string(),
+            if exact { "CSS name".to_string() } else { "Nearest CSS name".to_string() },
+        ),
+    ];
+
+    notations
+        .into_iter()
+        .map(|(notation, format)| SearchResultData {
+            description: format!("{} · Enter to copy", format),
+            path: PathBuf::from(&notation),
+            name: notation,
+            result_type: "color".to_string(),
+            swatch: Some(color),
+            ..Default::default()
+        })
+        .collect()
+}
+
+/// CSS named colors (CSS Color Module Level 4); aliases like "aqua"/"cyan" share a value
+const CSS_NAMES: &[(&str, u32)] = &[
+    ("aliceblue", 0xf0f8ff),
+    ("antiquewhite", 0xfaebd7),
+    ("aqua", 0x00ffff),
+    ("aquamarine", 0x7fffd4),
+    ("azure", 0xf0ffff),
+    ("beige", 0xf5f5dc),
+    ("bisque", 0xffe4c4),
+    ("black", 0x000000),
+    ("blanchedalmond", 0xffebcd),
+    ("blue", 0x0000ff),
+    ("blueviolet", 0x8a2be2),
+    ("brown", 0xa52a2a),
+    ("burlywood", 0xdeb887),
+    ("cadetblue", 0x5f9ea0),
+    ("chartreuse", 0x7fff00),
+    ("chocolate", 0xd2691e),
+    ("coral", 0xff7f50),
+    ("cornflowerblue", 0x6495ed),
+    ("cornsilk", 0xfff8dc),
+    ("crimson", 0xdc143c),
+    ("darkblue", 0x00008b),
+    ("darkcyan", 0x008b8b),
+    ("darkgoldenrod", 0xb8860b),
+    ("darkgray", 0xa9a9a9),
+    ("darkgreen", 0x006400),
+    ("darkkhaki", 0xbdb76b),
+    ("darkmagenta", 0x8b008b),
+    ("darkolivegreen", 0x556b2f),
+    ("darkorange", 0xff8c00),
+    ("darkorchid", 0x9932cc),
+    ("darkred", 0x8b0000),
+    ("darksalmon", 0xe9967a),
+    ("darkseagreen", 0x8fbc8f),
+    ("darkslateblue", 0x483d8b),
+    ("darkslategray", 0x2f4f4f),
+    ("darkturquoise", 0x00ced1),
+    ("darkviolet", 0x9400d3),
+    ("deeppink", 0xff1493),
+    ("deepskyblue", 0x00bfff),
+    ("dimgray", 0x696969),
+    ("dodgerblue", 0x1e90ff),
+    ("firebrick", 0xb22222),
+    ("floralwhite", 0xfffaf0),
+    ("forestgreen", 0x228b22),
+    ("fuchsia", 0xff00ff),
+    ("gainsboro", 0xdcdcdc),
+    ("ghostwhite", 0xf8f8ff),
+    ("gold", 0xffd700),
+    ("goldenrod", 0xdaa520),
+    ("gray", 0x808080),
+    ("green", 0x008000),
+    ("greenyellow", 0xadff2f),
+    ("honeydew", 0xf0fff0),
+    ("hotpink", 0xff69b4),
+    ("indianred", 0xcd5c5c),
+    ("indigo", 0x4b0082),
+    ("ivory", 0xfffff0),
+    ("khaki", 0xf0e68c),
+    ("lavender", 0xe6e6fa),
+    ("lavenderblush", 0xfff0f5),
+    ("lawngreen", 0x7cfc00),
+    ("lemonchiffon", 0xfffacd),
+    ("lightblue", 0xadd8e6),
+    ("lightcoral", 0xf08080),
+    ("lightcyan", 0xe0ffff),
+    ("lightgoldenrodyellow", 0xfafad2),
+    ("lightgray", 0xd3d3d3),
+    ("lightgreen", 0x90ee90),
+    ("lightpink", 0xffb6c1),
+    ("lightsalmon", 0xffa07a),
+    ("lightseagreen", 0x20b2aa),
+    ("lightskyblue", 0x87cefa),
+    ("lightslategray", 0x778899),
+    ("lightsteelblue", 0xb0c4de),
+    ("lightyellow", 0xffffe0),
+    ("lime", 0x00ff00),
+    ("limegreen", 0x32cd32),
+    ("linen", 0xfaf0e6),
+    ("maroon", 0x800000),
+    ("mediumaquamarine", 0x66cdaa),
+    ("mediumblue", 0x0000cd),
+    ("mediumorchid", 0xba55d3),
+    ("mediumpurple", 0x9370db),
+    ("mediumseagreen", 0x3cb371),
+    ("mediumslateblue", 0x7b68ee),
+    ("mediumspringgreen", 0x00fa9a),
+    ("mediumturquoise", 0x48d1cc),
+    ("mediumvioletred", 0xc71585),
+    ("midnightblue", 0x191970),
+    ("mintcream", 0xf5fffa),
+    ("mistyrose", 0xffe4e1),
+    ("moccasin", 0xffe4b5),
+    ("navajowhite", 0xffdead),
+    ("navy", 0x000080),
+    ("oldlace", 0xfdf5e6),
+    ("olive", 0x808000),
+    ("olivedrab", 0x6b8e23),
+    ("orange", 0xffa500),
+    ("orangered", 0xff4500),
+    ("orchid", 0xda70d6),
+    ("palegoldenrod", 0xeee8aa),
+    ("palegreen", 0x98fb98),
+    ("paleturquoise", 0xafeeee),
+    ("palevioletred", 0xdb7093),
+    ("papayawhip", 0xffefd5),
+    ("peachpuff", 0xffdab9),
+    ("peru", 0xcd853f),
+    ("pink", 0xffc0cb),
+    ("plum", 0xdda0dd),
+    ("powderblue", 0xb0e0e6),
+    ("purple", 0x800080),
+    ("rebeccapurple", 0x663399),
+    ("red", 0xff0000),
+    ("rosybrown", 0xbc8f8f),
+    ("royalblue", 0x4169e1),
+    ("saddlebrown", 0x8b4513),
+    ("salmon", 0xfa8072),
+    ("sandybrown", 0xf4a460),
+    ("seagreen", 0x2e8b57),
+    ("seashell", 0xfff5ee),
+    ("sienna", 0xa0522d),
+    ("silver", 0xc0c0c0),
+    ("skyblue", 0x87ceeb),
+    ("slateblue", 0x6a5acd),
+    ("slategray", 0x708090),
+    ("snow", 0xfffafa),
+    ("springgreen", 0x00ff7f),
+    ("steelblue", 0x4682b4),
+    ("tan", 0xd2b48c),
+    ("teal", 0x008080),
+    ("thistle", 0xd8bfd8),
+    ("tomato", 0xff6347),
+    ("turquoise", 0x40e0d0),
+    ("violet", 0xee82ee),
+    ("wheat", 0xf5deb3),
+    ("white", 0xffffff),
+    ("whitesmoke", 0xf5f5f5),
+    ("yellow", 0xffff00),
+    ("yellowgreen", 0x9acd32),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DODGER_BLUE: Rgb = Rgb { r: 30, g: 144, b: 255 };
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(parse("#1e90ff"), Some(DODGER_BLUE));
+        assert_eq!(parse("#1E90FFCC"), Some(DODGER_BLUE));
+        assert_eq!(parse("rgb(30,144,255)"), Some(DODGER_BLUE));
+        assert_eq!(parse("rgba(30 144 255 / 50%)"), Some(DODGER_BLUE));
+        assert_eq!(parse("hsl(210 100% 56%)"), Some(Rgb { r: 31, g: 143, b: 255 }));
+        assert_eq!(parse("hsla(210deg, 100%, 56%, 0.5)"), Some(Rgb { r: 31, g: 143, b: 255 }));
+        assert_eq!(parse("#09f"), Some(Rgb { r: 0, g: 153, b: 255 }));
+        assert_eq!(parse("hsv(210, 88%, 100%)"), Some(Rgb { r: 31, g: 143, b: 255 }));
+        assert_eq!(parse("cmyk(0%, 100%, 100%, 0%)"), Some(Rgb { r: 255, g: 0, b: 0 }));
+
+        assert_eq!(parse("#12345"), None);
+        assert_eq!(parse("#ggg"), None);
+        assert_eq!(parse("rgb(300, 0, 0)"), None);
+        assert_eq!(parse("rgb(1, 2)"), None);
+        assert_eq!(parse("cmyk(0%, 100%, 100%)"), None);
+        assert_eq!(parse("sqrt(16)"), None);
+    }
+
+    #[test]
+    fn test_notations() {
+        let names: Vec<String> = search_results(DODGER_BLUE).into_iter().map(|r| r.name).collect();
+        assert_eq!(
+            names,
+            [
+                "#1e90ff",
+                "rgb(30, 144, 255)",
+                "hsl(210, 100%, 56%)",
+                "hsv(210, 88%, 100%)",
+                "cmyk(88%, 44%, 0%, 0%)",
+                "dodgerblue",
+            ]
+        );
+
+        let results = search_results(Rgb { r: 31, g: 143, b: 255 });
+        assert_eq!(results[5].description, "Nearest CSS name · Enter to copy");
+        assert_eq!(results[5].name, "dodgerblue");
+        assert_eq!(results[0].swatch, Some(Rgb { r: 31, g: 143, b: 255 }));
+        assert_eq!(results[0].path, PathBuf::from("#1f8fff"));
+    }
+
+    #[test]
+    fn test_round_trips() {
+        let colors = CSS_NAMES
+            .iter()
+            .map(|(_, hex)| Rgb {
+                r: (hex >> 16) as u8,
+                g: (hex >> 8) as u8,
+                b: *hex as u8,
+            })
+            .chain([Rgb { r: 1, g: 2, b: 3 }, Rgb { r: 254, g: 0, b: 128 }]);
+
+        for color in colors {
+            assert_eq!(from_hsl(color.to_hsl()), color, "HSL {:?}", color);
+            assert_eq!(from_hsv(color.to_hsv()), color, "HSV {:?}", color);
+            assert_eq!(from_cmyk(color.to_cmyk()), color, "CMYK {:?}", color);
+            assert_eq!(parse(&color.hex()), Some(color));
+        }
+    }
+
+    #[test]
+    fn test_grays_and_names() {
+        let gray = Rgb { r: 128, g: 128, b: 128 };
+        assert_eq!(gray.to_hsl().s, 0.0);
+        assert_eq!(gray.to_cmyk().c, 0.0);
+        assert_eq!(Rgb { r: 0, g: 0, b: 0 }.to_cmyk().k, 1.0);
+        assert_eq!(gray.nearest_name(), ("gray", true));
+        assert_eq!(Rgb { r: 250, g: 2, b: 3 }.nearest_name(), ("red", false));
+    }
+}
@@ -0,0 +1,167 @@
+//! Commands module
+//! Registry of built-in system commands. Each command has a stable ID carried in its
+//! search results, keywords it is found by, and a handler that runs it.
+
+use std::io;
+use std::path::PathBuf;
+use std::process::Command;
+
+use crate::SearchResultData;
+
+/// A built-in command offered in search results
+pub struct CommandSpec {
+    /// Stable identifier, carried in `SearchResultData::command_id`
+    pub id: &'static str,
+    pub name: &'static str,
+    pub description: &'static str,
+
+    /// Extra terms the command is found by, besides its name
+    pub keywords: &'static [&'static str],
+    handler: fn() -> io::Result<()>,
+}
+
+/// All built-in commands
+pub const COMMANDS: &[CommandSpec] = &[
+    CommandSpec {
+        id: "lock",
+        name: "Lock Computer",
+        description: "Lock your workstation",
+        keywords: &["lock", "lock pc", "lock screen", "lock workstation"],
+        handler: lock,
+    },
+    CommandSpec {
+        id: "sleep",
+        name: "Sleep",
+        description: "Put computer to sleep",
+        keywords: &["suspend", "standby"],
+        handler: sleep,
+    },
+    CommandSpec {
+        id: "restart",
+        name: "Restart",
+        description: "Restart your computer",
+        keywords: &["reboot"],
+        handler: restart,
+    },
+    CommandSpec {
+        id: "shutdown",
+        name: "Shutdown",
+        description: "Shut down your computer",
+        keywords: &["shut down", "power off", "turn off"],
+        handler: shutdown,
+    },
+    CommandSpec {
+        id: "sign-out",
+        name: "Sign Out",
+        description: "Sign out of your account",
+        keywords: &["logout", "log out", "logoff", "log off"],
+        handler: sign_out,
+    },
+    CommandSpec {
+        id: "empty-recycle-bin",
+        name: "Empty Recycle Bin",
+        description: "Permanently delete items in Recycle Bin",
+        keywords: &["empty trash", "trash", "recycle bin"],
+        handler: empty_recycle_bin,
+    },
+];
+
+impl CommandSpec {
+    /// Search result for this command
+    pub fn result(&self) -> SearchResultData {
+        SearchResultData {
+            name: self.name.to_string(),
+            description: self.description.to_string(),
+            path: PathBuf::from(self.id),
+            result_type: "action".to_string(),
+            command_id: Some(self.id),
+            ..Default::default()
+        }
+    }
+
+    /// Lowercased name followed by the keywords, for matching against a query
+    pub fn search_terms(&self) -> impl Iterator<Item = String> + '_ {
+        std::iter::once(self.name.to_lowercase()).chain(self.keywords.iter().map(|k| k.to_string()))
+    }
+}
+
+/// Look up a command by its ID
+pub fn find(id: &str) -> Option<&'static CommandSpec> {
+    COMMANDS.iter().find(|command| command.id == id)
+}
+
+/// Run the command with the given ID
+pub fn execute(id: &str) {
+    let Some(command) = find(id) else {
+        log::warn!("Unknown command: {}", id);
+        return;
+    };
+
+    log::info!("Executing command: {}", command.name);
+    if let Err(e) = (command.handler)() {
+        log::error!("Failed to run {}: {}", command.name, e);
+    }
+}
+
+fn lock() -> io::Result<()> {
+    Command::new("rundll32.exe")
+        .args(["user32.dll,LockWorkStation"])
+        .spawn()
+        .map(drop)
+}
+
+fn sleep() -> io::Result<()> {
+    Command::new("rundll32.exe")
+        .args(["powrprof.dll,SetSuspendState", "0", "1", "0"])
+        .spawn()
+        .map(drop)
+}
+
+fn restart() -> io::Result<()> {
+    Command::new("shutdown").args(["/r", "/t", "0"]).spawn().map(drop)
+}
+
+fn shutdown() -> io::Result<()> {
+    Command::new("shutdown").args(["/s", "/t", "0"]).spawn().map(drop)
+}
+
+fn sign_out() -> io::Result<()> {
+    Command::new("shutdown").args(["/l"]).spawn().map(drop)
+}
+
+fn empty_recycle_bin() -> io::Result<()> {
+    // Uses PowerShell to empty recycle bin
+    Command::new("powershell")
+        .args(["-Command", "Clear-RecycleBin", "-Force", "-ErrorAction", "SilentlyContinue"])
+        .spawn()
+        .map(drop)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ids_unique_and_found() {
+        for (i, command) in COMMANDS.iter().enumerate() {
+            assert!(COMMANDS[i + 1..].iter().all(|other| other.id != command.id));
+            assert_eq!(find(command.id).map(|c| c.name), Some(command.name));
+        }
+        assert!(find("Sign Out").is_none());
+    }
+
+    #[test]
+    fn test_result_carries_id() {
+        let result = find("sign-out").unwrap().result();
+        assert_eq!(result.name, "Sign Out");
+        assert_eq!(result.result_type, "action");
+        assert_eq!(result.command_id, Some("sign-out"));
+    }
+
+    #[test]
+    fn test_search_terms() {
+        let terms: Vec<String> = find("lock").unwrap().search_terms().collect();
+        assert_eq!(terms[0], "lock computer");
+        assert!(terms.iter().any(|term| term.starts_with("lock pc")));
+    }
+}
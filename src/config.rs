@@ -31,10 +31,50 @@ pub struct AppConfig {
     #[serde(default)]
     pub update: UpdateConfig,
 
+    /// Calculator settings
+    #[serde(default)]
+    pub calculator: CalculatorConfig,
+
+    /// Clipboard settings
+    #[serde(default)]
+    pub clipboard: ClipboardConfig,
+
+    /// Shell command runner settings
+    #[serde(default)]
+    pub shell: ShellConfig,
+
+    /// Emoji and Unicode character picker settings
+    #[serde(default)]
+    pub characters: CharactersConfig,
+
+    /// Quick notes and todo settings
+    #[serde(default)]
+    pub notes: NotesConfig,
+
+    /// Browser bookmark settings
+    #[serde(default)]
+    pub bookmarks: BookmarksConfig,
+
+    /// UUID, password and lorem ipsum generator settings
+    #[serde(default)]
+    pub generators: GeneratorsConfig,
+
+    /// Recent editor workspace settings
+    #[serde(default)]
+    pub workspaces: WorkspacesConfig,
+
     /// Most Recently Used tracking
     #[serde(default)]
     pub mru: HashMap<String, u32>,
 
+    /// Apps pinned to the top of search results
+    #[serde(default)]
+    pub pinned: Vec<String>,
+
+    /// Emoji and characters copied from the picker, most recent first
+    #[serde(default)]
+    pub recent_characters: Vec<String>,
+
     /// First run flag
     #[serde(default = "default_first_run")]
     pub first_run: bool,
@@ -126,6 +166,10 @@ pub struct SearchConfig {
     /// Enable fuzzy matching
     #[serde(default = "default_fuzzy_search")]
     pub fuzzy_search: bool,
+
+    /// List recently opened documents on the empty query and under `recent`
+    #[serde(default = "default_recent_documents")]
+    pub recent_documents: bool,
 }
 
 fn default_search_delay() -> u32 {
@@ -136,6 +180,10 @@ fn default_fuzzy_search() -> bool {
     true
 }
 
+fn default_recent_documents() -> bool {
+    true
+}
+
 impl Default for AppConfig {
     fn default() -> Self {
         Self {
@@ -144,7 +192,17 @@ impl Default for AppConfig {
             appearance: AppearanceConfig::default(),
             search: SearchConfig::default(),
             update: UpdateConfig::default(),
+            calculator: CalculatorConfig::default(),
+            clipboard: ClipboardConfig::default(),
+            shell: ShellConfig::default(),
+            characters: CharactersConfig::default(),
+            notes: NotesConfig::default(),
+            bookmarks: BookmarksConfig::default(),
+            generators: GeneratorsConfig::default(),
+            workspaces: WorkspacesConfig::default(),
             mru: HashMap::new(),
+            pinned: Vec::new(),
+            recent_characters: Vec::new(),
             first_run: true,
             portable_mode: false, // Will be set during load
         }
@@ -188,6 +246,7 @@ impl Default for SearchConfig {
             file_type_filters: vec![],
             search_delay_ms: 150,
             fuzzy_search: true,
+            recent_documents: true,
         }
     }
 }
@@ -231,6 +290,224 @@ impl Default for UpdateConfig {
     }
 }
 
+/// Calculator configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CalculatorConfig {
+    /// Warn when the exchange rates file is older than this many days
+    #[serde(default = "default_rates_max_age_days")]
+    pub rates_max_age_days: u32,
+
+    /// Optional URL to refresh rates.json from (same JSON format); offline when unset
+    #[serde(default)]
+    pub rates_url: Option<String>,
+
+    /// Read `2,5` as a decimal number; follows the system locale when unset
+    #[serde(default)]
+    pub decimal_comma: Option<bool>,
+}
+
+fn default_rates_max_age_days() -> u32 {
+    7
+}
+
+impl Default for CalculatorConfig {
+    fn default() -> Self {
+        Self {
+            rates_max_age_days: 7,
+            rates_url: None,
+            decimal_comma: None,
+        }
+    }
+}
+
+/// Clipboard configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClipboardConfig {
+    /// Paste copied results into the window that was focused before the launcher opened
+    #[serde(default)]
+    pub paste_after_copy: bool,
+
+    /// Record copied text so it can be searched with the `cb` keyword
+    #[serde(default)]
+    pub history_enabled: bool,
+
+    /// Maximum number of clipboard history entries
+    #[serde(default = "default_history_max_entries")]
+    pub history_max_entries: usize,
+
+    /// Skip content that password managers mark as excluded from clipboard history
+    #[serde(default = "default_history_exclude_sensitive")]
+    pub history_exclude_sensitive: bool,
+}
+
+fn default_history_max_entries() -> usize {
+    100
+}
+
+fn default_history_exclude_sensitive() -> bool {
+    true
+}
+
+impl Default for ClipboardConfig {
+    fn default() -> Self {
+        Self {
+            paste_after_copy: false,
+            history_enabled: false,
+            history_max_entries: 100,
+            history_exclude_sensitive: true,
+        }
+    }
+}
+
+/// Shell command runner configuration (`>` prefix)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShellConfig {
+    /// Shell that runs commands: "cmd", "powershell", "pwsh" or "bash"
+    #[serde(default = "default_shell")]
+    pub shell: String,
+
+    /// Run commands in a new terminal window instead of capturing their output
+    #[serde(default)]
+    pub open_in_terminal: bool,
+
+    /// Stop captured commands that run longer than this many seconds
+    #[serde(default = "default_shell_timeout_secs")]
+    pub timeout_secs: u64,
+
+    /// Number of output lines shown as results
+    #[serde(default = "default_shell_output_lines")]
+    pub output_lines: usize,
+
+    /// Ask for confirmation before running a command
+    #[serde(default)]
+    pub confirm: bool,
+}
+
+fn default_shell() -> String {
+    if cfg!(windows) { "powershell" } else { "bash" }.to_string()
+}
+
+fn default_shell_timeout_secs() -> u64 {
+    10
+}
+
+fn default_shell_output_lines() -> usize {
+    8
+}
+
+impl Default for ShellConfig {
+    fn default() -> Self {
+        Self {
+            shell: default_shell(),
+            open_in_terminal: false,
+            timeout_secs: 10,
+            output_lines: 8,
+            confirm: false,
+        }
+    }
+}
+
+/// Emoji and Unicode character picker configuration
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CharactersConfig {
+    /// Skin tone for emoji that support one: "light", "medium-light", "medium",
+    /// "medium-dark" or "dark"; the default yellow when unset
+    #[serde(default)]
+    pub skin_tone: Option<String>,
+}
+
+/// Quick notes and todo configuration
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct NotesConfig {
+    /// Markdown file that notes and todos are added to; `notes.md` in the
+    /// configuration directory when unset
+    #[serde(default)]
+    pub file: Option<PathBuf>,
+}
+
+/// Browser bookmark configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BookmarksConfig {
+    /// Include browser bookmarks in search results
+    #[serde(default = "default_bookmarks_enabled")]
+    pub enabled: bool,
+
+    /// Browser profile folders (or `Bookmarks` / `places.sqlite` files) to read;
+    /// the default profiles of Chrome, Edge, Brave and Firefox when empty
+    #[serde(default)]
+    pub profiles: Vec<PathBuf>,
+}
+
+fn default_bookmarks_enabled() -> bool {
+    true
+}
+
+impl Default for BookmarksConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            profiles: Vec::new(),
+        }
+    }
+}
+
+/// Generator configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GeneratorsConfig {
+    /// Password length when the `pw` query gives none
+    #[serde(default = "default_password_length")]
+    pub password_length: usize,
+
+    /// Character classes passwords draw from unless the query adds or removes some:
+    /// `lower`, `upper`, `digits` and `symbols`
+    #[serde(default = "default_password_classes")]
+    pub password_classes: Vec<String>,
+}
+
+fn default_password_length() -> usize {
+    20
+}
+
+fn default_password_classes() -> Vec<String> {
+    vec!["lower".to_string(), "upper".to_string(), "digits".to_string()]
+}
+
+impl Default for GeneratorsConfig {
+    fn default() -> Self {
+        Self {
+            password_length: default_password_length(),
+            password_classes: default_password_classes(),
+        }
+    }
+}
+
+/// Recent editor workspace configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkspacesConfig {
+    /// Include recent VS Code and JetBrains projects in search results
+    #[serde(default = "default_workspaces_enabled")]
+    pub enabled: bool,
+
+    /// VS Code user data folders, JetBrains IDE config folders, or their
+    /// `state.vscdb` / `storage.json` / `recentProjects.xml` files to read;
+    /// every installed VS Code variant and JetBrains IDE when empty
+    #[serde(default)]
+    pub paths: Vec<PathBuf>,
+}
+
+fn default_workspaces_enabled() -> bool {
+    true
+}
+
+impl Default for WorkspacesConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            paths: Vec::new(),
+        }
+    }
+}
+
 impl AppConfig {
     /// Get the configuration directory path based on portable mode
     pub fn config_dir(portable_mode: PortableMode) -> Option<PathBuf> {
@@ -338,6 +615,28 @@ impl AppConfig {
         *self.mru.get(name).unwrap_or(&0)
     }
 
+    /// Check if an app is pinned
+    pub fn is_pinned(&self, name: &str) -> bool {
+        self.pinned.iter().any(|pinned| pinned == name)
+    }
+
+    /// Pin or unpin an app and save the change
+    pub fn set_pinned(&mut self, name: &str, pinned: bool) {
+        self.pinned.retain(|existing| existing != name);
+        if pinned {
+            self.pinned.push(name.to_string());
+        }
+        self.save();
+    }
+
+    /// Move a character to the front of the recently used list and save the change
+    pub fn record_character(&mut self, character: &str, max_recent: usize) {
+        self.recent_characters.retain(|existing| existing != character);
+        self.recent_characters.insert(0, character.to_string());
+        self.recent_characters.truncate(max_recent);
+        self.save();
+    }
+
     /// Mark first run as complete
     pub fn complete_first_run(&mut self) {
         self.first_run = false;
@@ -0,0 +1,351 @@
+//! Currency conversion module
+//! Converts "100 usd in eur"-style queries using exchange rates from a local JSON file,
+//! so conversion works fully offline
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+
+use crate::config::AppConfig;
+
+/// File name of the exchange rates inside the configuration directory
+pub const RATES_FILE_NAME: &str = "rates.json";
+
+/// Exchange rates relative to a base currency, e.g.
+/// `{"base": "EUR", "date": "2026-10-16", "rates": {"USD": 1.0712, "GBP": 0.8561}}`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RatesTable {
+    /// ISO 4217 code of the base currency
+    pub base: String,
+
+    /// Date the rates were published (YYYY-MM-DD)
+    pub date: String,
+
+    /// Units of each currency per one unit of the base currency
+    pub rates: HashMap<String, f64>,
+}
+
+/// Result of a successful currency conversion
+#[derive(Debug, Clone, PartialEq)]
+pub struct CurrencyConversion {
+    pub amount: f64,
+    pub from: String,
+    pub result: f64,
+    pub to: String,
+    pub rates_date: NaiveDate,
+    /// Age of the rates in days, set only when older than the configured maximum
+    pub stale_days: Option<i64>,
+}
+
+impl CurrencyConversion {
+    /// Formatted result, e.g. "93.35 EUR"
+    pub fn result_text(&self) -> String {
+        format!("{} {}", format_amount(self.result), self.to)
+    }
+
+    /// Formatted source amount, e.g. "100 USD"
+    pub fn source_text(&self) -> String {
+        format!("{} {}", format_amount(self.amount), self.from)
+    }
+}
+
+impl RatesTable {
+    /// Load rates from a JSON file
+    pub fn load(path: &Path) -> Result<Self, Box<dyn Error>> {
+        let content = fs::read_to_string(path)?;
+        let table: RatesTable = serde_json::from_str(&content)?;
+        table.published()?;
+        Ok(table.normalized())
+    }
+
+    /// Parsed publication date of the rates
+    pub fn published(&self) -> Result<NaiveDate, chrono::ParseError> {
+        NaiveDate::parse_from_str(&self.date, "%Y-%m-%d")
+    }
+
+    /// Upper-case all currency codes so lookups are case-insensitive
+    fn normalized(self) -> Self {
+        Self {
+            base: self.base.to_uppercase(),
+            date: self.date,
+            rates: self
+                .rates
+                .into_iter()
+                .map(|(code, rate)| (code.to_uppercase(), rate))
+                .collect(),
+        }
+    }
+
+    /// Rate of a currency relative to the base (the base itself is always 1)
+    fn rate(&self, code: &str) -> Option<f64> {
+        if code == self.base {
+            Some(1.0)
+        } else {
+            self.rates.get(code).copied().filter(|rate| *rate > 0.0)
+        }
+    }
+
+    /// Whether a currency code is known to this table
+    pub fn knows(&self, code: &str) -> bool {
+        self.rate(&code.to_uppercase()).is_some()
+    }
+
+    /// Convert an amount between two currencies, crossing through the base
+    pub fn convert(&self, amount: f64, from: &str, to: &str) -> Option<f64> {
+        let from_rate = self.rate(&from.to_uppercase())?;
+        let to_rate = self.rate(&to.to_uppercase())?;
+        Some(amount / from_rate * to_rate)
+    }
+}
+
+/// Parse "100 usd in eur" into (amount, FROM, TO) without touching the rates file.
+/// Unit conversions come first, so "10 min in sec" is never taken for currencies.
+pub fn parse_query(query: &str) -> Option<(f64, String, String)> {
+    if crate::units::parse_conversion(query).is_some() {
+        return None;
+    }
+
+    let words: Vec<&str> = query.split_whitespace().collect();
+
+    let (amount_text, from, to) = match words.as_slice() {
+        // "100 usd in eur"
+        [amount, from, connector, to] if is_connector(connector) => (*amount, *from, *to),
+        // "100usd in eur"
+        [quantity, connector, to] if is_connector(connector) => {
+            let split = quantity.find(|c: char| c.is_ascii_alphabetic())?;
+            (&quantity[..split], &quantity[split..], *to)
+        }
+        _ => return None,
+    };
+
+    if !is_currency_code(from) || !is_currency_code(to) {
+        return None;
+    }
+
+    let amount: f64 = amount_text.parse().ok()?;
+    Some((amount, from.to_uppercase(), to.to_uppercase()))
+}
+
+fn is_connector(word: &str) -> bool {
+    ["in", "to", "as", "into", "->"]
+        .iter()
+        .any(|c| word.eq_ignore_ascii_case(c))
+}
+
+fn is_currency_code(word: &str) -> bool {
+    word.len() == 3 && word.chars().all(|c| c.is_ascii_alphabetic())
+}
+
+/// Convert a query against a rates table, flagging rates older than `max_age_days`
+pub fn convert_query(
+    query: &str,
+    table: &RatesTable,
+    max_age_days: u32,
+    today: NaiveDate,
+) -> Option<CurrencyConversion> {
+    let (amount, from, to) = parse_query(query)?;
+    if !table.knows(&from) || !table.knows(&to) {
+        return None;
+    }
+    let result = table.convert(amount, &from, &to)?;
+    let rates_date = table.published().ok()?;
+
+    let age_days = (today - rates_date).num_days();
+    let stale_days = (age_days > max_age_days as i64).then_some(age_days);
+
+    Some(CurrencyConversion {
+        amount,
+        from,
+        result,
+        to,
+        rates_date,
+        stale_days,
+    })
+}
+
+/// Rates file read last, with its modification time
+struct CachedRates {
+    path: PathBuf,
+    modified: SystemTime,
+    table: Arc<RatesTable>,
+}
+
+static RATES_CACHE: Mutex<Option<CachedRates>> = Mutex::new(None);
+
+/// Rates of a file, read again only when its modification time changes
+fn cached_rates(path: &Path) -> Result<Arc<RatesTable>, Box<dyn Error>> {
+    let modified = fs::metadata(path)?.modified()?;
+    let mut cache = RATES_CACHE.lock().map_err(|_| "Exchange rates cache is poisoned")?;
+    if let Some(cached) = cache.as_ref().filter(|c| c.path == path && c.modified == modified) {
+        return Ok(Arc::clone(&cached.table));
+    }
+
+    let table = Arc::new(RatesTable::load(path)?);
+    *cache = Some(CachedRates {
+        path: path.to_path_buf(),
+        modified,
+        table: Arc::clone(&table),
+    });
+    Ok(table)
+}
+
+/// Convert a query using the rates file in the configuration directory.
+/// The file is only read once the query looks like a currency conversion, and
+/// then kept until it changes.
+pub fn try_convert(query: &str, max_age_days: u32) -> Option<CurrencyConversion> {
+    parse_query(query)?;
+
+    let path = rates_path()?;
+    let table = match cached_rates(&path) {
+        Ok(table) => table,
+        Err(e) => {
+            log::debug!("No usable exchange rates at {:?}: {}", path, e);
+            return None;
+        }
+    };
+
+    convert_query(query, &table, max_age_days, chrono::Local::now().date_naive())
+}
+
+/// Path of the exchange rates file in the configuration directory
+pub fn rates_path() -> Option<PathBuf> {
+    AppConfig::config_dir(crate::single_instance::detect_portable_mode())
+        .map(|dir| dir.join(RATES_FILE_NAME))
+}
+
+/// Whether the rates file is missing or older than `max_age_days`
+pub fn rates_need_update(max_age_days: u32) -> bool {
+    let today = chrono::Local::now().date_naive();
+    match rates_path().map(|path| RatesTable::load(&path)) {
+        Some(Ok(table)) => match table.published() {
+            Ok(date) => (today - date).num_days() > max_age_days as i64,
+            Err(_) => true,
+        },
+        _ => true,
+    }
+}
+
+/// Optional updater hook: download rates in the local file format from `url`
+/// and replace the rates file once they parse successfully
+pub fn update_rates(url: &str) -> Result<(), Box<dyn Error>> {
+    let path = rates_path().ok_or("No configuration directory for exchange rates")?;
+    log::info!("Updating exchange rates from: {}", url);
+
+    let client = reqwest::blocking::Client::builder()
+        .user_agent("Nexus-Updater/1.0")
+        .build()?;
+
+    let response = client.get(url).send()?;
+    let status = response.status();
+    if !status.is_success() {
+        return Err(format!("Exchange rates request failed with status: {}", status).into());
+    }
+
+    let table: RatesTable = response.json()?;
+    table.published()?;
+    if table.rates.is_empty() {
+        return Err("Exchange rates response contained no rates".into());
+    }
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(&path, serde_json::to_string_pretty(&table.normalized())?)?;
+    log::info!("Exchange rates saved to {:?}", path);
+    Ok(())
+}
+
+/// Format a money amount with two decimals, or more for very small amounts
+fn format_amount(amount: f64) -> String {
+    if amount != 0.0 && amount.abs() < 0.01 {
+        crate::units::format_number(amount)
+    } else {
+        format!("{:.2}", amount)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_table() -> RatesTable {
+        let json = r#"{
+            "base": "eur",
+            "date": "2026-10-10",
+            "rates": { "usd": 1.25, "GBP": 0.8, "JPY": 160.0 }
+        }"#;
+        serde_json::from_str::<RatesTable>(json).unwrap().normalized()
+    }
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn test_parse_query() {
+        assert_eq!(parse_query("100 usd in eur"), Some((100.0, "USD".into(), "EUR".into())));
+        assert_eq!(parse_query("12.5USD to gbp"), Some((12.5, "USD".into(), "GBP".into())));
+        assert_eq!(parse_query("100 usd"), None);
+        assert_eq!(parse_query("12 km in mi"), None);
+        assert_eq!(parse_query("10 min in sec"), None);
+        assert_eq!(parse_query("abc usd in eur"), None);
+    }
+
+    #[test]
+    fn test_convert_through_base() {
+        let table = sample_table();
+        assert_eq!(table.convert(100.0, "EUR", "USD"), Some(125.0));
+        assert_eq!(table.convert(125.0, "usd", "eur"), Some(100.0));
+        assert_eq!(table.convert(10.0, "USD", "GBP"), Some(6.4));
+        assert_eq!(table.convert(1.0, "USD", "XYZ"), None);
+        assert!(table.knows("jpy"));
+        assert!(!table.knows("min"));
+    }
+
+    #[test]
+    fn test_convert_query_reports_date_and_staleness() {
+        let table = sample_table();
+
+        let fresh = convert_query("100 usd in eur", &table, 7, date("2026-10-12")).unwrap();
+        assert_eq!(fresh.result_text(), "80.00 EUR");
+        assert_eq!(fresh.source_text(), "100.00 USD");
+        assert_eq!(fresh.rates_date, date("2026-10-10"));
+        assert_eq!(fresh.stale_days, None);
+
+        let stale = convert_query("100 usd in eur", &table, 7, date("2026-10-30")).unwrap();
+        assert_eq!(stale.stale_days, Some(20));
+
+        assert!(convert_query("100 usd in xyz", &table, 7, date("2026-10-12")).is_none());
+    }
+
+    #[test]
+    fn test_cached_rates_reload_on_change() {
+        let dir = std::env::temp_dir().join(format!("nexus_rates_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(RATES_FILE_NAME);
+        fs::write(&path, r#"{"base": "EUR", "date": "2026-10-10", "rates": {"USD": 1.25}}"#).unwrap();
+
+        let first = cached_rates(&path).unwrap();
+        assert!(Arc::ptr_eq(&first, &cached_rates(&path).unwrap()));
+
+        // Rewriting the file with a later timestamp triggers a reload
+        fs::write(&path, r#"{"base": "EUR", "date": "2026-10-11", "rates": {"USD": 1.5}}"#).unwrap();
+        let later = SystemTime::now() + std::time::Duration::from_secs(5);
+        fs::File::options().write(true).open(&path).unwrap().set_modified(later).unwrap();
+        assert_eq!(cached_rates(&path).unwrap().convert(1.0, "EUR", "USD"), Some(1.5));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_format_amount() {
+        assert_eq!(format_amount(1234.5), "1234.50");
+        assert_eq!(format_amount(0.004), "0.004");
+        assert_eq!(format_amount(0.0), "0.00");
+    }
+}
@@ -0,0 +1,669 @@
+//! Date and time queries module
+//! Answers "now + 3 days", "2026-12-24 - today", "time in tokyo", "15:00 PST in CET"
+//! and "unix 1760000000" / "ts 2026-10-18 09:00" using a small grammar and the bundled time-zone database
+
+use chrono::{
+    DateTime, Datelike, Duration, FixedOffset, Local, Months, NaiveDate, NaiveDateTime,
+    NaiveTime, TimeZone, Utc,
+};
+use chrono_tz::Tz;
+
+/// A time zone referenced in a query
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Zone {
+    /// The system time zone
+    Local,
+    /// An IANA zone from the bundled database, with DST rules
+    Named(Tz),
+    /// A fixed-offset abbreviation such as "PST"
+    Fixed(&'static str, FixedOffset),
+}
+
+/// The clock queries are evaluated against; tests pin it to a fixed instant
+#[derive(Debug, Clone, Copy)]
+pub struct Clock {
+    pub now: DateTime<Utc>,
+    pub local: Zone,
+}
+
+/// An evaluated date/time query
+#[derive(Debug, Clone, PartialEq)]
+pub struct DateTimeAnswer {
+    /// Main answer, e.g. "Tue, 21 Oct 2026 14:00"
+    pub title: String,
+    /// Explanation shown under the answer
+    pub detail: String,
+    /// Plain value suitable for copying
+    pub value: String,
+}
+
+/// Common time-zone abbreviations as fixed offsets (hours, minutes)
+const ABBREVIATIONS: &[(&str, i32, i32)] = &[
+    ("UTC", 0, 0), ("GMT", 0, 0), ("Z", 0, 0), ("WET", 0, 0), ("WEST", 1, 0),
+    ("BST", 1, 0), ("CET", 1, 0), ("CEST", 2, 0), ("EET", 2, 0), ("EEST", 3, 0),
+    ("MSK", 3, 0), ("IST", 5, 30), ("SGT", 8, 0), ("HKT", 8, 0), ("JST", 9, 0),
+    ("KST", 9, 0), ("AEST", 10, 0), ("AEDT", 11, 0), ("NZST", 12, 0), ("NZDT", 13, 0),
+    ("HST", -10, 0), ("AKST", -9, 0), ("AKDT", -8, 0), ("PST", -8, 0), ("PDT", -7, 0),
+    ("MST", -7, 0), ("MDT", -6, 0), ("CST", -6, 0), ("CDT", -5, 0), ("EST", -5, 0),
+    ("EDT", -4, 0),
+];
+
+/// Cities that are not the name of their IANA zone
+const CITY_ALIASES: &[(&str, Tz)] = &[
+    ("san francisco", Tz::America__Los_Angeles),
+    ("sf", Tz::America__Los_Angeles),
+    ("seattle", Tz::America__Los_Angeles),
+    ("boston", Tz::America__New_York),
+    ("washington", Tz::America__New_York),
+    ("nyc", Tz::America__New_York),
+    ("austin", Tz::America__Chicago),
+    ("dallas", Tz::America__Chicago),
+    ("beijing", Tz::Asia__Shanghai),
+    ("delhi", Tz::Asia__Kolkata),
+    ("new delhi", Tz::Asia__Kolkata),
+    ("mumbai", Tz::Asia__Kolkata),
+    ("bangalore", Tz::Asia__Kolkata),
+    ("osaka", Tz::Asia__Tokyo),
+    ("munich", Tz::Europe__Berlin),
+    ("frankfurt", Tz::Europe__Berlin),
+    ("barcelona", Tz::Europe__Madrid),
+    ("milan", Tz::Europe__Rome),
+    ("geneva", Tz::Europe__Zurich),
+];
+
+const CONNECTORS: &[&str] = &["in", "to", "as"];
+
+impl Clock {
+    /// The real clock in the system time zone
+    pub fn system() -> Self {
+        Self { now: Utc::now(), local: Zone::Local }
+    }
+
+    fn local_now(&self) -> DateTime<FixedOffset> {
+        self.local.at(self.now)
+    }
+}
+
+impl Zone {
+    /// The given UTC instant as seen in this zone
+    pub fn at(&self, instant: DateTime<Utc>) -> DateTime<FixedOffset> {
+        match self {
+            Zone::Local => instant.with_timezone(&Local).fixed_offset(),
+            Zone::Named(tz) => instant.with_timezone(tz).fixed_offset(),
+            Zone::Fixed(_, offset) => instant.with_timezone(offset),
+        }
+    }
+
+    /// Interpret a wall-clock time in this zone (earliest instant when ambiguous)
+    pub fn localize(&self, naive: NaiveDateTime) -> Option<DateTime<FixedOffset>> {
+        match self {
+            Zone::Local => Local.from_local_datetime(&naive).earliest().map(|d| d.fixed_offset()),
+            Zone::Named(tz) => tz.from_local_datetime(&naive).earliest().map(|d| d.fixed_offset()),
+            Zone::Fixed(_, offset) => offset.from_local_datetime(&naive).earliest(),
+        }
+    }
+
+    /// Display name: the abbreviation, IANA name or "local time"
+    pub fn name(&self) -> String {
+        match self {
+            Zone::Local => "local time".to_string(),
+            Zone::Named(tz) => tz.name().to_string(),
+            Zone::Fixed(abbreviation, _) => abbreviation.to_string(),
+        }
+    }
+}
+
+/// Resolve a zone from an abbreviation, IANA name or city ("tokyo", "new york")
+pub fn find_zone(name: &str) -> Option<Zone> {
+    let name = name.trim();
+    if name.is_empty() {
+        return None;
+    }
+
+    if name.eq_ignore_ascii_case("local") || name.eq_ignore_ascii_case("here") {
+        return Some(Zone::Local);
+    }
+
+    if let Some(&(abbreviation, hours, minutes)) = ABBREVIATIONS
+        .iter()
+        .find(|(abbreviation, _, _)| abbreviation.eq_ignore_ascii_case(name))
+    {
+        let seconds = hours * 3600 + hours.signum() * minutes * 60;
+        return FixedOffset::east_opt(seconds).map(|offset| Zone::Fixed(abbreviation, offset));
+    }
+
+    let city = name.to_lowercase().replace('_', " ");
+    if let Some((_, tz)) = CITY_ALIASES.iter().find(|(alias, _)| *alias == city) {
+        return Some(Zone::Named(*tz));
+    }
+
+    chrono_tz::TZ_VARIANTS
+        .iter()
+        .find(|tz| tz.name().eq_ignore_ascii_case(name))
+        .or_else(|| {
+            chrono_tz::TZ_VARIANTS.iter().find(|tz| {
+                tz.name()
+                    .rsplit('/')
+                    .next()
+                    .is_some_and(|last| last.replace('_', " ").eq_ignore_ascii_case(&city))
+            })
+        })
+        .map(|tz| Zone::Named(*tz))
+}
+
+/// Evaluate a date/time query against a clock
+pub fn evaluate(query: &str, clock: &Clock) -> Option<DateTimeAnswer> {
+    let query = query.trim();
+    if query.is_empty() {
+        return None;
+    }
+
+    evaluate_unix(query, clock)
+        .or_else(|| evaluate_time_in(query, clock))
+        .or_else(|| evaluate_zone_conversion(query, clock))
+        .or_else(|| evaluate_arithmetic(query, clock))
+}
+
+/// "unix 1760000000" (seconds or milliseconds), "unix now" and the reverse
+/// "unix 2026-10-18 09:00"; "epoch" and "ts" are aliases
+fn evaluate_unix(query: &str, clock: &Clock) -> Option<DateTimeAnswer> {
+    let (keyword, argument) = query.trim().split_once(char::is_whitespace)?;
+    if !["unix", "epoch", "ts"].iter().any(|k| keyword.eq_ignore_ascii_case(k)) {
+        return None;
+    }
+
+    let argument = argument.trim();
+    if argument.contains(char::is_whitespace) || (argument.contains('-') && !argument.starts_with('-')) {
+        return evaluate_unix_of_date(argument, clock);
+    }
+
+    if argument.eq_ignore_ascii_case("now") {
+        let timestamp = clock.now.timestamp();
+        return Some(DateTimeAnswer {
+            title: timestamp.to_string(),
+            detail: format!("Unix timestamp of {}", clock.now.format("%Y-%m-%d %H:%M:%S UTC")),
+            value: timestamp.to_string(),
+        });
+    }
+
+    let number: i64 = argument.parse().ok()?;
+    // Values this large are milliseconds (seconds would be past the year 5000)
+    let instant = if number.abs() >= 100_000_000_000 {
+        DateTime::<Utc>::from_timestamp_millis(number)?
+    } else {
+        DateTime::<Utc>::from_timestamp(number, 0)?
+    };
+
+    let local = clock.local.at(instant);
+    Some(DateTimeAnswer {
+        title: instant.format("%Y-%m-%d %H:%M:%S UTC").to_string(),
+        detail: format!(
+            "{} {} (UTC{})",
+            capitalize(&clock.local.name()),
+            local.format("%Y-%m-%d %H:%M:%S"),
+            local.format("%:z")
+        ),
+        value: instant.to_rfc3339_opts(chrono::SecondsFormat::AutoSi, true),
+    })
+}
+
+/// Unix timestamp of an RFC 3339 instant or a local "YYYY-MM-DD[ HH:MM[:SS]]"
+fn evaluate_unix_of_date(argument: &str, clock: &Clock) -> Option<DateTimeAnswer> {
+    let instant = match DateTime::parse_from_rfc3339(argument) {
+        Ok(instant) => instant,
+        Err(_) => {
+            let naive = ["%Y-%m-%d %H:%M:%S", "%Y-%m-%d %H:%M", "%Y-%m-%dT%H:%M:%S", "%Y-%m-%dT%H:%M"]
+                .iter()
+                .find_map(|format| NaiveDateTime::parse_from_str(argument, format).ok())
+                .or_else(|| {
+                    NaiveDate::parse_from_str(argument, "%Y-%m-%d")
+                        .ok()
+                        .map(|date| date.and_time(NaiveTime::MIN))
+                })?;
+            clock.local.localize(naive)?
+        }
+    };
+
+    let timestamp = instant.timestamp();
+    Some(DateTimeAnswer {
+        title: timestamp.to_string(),
+        detail: format!(
+            "Unix timestamp of {} · {} ms",
+            instant.with_timezone(&Utc).format("%Y-%m-%d %H:%M:%S UTC"),
+            instant.timestamp_millis()
+        ),
+        value: timestamp.to_string(),
+    })
+}
+
+/// "time in tokyo" / "time tokyo"
+fn evaluate_time_in(query: &str, clock: &Clock) -> Option<DateTimeAnswer> {
+    let rest = strip_word(query, "time")?;
+    let place = strip_word(rest, "in").unwrap_or(rest);
+    let zone = find_zone(place)?;
+    let time = zone.at(clock.now);
+
+    Some(DateTimeAnswer {
+        title: format!("{} · {}", time.format("%H:%M"), time.format("%a, %-d %b %Y")),
+        detail: format!("Time in {} (UTC{})", zone.name(), time.format("%:z")),
+        value: time.format("%H:%M").to_string(),
+    })
+}
+
+/// "15:00 PST in CET" / "3pm tokyo to london"
+fn evaluate_zone_conversion(query: &str, clock: &Clock) -> Option<DateTimeAnswer> {
+    let words: Vec<&str> = query.split_whitespace().collect();
+    let connector = words
+        .iter()
+        .position(|w| CONNECTORS.iter().any(|c| w.eq_ignore_ascii_case(c)))?;
+
+    let time = parse_time(words.first()?)?;
+    let from = find_zone(&words.get(1..connector)?.join(" "))?;
+    let to = find_zone(&words.get(connector + 1..)?.join(" "))?;
+
+    // The time is taken on today's date in the source zone
+    let source_date = from.at(clock.now).date_naive();
+    let source = from.localize(source_date.and_time(time))?;
+    let target = to.at(source.with_timezone(&Utc));
+
+    let day_note = match (target.date_naive() - source.date_naive()).num_days() {
+        0 => String::new(),
+        1 => ", next day".to_string(),
+        -1 => ", previous day".to_string(),
+        days => format!(", {:+} days", days),
+    };
+
+    Some(DateTimeAnswer {
+        title: format!("{} {}", target.format("%H:%M"), to.name()),
+        detail: format!(
+            "{} {} (UTC{}) = {} (UTC{}){}",
+            source.format("%H:%M"),
+            from.name(),
+            source.format("%:z"),
+            target.format("%a %H:%M"),
+            target.format("%:z"),
+            day_note
+        ),
+        value: target.format("%H:%M").to_string(),
+    })
+}
+
+/// Lexical tokens of the arithmetic grammar
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Date(NaiveDate),
+    Time(NaiveTime),
+    Number(i64),
+    Word(String),
+    Plus,
+    Minus,
+}
+
+/// A point in time; `has_time` is false for whole dates
+#[derive(Debug, Clone, Copy)]
+struct Point {
+    at: NaiveDateTime,
+    has_time: bool,
+}
+
+/// Date arithmetic: `point ((+|-) duration)*` or `point - point`
+fn evaluate_arithmetic(query: &str, clock: &Clock) -> Option<DateTimeAnswer> {
+    let tokens = tokenize(query)?;
+    let today = clock.local_now().naive_local();
+    let mut position = 0;
+
+    let start = parse_point(&tokens, &mut position, today)?;
+    let mut point = start;
+    let mut applied = false;
+
+    while position < tokens.len() {
+        let sign = match tokens[position] {
+            Token::Plus => 1,
+            Token::Minus => -1,
+            _ => return None,
+        };
+        position += 1;
+
+        // "point - point" must be the whole expression
+        if sign < 0 && !applied {
+            let mut lookahead = position;
+            if let Some(other) = parse_point(&tokens, &mut lookahead, today) {
+                if lookahead == tokens.len() {
+                    return Some(difference_answer(point, other));
+                }
+            }
+        }
+
+        point = apply_duration(point, &tokens, &mut position, sign)?;
+        applied = true;
+    }
+
+    Some(point_answer(point, today))
+}
+
+fn tokenize(query: &str) -> Option<Vec<Token>> {
+    let chars: Vec<char> = query.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() {
+            i += 1;
+        } else if c == '+' {
+            tokens.push(Token::Plus);
+            i += 1;
+        } else if c == '-' {
+            tokens.push(Token::Minus);
+            i += 1;
+        } else if c.is_ascii_digit() {
+            let start = i;
+            while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == ':') {
+                i += 1;
+            }
+            // ISO dates: 4 digits, '-', 2 digits, '-', 2 digits
+            let rest: String = chars[start..chars.len().min(start + 10)].iter().collect();
+            if let Ok(date) = NaiveDate::parse_from_str(&rest, "%Y-%m-%d") {
+                if rest.len() == 10 {
+                    tokens.push(Token::Date(date));
+                    i = start + 10;
+                    continue;
+                }
+            }
+            let text: String = chars[start..i].iter().collect();
+            if text.contains(':') {
+                tokens.push(Token::Time(parse_time(&text)?));
+            } else {
+                tokens.push(Token::Number(text.parse().ok()?));
+            }
+        } else if c.is_alphabetic() {
+            let start = i;
+            while i < chars.len() && chars[i].is_alphabetic() {
+                i += 1;
+            }
+            tokens.push(Token::Word(chars[start..i].iter().collect::<String>().to_lowercase()));
+        } else {
+            return None;
+        }
+    }
+
+    Some(tokens)
+}
+
+fn parse_point(tokens: &[Token], position: &mut usize, now: NaiveDateTime) -> Option<Point> {
+    let point = match tokens.get(*position)? {
+        Token::Word(word) => match word.as_str() {
+            "now" => Point { at: now, has_time: true },
+            "today" => Point { at: now.date().and_time(NaiveTime::MIN), has_time: false },
+            "tomorrow" => Point { at: (now.date() + Duration::days(1)).and_time(NaiveTime::MIN), has_time: false },
+            "yesterday" => Point { at: (now.date() - Duration::days(1)).and_time(NaiveTime::MIN), has_time: false },
+            _ => return None,
+        },
+        Token::Date(date) => match tokens.get(*position + 1) {
+            Some(Token::Time(time)) => {
+                *position += 1;
+                Point { at: date.and_time(*time), has_time: true }
+            }
+            _ => Point { at: date.and_time(NaiveTime::MIN), has_time: false },
+        },
+        _ => return None,
+    };
+    *position += 1;
+    Some(point)
+}
+
+fn apply_duration(point: Point, tokens: &[Token], position: &mut usize, sign: i64) -> Option<Point> {
+    let amount = match tokens.get(*position)? {
+        Token::Number(n) => *n,
+        _ => return None,
+    };
+    let unit = match tokens.get(*position + 1)? {
+        Token::Word(word) => word.as_str(),
+        _ => return None,
+    };
+    *position += 2;
+
+    let signed = amount.checked_mul(sign)?;
+    let months = |n: i64| -> Option<NaiveDateTime> {
+        let m = Months::new(u32::try_from(n.unsigned_abs()).ok()?);
+        if n >= 0 {
+            point.at.checked_add_months(m)
+        } else {
+            point.at.checked_sub_months(m)
+        }
+    };
+
+    let (at, adds_time) = match unit {
+        "s" | "sec" | "secs" | "second" | "seconds" => (point.at.checked_add_signed(Duration::try_seconds(signed)?)?, true),
+        "m" | "min" | "mins" | "minute" | "minutes" => (point.at.checked_add_signed(Duration::try_minutes(signed)?)?, true),
+        "h" | "hr" | "hrs" | "hour" | "hours" => (point.at.checked_add_signed(Duration::try_hours(signed)?)?, true),
+        "d" | "day" | "days" => (point.at.checked_add_signed(Duration::try_days(signed)?)?, false),
+        "w" | "wk" | "week" | "weeks" => (point.at.checked_add_signed(Duration::try_weeks(signed)?)?, false),
+        "mo" | "month" | "months" => (months(signed)?, false),
+        "y" | "yr" | "year" | "years" => (months(signed.checked_mul(12)?)?, false),
+        _ => return None,
+    };
+
+    Some(Point { at, has_time: point.has_time || adds_time })
+}
+
+fn point_answer(point: Point, now: NaiveDateTime) -> DateTimeAnswer {
+    let days = (point.at.date() - now.date()).num_days();
+    let relative = match days {
+        0 => "today".to_string(),
+        1 => "tomorrow".to_string(),
+        -1 => "yesterday".to_string(),
+        d if d > 0 => format!("in {} days", d),
+        d => format!("{} days ago", -d),
+    };
+
+    if point.has_time {
+        DateTimeAnswer {
+            title: point.at.format("%a, %-d %b %Y %H:%M").to_string(),
+            detail: format!("{} · week {} · {}", point.at.format("%Y-%m-%d %H:%M"), point.at.iso_week().week(), relative),
+            value: point.at.format("%Y-%m-%d %H:%M").to_string(),
+        }
+    } else {
+        DateTimeAnswer {
+            title: point.at.format("%A, %-d %B %Y").to_string(),
+            detail: format!("{} · week {} · {}", point.at.format("%Y-%m-%d"), point.at.iso_week().week(), relative),
+            value: point.at.format("%Y-%m-%d").to_string(),
+        }
+    }
+}
+
+fn difference_answer(from: Point, to: Point) -> DateTimeAnswer {
+    let span = from.at - to.at;
+    let days = span.num_days();
+
+    if !from.has_time && !to.has_time {
+        let breakdown = format!("{} weeks {} days", days / 7, days % 7);
+        return DateTimeAnswer {
+            title: plural(days, "day"),
+            detail: format!("{} · from {} to {}", breakdown, to.at.format("%Y-%m-%d"), from.at.format("%Y-%m-%d")),
+            value: days.to_string(),
+        };
+    }
+
+    let hours = span.num_hours() - days * 24;
+    let minutes = span.num_minutes() - span.num_hours() * 60;
+    DateTimeAnswer {
+        title: format!("{} {} {}", plural(days, "day"), plural(hours, "hour"), plural(minutes, "minute")),
+        detail: format!("{} hours in total", span.num_minutes() as f64 / 60.0),
+        value: span.num_seconds().to_string(),
+    }
+}
+
+/// Parse "15:00", "9:30:15", "3pm" or "3:30pm"
+fn parse_time(text: &str) -> Option<NaiveTime> {
+    let lower = text.to_lowercase();
+    let (clock, meridiem) = if let Some(rest) = lower.strip_suffix("am") {
+        (rest, Some(false))
+    } else if let Some(rest) = lower.strip_suffix("pm") {
+        (rest, Some(true))
+    } else {
+        (lower.as_str(), None)
+    };
+
+    let mut parts = clock.split(':');
+    let hour: u32 = parts.next()?.parse().ok()?;
+    let minute: u32 = parts.next().map(|m| m.parse().ok()).unwrap_or(Some(0))?;
+    let second: u32 = parts.next().map(|s| s.parse().ok()).unwrap_or(Some(0))?;
+    if parts.next().is_some() || (meridiem.is_none() && !clock.contains(':')) {
+        return None;
+    }
+
+    let hour = match meridiem {
+        Some(pm) if (1..=12).contains(&hour) => hour % 12 + if pm { 12 } else { 0 },
+        Some(_) => return None,
+        None => hour,
+    };
+
+    NaiveTime::from_hms_opt(hour, minute, second)
+}
+
+/// Strip a leading case-insensitive word followed by whitespace
+fn strip_word<'a>(text: &'a str, word: &str) -> Option<&'a str> {
+    let head = text.get(..word.len())?;
+    let rest = &text[word.len()..];
+    if head.eq_ignore_ascii_case(word) && rest.starts_with(char::is_whitespace) {
+        Some(rest.trim_start())
+    } else {
+        None
+    }
+}
+
+fn plural(count: i64, unit: &str) -> String {
+    if count.abs() == 1 {
+        format!("{} {}", count, unit)
+    } else {
+        format!("{} {}s", count, unit)
+    }
+}
+
+fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Saturday 2026-10-17 12:00 UTC, local zone Europe/Berlin (CEST, UTC+2)
+    fn clock() -> Clock {
+        Clock {
+            now: Utc.with_ymd_and_hms(2026, 10, 17, 12, 0, 0).unwrap(),
+            local: Zone::Named(Tz::Europe__Berlin),
+        }
+    }
+
+    fn eval(query: &str) -> DateTimeAnswer {
+        evaluate(query, &clock()).unwrap_or_else(|| panic!("query should evaluate: {}", query))
+    }
+
+    #[test]
+    fn test_now_and_relative_days() {
+        assert_eq!(eval("now").value, "2026-10-17 14:00");
+        assert_eq!(eval("today").title, "Saturday, 17 October 2026");
+        assert_eq!(eval("tomorrow").value, "2026-10-18");
+        assert_eq!(eval("now + 3 days").value, "2026-10-20 14:00");
+        assert_eq!(eval("today + 2 weeks").value, "2026-10-31");
+        assert_eq!(eval("today - 1 month").value, "2026-09-17");
+        assert_eq!(eval("now + 90 min").value, "2026-10-17 15:30");
+        assert_eq!(eval("today + 1 year + 2 days").value, "2027-10-19");
+        assert!(eval("today + 3 days").detail.ends_with("in 3 days"));
+    }
+
+    #[test]
+    fn test_date_difference() {
+        let answer = eval("2026-12-24 - today");
+        assert_eq!(answer.title, "68 days");
+        assert_eq!(answer.value, "68");
+        assert!(answer.detail.starts_with("9 weeks 5 days"));
+
+        assert_eq!(eval("2026-01-01 - 2026-12-25").title, "-358 days");
+        assert_eq!(eval("2026-10-18 12:00 - now").title, "0 days 22 hours 0 minutes");
+    }
+
+    #[test]
+    fn test_date_literal_with_time() {
+        assert_eq!(eval("2026-12-24 18:30").value, "2026-12-24 18:30");
+        assert_eq!(eval("2026-12-24 - 1 day").value, "2026-12-23");
+    }
+
+    #[test]
+    fn test_month_end_clamping() {
+        assert_eq!(eval("2026-01-31 + 1 month").value, "2026-02-28");
+    }
+
+    #[test]
+    fn test_time_in_city() {
+        let answer = eval("time in tokyo");
+        assert_eq!(answer.value, "21:00");
+        assert_eq!(answer.detail, "Time in Asia/Tokyo (UTC+09:00)");
+
+        assert_eq!(eval("time new york").value, "08:00");
+        assert_eq!(eval("time in san francisco").value, "05:00");
+        assert_eq!(eval("time in UTC").value, "12:00");
+    }
+
+    #[test]
+    fn test_zone_conversion() {
+        let answer = eval("15:00 PST in CET");
+        assert_eq!(answer.title, "00:00 CET");
+        assert!(answer.detail.ends_with("next day"));
+
+        assert_eq!(eval("9am london to tokyo").title, "17:00 Asia/Tokyo");
+        assert_eq!(eval("3:30pm EST to IST").title, "02:00 IST");
+    }
+
+    #[test]
+    fn test_unix_timestamps() {
+        let answer = eval("unix 1760000000");
+        assert_eq!(answer.title, "2025-10-09 08:53:20 UTC");
+        assert_eq!(answer.value, "2025-10-09T08:53:20Z");
+        assert_eq!(answer.detail, "Europe/Berlin 2025-10-09 10:53:20 (UTC+02:00)");
+
+        assert_eq!(eval("unix 1760000000000").title, "2025-10-09 08:53:20 UTC");
+        assert_eq!(eval("unix now").value, "1792238400");
+        assert_eq!(eval("ts -86400").title, "1969-12-31 00:00:00 UTC");
+
+        let answer = eval("ts 2025-10-09 10:53:20");
+        assert_eq!(answer.value, "1760000000");
+        assert_eq!(answer.detail, "Unix timestamp of 2025-10-09 08:53:20 UTC · 1760000000000 ms");
+        assert_eq!(eval("epoch 2025-10-09T08:53:20Z").value, "1760000000");
+        assert_eq!(eval("unix 2026-10-17").value, "1792188000");
+        assert!(evaluate("ts tomorrow-ish", &clock()).is_none());
+    }
+
+    #[test]
+    fn test_find_zone() {
+        assert_eq!(find_zone("Europe/Paris"), Some(Zone::Named(Tz::Europe__Paris)));
+        assert_eq!(find_zone("los angeles"), Some(Zone::Named(Tz::America__Los_Angeles)));
+        assert!(matches!(find_zone("pst"), Some(Zone::Fixed("PST", _))));
+        assert_eq!(find_zone("atlantis"), None);
+    }
+
+    #[test]
+    fn test_parse_time() {
+        assert_eq!(parse_time("15:00"), NaiveTime::from_hms_opt(15, 0, 0));
+        assert_eq!(parse_time("12am"), NaiveTime::from_hms_opt(0, 0, 0));
+        assert_eq!(parse_time("12pm"), NaiveTime::from_hms_opt(12, 0, 0));
+        assert_eq!(parse_time("3:45pm"), NaiveTime::from_hms_opt(15, 45, 0));
+        assert_eq!(parse_time("15"), None);
+        assert_eq!(parse_time("25:00"), None);
+    }
+
+    #[test]
+    fn test_rejects_other_queries() {
+        let clock = clock();
+        assert!(evaluate("notepad", &clock).is_none());
+        assert!(evaluate("2+2", &clock).is_none());
+        assert!(evaluate("now + 3 parsecs", &clock).is_none());
+        assert!(evaluate("time in atlantis", &clock).is_none());
+    }
+}
@@ -0,0 +1,517 @@
+//! Exact arithmetic module
+//! Evaluates calculator expressions over arbitrary-precision rationals, so `0.1+0.2` is
+//! exactly 0.3, `1/3 + 1/6` is 1/2 and `30!` keeps every digit. Expressions that need
+//! transcendental functions or constants are left to the f64 evaluator.
+
+use num_bigint::BigInt;
+use num_rational::BigRational;
+use num_traits::{One, Signed, ToPrimitive, Zero};
+use std::sync::OnceLock;
+
+/// Largest argument accepted by the factorial operator (1000! has 2,568 digits)
+const MAX_FACTORIAL: u64 = 1_000;
+
+/// Upper bound on the size of an exact result, in bits (about 4,900 digits); larger
+/// results are left to the f64 evaluator so formatting stays fast while typing
+const MAX_RESULT_BITS: u64 = 16_384;
+
+/// Integers with more digits than this are shown in scientific notation
+const MAX_DISPLAY_DIGITS: usize = 1_000;
+
+/// Fractional digits shown for non-terminating decimal results
+const DECIMAL_PLACES: usize = 20;
+
+/// Exactly evaluated calculator result
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExactValue {
+    pub value: BigRational,
+
+    /// Whether the expression used decimal literals; non-integer results are then shown
+    /// as decimals instead of fractions
+    pub decimal_input: bool,
+}
+
+impl ExactValue {
+    /// Result as displayed: an integer, a terminating decimal, a fraction (`1/2`) or a
+    /// rounded decimal when the input was decimal and the result does not terminate
+    pub fn display(&self) -> String {
+        if self.value.is_integer() {
+            format_integer(self.value.numer())
+        } else if let Some(decimal) = terminating_decimal(&self.value) {
+            if self.decimal_input {
+                decimal
+            } else {
+                format_fraction(&self.value)
+            }
+        } else if self.decimal_input {
+            rounded_decimal(&self.value, DECIMAL_PLACES)
+        } else {
+            format_fraction(&self.value)
+        }
+    }
+
+    /// Whether the displayed result is rounded rather than exact
+    pub fn is_rounded(&self) -> bool {
+        let long_integer =
+            self.value.is_integer() && self.value.numer().abs().to_string().len() > MAX_DISPLAY_DIGITS;
+        let long_decimal = self.decimal_input
+            && !self.value.is_integer()
+            && terminating_decimal(&self.value).is_none();
+        long_integer || long_decimal
+    }
+
+    /// Decimal form used alongside fractions: exact when it terminates, "≈ …" otherwise
+    pub fn decimal_note(&self) -> Option<String> {
+        if self.value.is_integer() {
+            return None;
+        }
+        match terminating_decimal(&self.value) {
+            Some(decimal) if !self.decimal_input => Some(format!("= {}", decimal)),
+            Some(_) => None,
+            None if self.decimal_input => None,
+            None => Some(format!("≈ {}", rounded_decimal(&self.value, 6))),
+        }
+    }
+
+    /// Nearest f64, for session variables and anything else that needs a float
+    pub fn to_f64(&self) -> f64 {
+        self.value.to_f64().unwrap_or(f64::NAN)
+    }
+}
+
+/// Evaluate an expression exactly. Returns `None` for anything outside rational
+/// arithmetic (functions, constants, non-integer powers, unknown names) so the caller
+/// can fall back to floating point.
+pub fn evaluate(
+    expression: &str,
+    variable: impl Fn(&str) -> Option<BigRational>,
+) -> Option<ExactValue> {
+    let tokens = tokenize(expression)?;
+    let decimal_input = tokens.iter().any(|t| matches!(t, Token::Number(_, true)));
+
+    let mut parser = Parser {
+        tokens: &tokens,
+        position: 0,
+        variable: &variable,
+    };
+    let value = parser.parse_additive()?;
+    if parser.position != tokens.len() {
+        return None;
+    }
+    if value.numer().bits().max(value.denom().bits()) > MAX_RESULT_BITS {
+        return None;
+    }
+
+    Some(ExactValue {
+        value,
+        decimal_input,
+    })
+}
+
+/// Exact rational for a float via its shortest round-trip representation, so 0.1
+/// becomes 1/10 rather than the binary approximation
+pub fn from_f64(value: f64) -> Option<BigRational> {
+    if !value.is_finite() {
+        return None;
+    }
+    parse_decimal(&format!("{:e}", value))
+}
+
+/// Rewrite decimal commas (`2,5`) to points for the evaluators. In decimal-comma
+/// locales `;` separates function arguments, as in spreadsheets.
+pub fn normalize_decimal_comma(expression: &str) -> String {
+    let chars: Vec<char> = expression.chars().collect();
+    chars
+        .iter()
+        .enumerate()
+        .map(|(i, &c)| match c {
+            ',' if i > 0
+                && chars[i - 1].is_ascii_digit()
+                && chars.get(i + 1).is_some_and(|next| next.is_ascii_digit()) =>
+            {
+                '.'
+            }
+            ';' => ',',
+            _ => c,
+        })
+        .collect()
+}
+
+/// Whether the user's locale writes decimals with a comma
+pub fn system_uses_decimal_comma() -> bool {
+    static DECIMAL_COMMA: OnceLock<bool> = OnceLock::new();
+    *DECIMAL_COMMA.get_or_init(detect_decimal_comma)
+}
+
+#[cfg(windows)]
+fn detect_decimal_comma() -> bool {
+    use windows::core::PCWSTR;
+    use windows::Win32::Globalization::{GetLocaleInfoEx, LOCALE_SDECIMAL};
+
+    let mut buffer = [0u16; 8];
+    let len = unsafe { GetLocaleInfoEx(PCWSTR::null(), LOCALE_SDECIMAL, Some(&mut buffer)) };
+    len > 0 && String::from_utf16_lossy(&buffer[..len as usize - 1]) == ","
+}
+
+#[cfg(not(windows))]
+fn detect_decimal_comma() -> bool {
+    // Languages whose common locales use a decimal comma
+    const COMMA_LANGUAGES: &[&str] = &[
+        "bg", "cs", "da", "de", "el", "es", "et", "fi", "fr", "hr", "hu", "id", "it", "lt",
+        "lv", "nb", "nl", "nn", "pl", "pt", "ro", "ru", "sk", "sl", "sr", "sv", "tr", "uk",
+    ];
+
+    let locale = ["LC_ALL", "LC_NUMERIC", "LANG"]
+        .iter()
+        .filter_map(|name| std::env::var(name).ok())
+        .find(|value| !value.is_empty())
+        .unwrap_or_default();
+    let language = locale.split(['_', '.', '-']).next().unwrap_or_default();
+    COMMA_LANGUAGES.contains(&language)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    /// Number and whether it was written with a fraction or exponent
+    Number(BigRational, bool),
+    Ident(String),
+    Op(char),
+}
+
+fn tokenize(expression: &str) -> Option<Vec<Token>> {
+    let chars: Vec<char> = expression.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() {
+            i += 1;
+        } else if c.is_ascii_digit() || c == '.' {
+            let start = i;
+            while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+                i += 1;
+            }
+            // Exponent only when digits follow; a lone `e` is the constant
+            if i < chars.len() && (chars[i] == 'e' || chars[i] == 'E') {
+                let digits_at = match chars.get(i + 1) {
+                    Some('+') | Some('-') => i + 2,
+                    _ => i + 1,
+                };
+                if chars.get(digits_at).is_some_and(|d| d.is_ascii_digit()) {
+                    i = digits_at;
+                    while i < chars.len() && chars[i].is_ascii_digit() {
+                        i += 1;
+                    }
+                }
+            }
+            let text: String = chars[start..i].iter().collect();
+            let decimal = text.contains(['.', 'e', 'E']);
+            tokens.push(Token::Number(parse_decimal(&text)?, decimal));
+        } else if c.is_ascii_alphabetic() || c == '_' {
+            let start = i;
+            while i < chars.len() && (chars[i].is_ascii_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+            tokens.push(Token::Ident(chars[start..i].iter().collect()));
+        } else if "+-*/^%()!".contains(c) {
+            tokens.push(Token::Op(c));
+            i += 1;
+        } else {
+            return None;
+        }
+    }
+
+    Some(tokens)
+}
+
+/// Parse "12.5", ".5" or "1.5e-3" into an exact rational
+fn parse_decimal(text: &str) -> Option<BigRational> {
+    let (mantissa, exponent) = match text.find(['e', 'E']) {
+        Some(pos) => (&text[..pos], text[pos + 1..].parse::<i32>().ok()?),
+        None => (text, 0),
+    };
+    let (negative, mantissa) = match mantissa.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, mantissa),
+    };
+    let (whole, fraction) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+    if whole.is_empty() && fraction.is_empty() {
+        return None;
+    }
+
+    let digits: BigInt = format!("{}{}", whole, fraction).parse().ok()?;
+    let scale = exponent.checked_sub(i32::try_from(fraction.len()).ok()?)?;
+    // 10^n takes about 3.3n bits, so anything beyond this could never fit the result
+    if u64::from(scale.unsigned_abs()) > MAX_RESULT_BITS / 3 {
+        return None;
+    }
+
+    let ten = BigInt::from(10);
+    let value = if scale >= 0 {
+        BigRational::from_integer(digits * num_traits::pow(ten, scale as usize))
+    } else {
+        BigRational::new(digits, num_traits::pow(ten, scale.unsigned_abs() as usize))
+    };
+    Some(if negative { -value } else { value })
+}
+
+struct Parser<'a, F> {
+    tokens: &'a [Token],
+    position: usize,
+    variable: &'a F,
+}
+
+impl<F: Fn(&str) -> Option<BigRational>> Parser<'_, F> {
+    fn peek_op(&self, ops: &str) -> Option<char> {
+        match self.tokens.get(self.position) {
+            Some(Token::Op(op)) if ops.contains(*op) => Some(*op),
+            _ => None,
+        }
+    }
+
+    fn parse_additive(&mut self) -> Option<BigRational> {
+        let mut left = self.parse_multiplicative()?;
+        while let Some(op) = self.peek_op("+-") {
+            self.position += 1;
+            let right = self.parse_multiplicative()?;
+            left = if op == '+' { left + right } else { left - right };
+        }
+        Some(left)
+    }
+
+    fn parse_multiplicative(&mut self) -> Option<BigRational> {
+        let mut left = self.parse_unary()?;
+        while let Some(op) = self.peek_op("*/%") {
+            self.position += 1;
+            let right = self.parse_unary()?;
+            if op != '*' && right.is_zero() {
+                return None;
+            }
+            left = match op {
+                '*' => left * right,
+                '/' => left / right,
+                // Remainder takes the sign of the dividend, like f64's `%`
+                _ => {
+                    let quotient = (&left / &right).trunc();
+                    &left - quotient * right
+                }
+            };
+        }
+        Some(left)
+    }
+
+    /// Unary minus binds looser than `^`, so -2^2 is -4
+    fn parse_unary(&mut self) -> Option<BigRational> {
+        match self.peek_op("+-") {
+            Some(op) => {
+                self.position += 1;
+                let value = self.parse_unary()?;
+                Some(if op == '-' { -value } else { value })
+            }
+            None => self.parse_power(),
+        }
+    }
+
+    fn parse_power(&mut self) -> Option<BigRational> {
+        let base = self.parse_postfix()?;
+        if self.peek_op("^").is_none() {
+            return Some(base);
+        }
+        self.position += 1;
+        // Right-associative: 2^3^2 = 2^9
+        let exponent = self.parse_unary()?;
+        power(&base, &exponent)
+    }
+
+    fn parse_postfix(&mut self) -> Option<BigRational> {
+        let mut value = self.parse_primary()?;
+        while self.peek_op("!").is_some() {
+            self.position += 1;
+            value = factorial(&value)?;
+        }
+        Some(value)
+    }
+
+    fn parse_primary(&mut self) -> Option<BigRational> {
+        let token = self.tokens.get(self.position)?.clone();
+        self.position += 1;
+        match token {
+            Token::Number(value, _) => Some(value),
+            Token::Ident(name) => (self.variable)(&name),
+            Token::Op('(') => {
+                let value = self.parse_additive()?;
+                match self.peek_op(")") {
+                    Some(_) => {
+                        self.position += 1;
+                        Some(value)
+                    }
+                    None => None,
+                }
+            }
+            Token::Op(_) => None,
+        }
+    }
+}
+
+/// Integer powers only; anything else needs floating point
+fn power(base: &BigRational, exponent: &BigRational) -> Option<BigRational> {
+    if !exponent.is_integer() {
+        return None;
+    }
+    let exponent = exponent.to_integer().to_i64()?;
+    if exponent == 0 {
+        return Some(BigRational::one());
+    }
+    if base.is_zero() {
+        return (exponent > 0).then(BigRational::zero);
+    }
+
+    let bits = base.numer().bits().max(base.denom().bits());
+    if bits.saturating_mul(exponent.unsigned_abs()) > MAX_RESULT_BITS {
+        return None;
+    }
+
+    let result = num_traits::pow(base.clone(), exponent.unsigned_abs() as usize);
+    Some(if exponent < 0 { result.recip() } else { result })
+}
+
+fn factorial(value: &BigRational) -> Option<BigRational> {
+    if !value.is_integer() || value.is_negative() {
+        return None;
+    }
+    let n = value.to_integer().to_u64().filter(|n| *n <= MAX_FACTORIAL)?;
+    let product = (2..=n).fold(BigInt::one(), |acc, i| acc * i);
+    Some(BigRational::from_integer(product))
+}
+
+fn format_integer(value: &BigInt) -> String {
+    let digits = value.abs().to_string();
+    if digits.len() <= MAX_DISPLAY_DIGITS {
+        return value.to_string();
+    }
+
+    let sign = if value.is_negative() { "-" } else { "" };
+    let mantissa = digits[1..15].trim_end_matches('0');
+    let separator = if mantissa.is_empty() { "" } else { "." };
+    format!("{}{}{}{}e{}", sign, &digits[..1], separator, mantissa, digits.len() - 1)
+}
+
+fn format_fraction(value: &BigRational) -> String {
+    format!("{}/{}", value.numer(), value.denom())
+}
+
+/// Exact decimal expansion when the denominator only has factors 2 and 5
+fn terminating_decimal(value: &BigRational) -> Option<String> {
+    let mut denom = value.denom().clone();
+    let mut places = 0;
+    for factor in [2u32, 5] {
+        let factor = BigInt::from(factor);
+        while (&denom % &factor).is_zero() {
+            denom /= &factor;
+            places += 1;
+        }
+    }
+    if !denom.is_one() {
+        return None;
+    }
+    Some(rounded_decimal(value, places))
+}
+
+/// Decimal with at most `places` fractional digits, trailing zeros trimmed
+fn rounded_decimal(value: &BigRational, places: usize) -> String {
+    let scale = num_traits::pow(BigInt::from(10), places);
+    let scaled = (value * BigRational::from_integer(scale)).round().to_integer();
+
+    let sign = if scaled.is_negative() { "-" } else { "" };
+    let digits = format!("{:0>width$}", scaled.abs(), width = places + 1);
+    let (whole, fraction) = digits.split_at(digits.len() - places);
+    let fraction = fraction.trim_end_matches('0');
+
+    if fraction.is_empty() {
+        format!("{}{}", sign, whole)
+    } else {
+        format!("{}{}.{}", sign, whole, fraction)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn eval(expression: &str) -> Option<String> {
+        evaluate(expression, |_| None).map(|value| value.display())
+    }
+
+    #[test]
+    fn test_decimals_are_exact() {
+        assert_eq!(eval("0.1+0.2").as_deref(), Some("0.3"));
+        assert_eq!(eval("1.1 * 1.1").as_deref(), Some("1.21"));
+        assert_eq!(eval("2.5e3 / 4").as_deref(), Some("625"));
+
+        let value = evaluate("1.5 / 7", |_| None).unwrap();
+        assert_eq!(value.display(), "0.21428571428571428571");
+        assert!(value.is_rounded());
+    }
+
+    #[test]
+    fn test_fraction_output() {
+        let value = evaluate("1/3 + 1/6", |_| None).unwrap();
+        assert_eq!(value.display(), "1/2");
+        assert_eq!(value.decimal_note().as_deref(), Some("= 0.5"));
+
+        let value = evaluate("2/3", |_| None).unwrap();
+        assert_eq!(value.display(), "2/3");
+        assert_eq!(value.decimal_note().as_deref(), Some("≈ 0.666667"));
+        assert_eq!(eval("-10 / 4").as_deref(), Some("-5/2"));
+    }
+
+    #[test]
+    fn test_big_integers() {
+        assert_eq!(eval("2^64").as_deref(), Some("18446744073709551616"));
+        assert_eq!(eval("25!").as_deref(), Some("15511210043330985984000000"));
+        assert_eq!(eval("10^2000").as_deref(), Some("1e2000"));
+        assert_eq!(eval("2^-2").as_deref(), Some("1/4"));
+
+        // Results of more than a few thousand digits are left to the f64 evaluator
+        assert_eq!(eval("2^3999999"), None);
+        assert_eq!(eval("1e99999"), None);
+        assert!(parse_decimal("1e-99999").is_none());
+        assert_eq!(eval("1001!"), None);
+        assert_eq!(eval("1000! * 1000!"), None);
+        assert!(eval("1000!").is_some());
+    }
+
+    #[test]
+    fn test_precedence_and_remainder() {
+        assert_eq!(eval("-2^2").as_deref(), Some("-4"));
+        assert_eq!(eval("2^3^2").as_deref(), Some("512"));
+        assert_eq!(eval("2 + 3 * (4 - 1)").as_deref(), Some("11"));
+        assert_eq!(eval("-7 % 3").as_deref(), Some("-1"));
+        assert_eq!(eval("3!^2").as_deref(), Some("36"));
+    }
+
+    #[test]
+    fn test_falls_back_for_non_rational() {
+        assert_eq!(eval("sqrt(2)"), None);
+        assert_eq!(eval("2^0.5"), None);
+        assert_eq!(eval("pi * 2"), None);
+        assert_eq!(eval("1/0"), None);
+        assert_eq!(eval("(1 + 2"), None);
+    }
+
+    #[test]
+    fn test_variables_and_floats() {
+        let x = from_f64(0.1).unwrap();
+        let value = evaluate("x * 3", |name| (name == "x").then(|| x.clone())).unwrap();
+        assert_eq!(value.display(), "3/10");
+        assert_eq!(value.to_f64(), 0.3);
+    }
+
+    #[test]
+    fn test_normalize_decimal_comma() {
+        assert_eq!(normalize_decimal_comma("0,1 + 0,2"), "0.1 + 0.2");
+        assert_eq!(normalize_decimal_comma("max(1,5; 2)"), "max(1.5, 2)");
+        assert_eq!(normalize_decimal_comma("max(1, 5)"), "max(1, 5)");
+    }
+}
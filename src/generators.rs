@@ -0,0 +1,433 @@
+//! Generators module
+//! Fresh values on every query: "uuid", "uuid v7", "pw 24 symbols" and "lorem 3p".
+//! Randomness comes from the operating system's secure generator.
+
+use std::error::Error;
+
+use chrono::{DateTime, Utc};
+
+use crate::config::GeneratorsConfig;
+use crate::SearchResultData;
+
+/// UUID layout
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UuidVersion {
+    /// Fully random
+    V4,
+    /// Millisecond timestamp followed by random bits, so values sort by creation time
+    V7,
+}
+
+/// Characters a password can draw from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CharClass {
+    Lower,
+    Upper,
+    Digits,
+    Symbols,
+}
+
+impl CharClass {
+    const ALL: [CharClass; 4] = [CharClass::Lower, CharClass::Upper, CharClass::Digits, CharClass::Symbols];
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "lower" | "lowercase" => Some(CharClass::Lower),
+            "upper" | "uppercase" => Some(CharClass::Upper),
+            "digits" | "numbers" => Some(CharClass::Digits),
+            "symbols" | "special" => Some(CharClass::Symbols),
+            _ => None,
+        }
+    }
+
+    fn chars(self) -> &'static [u8] {
+        match self {
+            CharClass::Lower => b"abcdefghijklmnopqrstuvwxyz",
+            CharClass::Upper => b"ABCDEFGHIJKLMNOPQRSTUVWXYZ",
+            CharClass::Digits => b"0123456789",
+            CharClass::Symbols => b"!#$%&()*+,-./:;<=>?@[]^_{}~",
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            CharClass::Lower => "lowercase",
+            CharClass::Upper => "uppercase",
+            CharClass::Digits => "digits",
+            CharClass::Symbols => "symbols",
+        }
+    }
+}
+
+/// Lorem ipsum amount
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LoremUnit {
+    Words,
+    Sentences,
+    Paragraphs,
+}
+
+/// A parsed generator query
+#[derive(Debug, Clone, PartialEq)]
+pub enum Generator {
+    Uuid(UuidVersion),
+    Password { length: usize, classes: Vec<CharClass> },
+    Lorem { count: usize, unit: LoremUnit },
+    /// The keyword matched but the arguments did not; shown as a hint
+    Invalid(String),
+}
+
+const MIN_PASSWORD_LENGTH: usize = 4;
+const MAX_PASSWORD_LENGTH: usize = 256;
+const MAX_LOREM: usize = 100;
+
+const USAGE: &str = "uuid [v7] · pw [length] [symbols] [no-digits] · lorem [3p | 5s | 20w]";
+
+/// "uuid v7", "pw 24 symbols no-digits", "lorem 3p" / "lorem 20 words"
+pub fn parse_query(query: &str, config: &GeneratorsConfig) -> Option<Generator> {
+    let mut words = query.split_whitespace();
+    let keyword = words.next()?.to_lowercase();
+    let args: Vec<String> = words.map(|word| word.to_lowercase()).collect();
+
+    match keyword.as_str() {
+        "uuid" | "guid" => Some(match args.as_slice() {
+            [] => Generator::Uuid(UuidVersion::V4),
+            [version] if version == "v4" || version == "4" => Generator::Uuid(UuidVersion::V4),
+            [version] if version == "v7" || version == "7" => Generator::Uuid(UuidVersion::V7),
+            _ => Generator::Invalid("Use uuid, uuid v4 or uuid v7".to_string()),
+        }),
+        "pw" | "password" => Some(parse_password(&args, config)),
+        "lorem" => Some(parse_lorem(&args)),
+        _ => None,
+    }
+}
+
+/// Length and class words added to or removed from (`no-symbols`) the configured classes
+fn parse_password(args: &[String], config: &GeneratorsConfig) -> Generator {
+    let mut length = config.password_length;
+    let mut classes: Vec<CharClass> = config
+        .password_classes
+        .iter()
+        .filter_map(|name| CharClass::from_name(name))
+        .collect();
+
+    for arg in args {
+        if let Ok(number) = arg.parse::<usize>() {
+            length = number;
+        } else if let Some(class) = CharClass::from_name(arg) {
+            if !classes.contains(&class) {
+                classes.push(class);
+            }
+        } else if let Some(class) = arg
+            .strip_prefix("no-")
+            .or_else(|| arg.strip_prefix("no"))
+            .and_then(CharClass::from_name)
+        {
+            classes.retain(|c| *c != class);
+        } else {
+            return Generator::Invalid(format!("Unknown option \"{}\"", arg));
+        }
+    }
+
+    if classes.is_empty() {
+        return Generator::Invalid("No character classes left".to_string());
+    }
+    if !(MIN_PASSWORD_LENGTH..=MAX_PASSWORD_LENGTH).contains(&length) {
+        return Generator::Invalid(format!(
+            "Length must be between {} and {}",
+            MIN_PASSWORD_LENGTH, MAX_PASSWORD_LENGTH
+        ));
+    }
+    // Keep a fixed class order so descriptions read the same however the query was typed
+    classes.sort_by_key(|class| CharClass::ALL.iter().position(|c| c == class));
+    Generator::Password { length, classes }
+}
+
+/// "", "3p", "3 paragraphs", "20w", "5 sentences"
+fn parse_lorem(args: &[String]) -> Generator {
+    let joined = args.concat();
+    if joined.is_empty() {
+        return Generator::Lorem {
+            count: 1,
+            unit: LoremUnit::Paragraphs,
+        };
+    }
+
+    let digits = joined.chars().take_while(|c| c.is_ascii_digit()).count();
+    let (count, unit) = joined.split_at(digits);
+    let count = if count.is_empty() { Ok(1) } else { count.parse::<usize>() };
+    let unit = match unit {
+        "" | "p" | "paragraph" | "paragraphs" => Some(LoremUnit::Paragraphs),
+        "s" | "sentence" | "sentences" => Some(LoremUnit::Sentences),
+        "w" | "word" | "words" => Some(LoremUnit::Words),
+        _ => None,
+    };
+    match (count, unit) {
+        (Ok(count @ 1..=MAX_LOREM), Some(unit)) => Generator::Lorem { count, unit },
+        _ => Generator::Invalid(format!("Use lorem 3p, lorem 5s or lorem 20w (up to {})", MAX_LOREM)),
+    }
+}
+
+/// Freshly generated values. Passwords get the "password" result type so they are copied
+/// as secrets; everything else is "generated".
+pub fn search_results(generator: &Generator, now: DateTime<Utc>) -> Vec<SearchResultData> {
+    let generated = match generator {
+        Generator::Uuid(version) => uuid(*version, now).map(|value| {
+            let version = match version {
+                UuidVersion::V4 => "UUID v4",
+                UuidVersion::V7 => "UUID v7",
+            };
+            (value, version.to_string(), "generated")
+        }),
+        Generator::Password { length, classes } => password(*length, classes).map(|value| {
+            let names: Vec<&str> = classes.iter().map(|class| class.name()).collect();
+            (value, format!("{}-character password · {}", length, names.join(", ")), "password")
+        }),
+        Generator::Lorem { count, unit } => lorem(*count, *unit).map(|value| {
+            let words = value.split_whitespace().count();
+            (value, format!("Lorem ipsum · {} words", words), "generated")
+        }),
+        Generator::Invalid(message) => Err(message.clone().into()),
+    };
+
+    match generated {
+        Ok((value, label, result_type)) => vec![SearchResultData {
+            name: crate::toolbox::preview(&value),
+            description: format!("{} · Enter to copy", label),
+            path: value.into(),
+            result_type: result_type.to_string(),
+            ..Default::default()
+        }],
+        Err(e) => vec![SearchResultData {
+            name: e.to_string(),
+            description: USAGE.to_string(),
+            result_type: "info".to_string(),
+            ..Default::default()
+        }],
+    }
+}
+
+/// Bytes from the operating system's cryptographically secure generator
+fn random_bytes<const N: usize>() -> Result<[u8; N], Box<dyn Error>> {
+    let mut bytes = [0u8; N];
+    getrandom::getrandom(&mut bytes).map_err(|e| format!("No secure random source: {}", e))?;
+    Ok(bytes)
+}
+
+/// Uniform value below `bound`; rejection sampling avoids the bias of a plain modulo
+fn random_below(bound: usize) -> Result<usize, Box<dyn Error>> {
+    let bound = bound as u64;
+    let zone = u64::MAX - u64::MAX % bound;
+    loop {
+        let value = u64::from_le_bytes(random_bytes::<8>()?);
+        if value < zone {
+            return Ok((value % bound) as usize);
+        }
+    }
+}
+
+pub fn uuid(version: UuidVersion, now: DateTime<Utc>) -> Result<String, Box<dyn Error>> {
+    let mut bytes = random_bytes::<16>()?;
+    let version_bits = match version {
+        UuidVersion::V4 => 0x40,
+        UuidVersion::V7 => {
+            let millis = now.timestamp_millis().max(0) as u64;
+            bytes[..6].copy_from_slice(&millis.to_be_bytes()[2..]);
+            0x70
+        }
+    };
+    bytes[6] = (bytes[6] & 0x0f) | version_bits;
+    // RFC 9562 variant: the two top bits are 10
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+
+    let hex: String = bytes.iter().map(|byte| format!("{:02x}", byte)).collect();
+    Ok(format!("{}-{}-{}-{}-{}", &hex[..8], &hex[8..12], &hex[12..16], &hex[16..20], &hex[20..]))
+}
+
+/// At least one character from every class, the rest from all of them, shuffled
+pub fn password(length: usize, classes: &[CharClass]) -> Result<String, Box<dyn Error>> {
+    let pool: Vec<u8> = classes.iter().flat_map(|class| class.chars()).copied().collect();
+    let mut chars = Vec::with_capacity(length);
+    for class in classes {
+        let set = class.chars();
+        chars.push(set[random_below(set.len())?]);
+    }
+    while chars.len() < length {
+        chars.push(pool[random_below(pool.len())?]);
+    }
+    // Fisher-Yates, so the guaranteed characters are not always at the front
+    for i in (1..chars.len()).rev() {
+        chars.swap(i, random_below(i + 1)?);
+    }
+    Ok(chars.into_iter().map(char::from).collect())
+}
+
+const LOREM_OPENING: &str = "Lorem ipsum dolor sit amet, consectetur adipiscing elit.";
+
+const LOREM_WORDS: &[&str] = &[
+    "lorem", "ipsum", "dolor", "sit", "amet", "consectetur", "adipiscing", "elit", "sed", "do",
+    "eiusmod", "tempor", "incididunt", "ut", "labore", "et", "dolore", "magna", "aliqua", "enim",
+    "ad", "minim", "veniam", "quis", "nostrud", "exercitation", "ullamco", "laboris", "nisi",
+    "aliquip", "ex", "ea", "commodo", "consequat", "duis", "aute", "irure", "in",
+    "reprehenderit", "voluptate", "velit", "esse", "cillum", "eu", "fugiat", "nulla", "pariatur",
+    "excepteur", "sint", "occaecat", "cupidatat", "non", "proident", "sunt", "culpa", "qui",
+    "officia", "deserunt", "mollit", "anim", "id", "est", "laborum", "integer", "vitae",
+    "sapien", "pellentesque", "habitant", "morbi", "tristique", "senectus", "netus", "fames",
+    "turpis", "egestas", "mauris", "nunc", "congue", "nisl", "purus", "viverra", "accumsan",
+];
+
+/// Placeholder text that always opens with the classic "Lorem ipsum dolor sit amet" sentence
+pub fn lorem(count: usize, unit: LoremUnit) -> Result<String, Box<dyn Error>> {
+    let mut words: Vec<&str> = LOREM_OPENING.trim_end_matches('.').split(' ').collect();
+    if unit == LoremUnit::Words {
+        while words.len() < count {
+            words.push(LOREM_WORDS[random_below(LOREM_WORDS.len())?]);
+        }
+        words.truncate(count);
+        return Ok(capitalize(&words.join(" ")));
+    }
+
+    let mut sentences = vec![LOREM_OPENING.to_string()];
+    let sentence_count = match unit {
+        LoremUnit::Paragraphs => count * 5,
+        _ => count,
+    };
+    while sentences.len() < sentence_count {
+        let length = 6 + random_below(9)?;
+        let mut sentence = Vec::with_capacity(length);
+        for _ in 0..length {
+            sentence.push(LOREM_WORDS[random_below(LOREM_WORDS.len())?]);
+        }
+        sentences.push(format!("{}.", capitalize(&sentence.join(" "))));
+    }
+
+    Ok(match unit {
+        LoremUnit::Paragraphs => {
+            let paragraphs: Vec<String> = sentences.chunks(5).map(|paragraph| paragraph.join(" ")).collect();
+            paragraphs.join("\n\n")
+        }
+        _ => sentences.join(" "),
+    })
+}
+
+fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn parse(query: &str) -> Option<Generator> {
+        parse_query(query, &GeneratorsConfig::default())
+    }
+
+    #[test]
+    fn test_parse_query() {
+        assert_eq!(parse("uuid"), Some(Generator::Uuid(UuidVersion::V4)));
+        assert_eq!(parse("UUID v7"), Some(Generator::Uuid(UuidVersion::V7)));
+        assert_eq!(
+            parse("pw 24 symbols"),
+            Some(Generator::Password {
+                length: 24,
+                classes: vec![CharClass::Lower, CharClass::Upper, CharClass::Digits, CharClass::Symbols],
+            })
+        );
+        assert_eq!(
+            parse("pw 6 no-lower noupper"),
+            Some(Generator::Password {
+                length: 6,
+                classes: vec![CharClass::Digits],
+            })
+        );
+        assert_eq!(
+            parse("lorem 3p"),
+            Some(Generator::Lorem {
+                count: 3,
+                unit: LoremUnit::Paragraphs,
+            })
+        );
+        assert_eq!(
+            parse("lorem 20 words"),
+            Some(Generator::Lorem {
+                count: 20,
+                unit: LoremUnit::Words,
+            })
+        );
+
+        assert!(matches!(parse("pw 2"), Some(Generator::Invalid(_))));
+        assert!(matches!(parse("pw 12 emoji"), Some(Generator::Invalid(_))));
+        assert!(matches!(parse("lorem 0p"), Some(Generator::Invalid(_))));
+        assert_eq!(parse("uuidgen"), None);
+    }
+
+    #[test]
+    fn test_configured_password_classes() {
+        let config = GeneratorsConfig {
+            password_length: 32,
+            password_classes: vec!["digits".to_string(), "symbols".to_string()],
+        };
+        assert_eq!(
+            parse_query("pw", &config),
+            Some(Generator::Password {
+                length: 32,
+                classes: vec![CharClass::Digits, CharClass::Symbols],
+            })
+        );
+    }
+
+    #[test]
+    fn test_uuid() {
+        let now = Utc.with_ymd_and_hms(2026, 10, 18, 12, 0, 0).unwrap();
+        let v4 = uuid(UuidVersion::V4, now).unwrap();
+        assert_eq!(v4.len(), 36);
+        assert_eq!(&v4[14..15], "4");
+        assert!(matches!(&v4[19..20], "8" | "9" | "a" | "b"));
+        assert_ne!(v4, uuid(UuidVersion::V4, now).unwrap());
+
+        // 1792324800000 ms is 0x01a14ee20e00
+        let v7 = uuid(UuidVersion::V7, now).unwrap();
+        assert!(v7.starts_with("01a14ee2-0e00-7"), "{}", v7);
+    }
+
+    #[test]
+    fn test_password() {
+        let classes = [CharClass::Lower, CharClass::Digits, CharClass::Symbols];
+        for _ in 0..50 {
+            let password = password(8, &classes).unwrap();
+            assert_eq!(password.len(), 8);
+            assert!(password.bytes().any(|c| c.is_ascii_lowercase()));
+            assert!(password.bytes().any(|c| c.is_ascii_digit()));
+            assert!(password.bytes().any(|c| CharClass::Symbols.chars().contains(&c)));
+            assert!(!password.bytes().any(|c| c.is_ascii_uppercase()));
+        }
+
+        let result = &search_results(parse("pw 24 symbols").as_ref().unwrap(), Utc::now())[0];
+        assert_eq!(result.result_type, "password");
+        assert_eq!(
+            result.description,
+            "24-character password · lowercase, uppercase, digits, symbols · Enter to copy"
+        );
+        assert_eq!(result.path.to_string_lossy().len(), 24);
+    }
+
+    #[test]
+    fn test_lorem() {
+        let words = lorem(20, LoremUnit::Words).unwrap();
+        assert_eq!(words.split_whitespace().count(), 20);
+        assert!(words.starts_with("Lorem ipsum dolor sit amet"));
+        assert_eq!(lorem(3, LoremUnit::Words).unwrap(), "Lorem ipsum dolor");
+
+        let paragraphs = lorem(3, LoremUnit::Paragraphs).unwrap();
+        assert_eq!(paragraphs.split("\n\n").count(), 3);
+        assert!(paragraphs.starts_with(LOREM_OPENING));
+
+        let sentences = lorem(4, LoremUnit::Sentences).unwrap();
+        assert_eq!(sentences.matches('.').count(), 4);
+    }
+}
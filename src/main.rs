@@ -21,22 +21,59 @@ use windows::Win32::Graphics::Gdi::{
 mod ui;
 
 // Re-export generated UI types
-pub use ui::{Launcher, SearchResult};
+pub use ui::{ActionItem, Launcher, SearchResult};
 mod actions;
 mod app_discovery;
+mod bangs;
+mod bookmarks;
+mod calc_session;
+mod characters;
+mod clipboard;
+mod clipboard_history;
+mod colors;
+mod commands;
 mod config;
+mod currency;
+mod datetime;
+mod exact;
+mod generators;
+mod notes;
+mod notifier;
 mod platform_window;
+mod processes;
+mod programmer;
+mod recent;
+mod result_actions;
 mod search;
 mod single_instance;
+mod snippets;
+mod system_commands;
 mod startup;
+mod timers;
+mod toolbox;
 mod tray;
 mod wizard;
 mod settings_ui;
+mod shell;
+mod ssh;
 mod updater;
+mod units;
+mod workspaces;
 
 use updater::UpdateInfo;
 
+use calc_session::CalcSession;
+use clipboard::ClipboardService;
+use clipboard_history::ClipboardHistory;
+use notes::{NotesFile, NotesQuery};
+use notifier::SystemNotifier;
+use processes::ProcessMonitor;
+use shell::ShellRunner;
+use snippets::SnippetStore;
+use system_commands::{CommandScheduler, ScheduledCommand, SystemCommand};
+use timers::TimerService;
 use config::AppConfig;
+use result_actions::ResultAction;
 use single_instance::{SingleInstance, PortableMode, detect_portable_mode};
 use tray::{TrayEvent, TrayManager, check_tray_event};
 
@@ -133,6 +170,28 @@ fn create_hotkey_from_config(config: &AppConfig) -> Result<HotKey, String> {
 struct LauncherState {
     apps: Vec<AppEntry>,
     config: AppConfig,
+    calc_session: CalcSession,
+    clipboard: ClipboardService,
+    /// Recorded clipboard texts; `None` while clipboard history is disabled
+    clipboard_history: Option<ClipboardHistory>,
+    /// Shutdown, restart or sign-out counting down after confirmation
+    scheduler: CommandScheduler,
+    /// Captured `>` command currently running
+    shell_runner: ShellRunner,
+    /// Markdown file for `note` and `todo`
+    notes: Option<NotesFile>,
+    /// Pending `timer` and `remind` notifications
+    timers: TimerService,
+    /// Running processes for `ps` and `kill`
+    processes: ProcessMonitor,
+    /// Browser bookmarks, searched together with the apps
+    bookmarks: Vec<AppEntry>,
+    /// Recently opened documents, newest first
+    recent_documents: Vec<AppEntry>,
+    /// Recent VS Code and JetBrains projects, searched together with the apps
+    workspaces: Vec<AppEntry>,
+    /// Text snippets from snippets.json
+    snippets: SnippetStore,
 }
 
 /// Represents a discovered application
@@ -149,6 +208,9 @@ pub enum AppType {
     DesktopApp,
     UwpApp,
     File,
+    Bookmark,
+    /// Recent project of a code editor, reopened in that editor
+    Workspace(workspaces::Editor),
 }
 
 impl std::fmt::Display for AppType {
@@ -157,30 +219,68 @@ impl std::fmt::Display for AppType {
             AppType::DesktopApp => write!(f, "Desktop"),
             AppType::UwpApp => write!(f, "UWP"),
             AppType::File => write!(f, "File"),
+            AppType::Bookmark => write!(f, "Bookmark"),
+            AppType::Workspace(_) => write!(f, "Workspace"),
         }
     }
 }
 
 impl LauncherState {
     fn new(config: AppConfig) -> Self {
+        let decimal_comma = config
+            .calculator
+            .decimal_comma
+            .unwrap_or_else(exact::system_uses_decimal_comma);
+        let clipboard_history = config.clipboard.history_enabled.then(|| {
+            let max_entries = config.clipboard.history_max_entries;
+            let exclude_sensitive = config.clipboard.history_exclude_sensitive;
+            match clipboard_history::history_path() {
+                Some(path) => ClipboardHistory::load(max_entries, exclude_sensitive, path),
+                None => ClipboardHistory::new(max_entries, exclude_sensitive, None),
+            }
+        });
+        let notes = NotesFile::from_config(&config.notes);
+        let timers = timers::timers_path().map_or_else(TimerService::default, TimerService::load);
         Self {
             apps: Vec::new(),
             config,
+            calc_session: CalcSession::with_decimal_comma(decimal_comma),
+            clipboard: ClipboardService::system(),
+            clipboard_history,
+            scheduler: CommandScheduler::new(),
+            shell_runner: ShellRunner::new(),
+            notes,
+            timers,
+            processes: ProcessMonitor::system(),
+            bookmarks: Vec::new(),
+            recent_documents: Vec::new(),
+            workspaces: Vec::new(),
+            snippets: SnippetStore::default(),
         }
     }
 
-    /// Two-tier search: prefix matching (high priority) + fuzzy matching (fallback)
+    /// Two-tier search: prefix matching (high priority) + fuzzy matching (fallback).
+    /// Works before app discovery finishes, with commands, bookmarks and workspaces.
     fn search(&self, query: &str) -> Vec<SearchResultData> {
+        if let Some(results) = self.provider_results(query) {
+            return results;
+        }
+
         let mut results = Vec::new();
         let query_lower = query.to_lowercase();
 
-        // Check for special prefixes first
-        if let Some(action_result) = actions::check_special_query(query) {
-            return vec![action_result];
+        // Check for currency conversion
+        if let Some(currency_result) = actions::try_currency(query, &self.config.calculator) {
+            results.push(currency_result);
+        }
+
+        // Check for date/time query
+        if let Some(datetime_result) = actions::try_datetime(query) {
+            results.push(datetime_result);
         }
 
         // Check for calculator expression
-        if let Some(calc_result) = actions::try_calculate(query) {
+        if let Some(calc_result) = actions::try_calculate_with(query, &self.calc_session) {
             results.push(calc_result);
         }
 
@@ -190,60 +290,52 @@ impl LauncherState {
         }
 
         // === TIER 1: Prefix Matching (Highest Priority) ===
-        let mut prefix_matches: Vec<(&AppEntry, i64)> = Vec::new();
-        let mut fuzzy_only_matches: Vec<(&AppEntry, i64)> = Vec::new();
-
-        for app in &self.apps {
-            let name_lower = app.name.to_lowercase();
-            let mru_bonus = (self.config.get_mru_score(&app.name) as i64) * 10;
-
-            // Check if name starts with query
-            if name_lower.starts_with(&query_lower) {
-                // Exact prefix match - highest score
-                let score = 1000 + mru_bonus + (100 - name_lower.len() as i64);
-                prefix_matches.push((app, score));
-                continue;
-            }
-
-            // Check if any word starts with query
-            let words: Vec<&str> = name_lower.split_whitespace().collect();
-            let mut word_match = false;
-            for word in &words {
-                if word.starts_with(&query_lower) {
-                    let score = 800 + mru_bonus;
-                    prefix_matches.push((app, score));
-                    word_match = true;
-                    break;
-                }
-            }
+        let mut prefix_matches: Vec<(SearchResultData, i64)> = Vec::new();
+        let mut fuzzy_only_matches: Vec<(SearchResultData, i64)> = Vec::new();
 
-            if word_match {
+        for app in self.apps.iter().chain(&self.bookmarks).chain(&self.workspaces) {
+            let Some((prefix, score)) = match_score(&app.name.to_lowercase(), &query_lower) else {
                 continue;
-            }
+            };
+            let mut mru_bonus = (self.config.get_mru_score(&app.name) as i64) * 10;
 
-            // Check initials match (e.g., "vsc" matches "Visual Studio Code")
-            if query.len() >= 2 {
-                let initials: String = words
-                    .iter()
-                    .filter_map(|w| w.chars().next())
-                    .collect();
-                if initials.starts_with(&query_lower) {
-                    let score = 700 + mru_bonus;
-                    prefix_matches.push((app, score));
-                    continue;
-                }
+            // Pinned apps rank above every other match
+            if self.config.is_pinned(&app.name) {
+                mru_bonus += 10_000;
             }
 
-            // === TIER 2: Fuzzy Matching (Fallback) ===
-            // Check if query is a subsequence of name
-            if is_subsequence(&query_lower, &name_lower) {
-                let score = 300 + mru_bonus;
-                fuzzy_only_matches.push((app, score));
-            } else if name_lower.contains(&query_lower) {
-                // Substring match
-                let score = 200 + mru_bonus;
-                fuzzy_only_matches.push((app, score));
-            }
+            let result = SearchResultData {
+                name: app.name.clone(),
+                description: app.description.clone(),
+                path: app.path.clone(),
+                result_type: match app.app_type {
+                    AppType::DesktopApp | AppType::UwpApp => "app".to_string(),
+                    AppType::File => "file".to_string(),
+                    AppType::Bookmark => "bookmark".to_string(),
+                    AppType::Workspace(_) => "workspace".to_string(),
+                },
+                editor: match &app.app_type {
+                    AppType::Workspace(editor) => Some(editor.clone()),
+                    _ => None,
+                },
+                ..Default::default()
+            };
+            let matches = if prefix { &mut prefix_matches } else { &mut fuzzy_only_matches };
+            matches.push((result, score + mru_bonus));
+        }
+
+        // Built-in commands match on their name or any keyword ("lock pc", "reboot")
+        for command in commands::COMMANDS {
+            let Some((prefix, score)) = command
+                .search_terms()
+                .filter_map(|term| match_score(&term, &query_lower))
+                .max()
+            else {
+                continue;
+            };
+            let mru_bonus = (self.config.get_mru_score(command.name) as i64) * 10;
+            let matches = if prefix { &mut prefix_matches } else { &mut fuzzy_only_matches };
+            matches.push((command.result(), score + mru_bonus));
         }
 
         // Sort prefix matches by score
@@ -256,45 +348,270 @@ impl LauncherState {
         let max_results = self.config.appearance.max_results;
         let mut app_count = 0;
 
-        for (app, _score) in prefix_matches.into_iter() {
+        for (result, _score) in prefix_matches.into_iter() {
             if app_count >= max_results {
                 break;
             }
-            results.push(SearchResultData {
-                name: app.name.clone(),
-                description: app.description.clone(),
-                path: app.path.clone(),
-                result_type: match app.app_type {
-                    AppType::DesktopApp | AppType::UwpApp => "app".to_string(),
-                    AppType::File => "file".to_string(),
-                },
-            });
+            results.push(result);
             app_count += 1;
         }
 
         // Add fuzzy matches if we have room
-        for (app, _score) in fuzzy_only_matches.into_iter() {
+        for (result, _score) in fuzzy_only_matches.into_iter() {
             if app_count >= max_results {
                 break;
             }
-            results.push(SearchResultData {
-                name: app.name.clone(),
-                description: app.description.clone(),
-                path: app.path.clone(),
-                result_type: match app.app_type {
-                    AppType::DesktopApp | AppType::UwpApp => "app".to_string(),
-                    AppType::File => "file".to_string(),
-                },
-            });
+            results.push(result);
             app_count += 1;
         }
 
         results
     }
 
+    /// Results of the keyword and prefix providers ("cb", ">", ";", "note", "ps", pasted
+    /// colors...), which replace the app search when the query is theirs
+    fn provider_results(&self, query: &str) -> Option<Vec<SearchResultData>> {
+        // Cancel a scheduled shutdown, restart or sign-out
+        if system_commands::is_cancel_query(query) {
+            return Some(vec![self.scheduler.cancel_result()]);
+        }
+
+        // Check for special prefixes first
+        if let Some(action_result) = actions::check_special_query(query) {
+            return Some(vec![action_result]);
+        }
+
+        // Calculator history on a bare "=" prefix
+        if query.trim() == "=" {
+            return Some(actions::calc_history(&self.calc_session));
+        }
+
+        // Clipboard history on the "cb" keyword
+        if let Some(terms) = clipboard_history::parse_query(query) {
+            let now = chrono::Utc::now().timestamp();
+            return Some(clipboard_history::search_results(self.clipboard_history.as_ref(), terms, now));
+        }
+
+        // Shell commands on the ">" prefix
+        if let Some(command) = shell::parse_query(query) {
+            return Some(shell::preview_results(command, &self.config.shell));
+        }
+
+        // Snippets on the ";" prefix
+        if let Some(terms) = snippets::parse_query(query) {
+            return Some(snippets::search_results(&self.snippets, terms));
+        }
+
+        // Quick notes and todos on the "note" and "todo" keywords
+        if let Some(notes_query) = notes::parse_query(query) {
+            return Some(notes::search_results(self.notes.as_ref(), notes_query));
+        }
+
+        // Timers and reminders on the "timer" and "remind" keywords
+        if let Some(timer_query) = timers::parse_query(query) {
+            return Some(timers::search_results(&self.timers, timer_query, &chrono::Local::now()));
+        }
+
+        // Running processes on the "ps" and "kill" keywords
+        if let Some(terms) = processes::parse_query(query) {
+            return Some(self.process_results(terms));
+        }
+
+        // Recently opened documents on the "recent" keyword
+        if let Some(terms) = recent::parse_query(query) {
+            return Some(self.recent_results(terms));
+        }
+
+        // SSH hosts on the "ssh" keyword
+        if let Some(terms) = ssh::parse_query(query) {
+            return Some(self.ssh_results(terms));
+        }
+
+        // Emoji on the ":" prefix, Unicode characters on the "u" keyword
+        if let Some(character_query) = characters::parse_query(query) {
+            return Some(self.character_results(character_query));
+        }
+
+        // Pasted color codes in every notation
+        if let Some(color) = colors::parse(query) {
+            return Some(colors::search_results(color));
+        }
+
+        // Encode, decode and hash commands such as "b64 hello" and "sha256 <path>"
+        if let Some((tool, input)) = toolbox::parse_query(query) {
+            return Some(toolbox::search_results(tool, input, chrono::Utc::now()));
+        }
+
+        // Fresh UUIDs, passwords and lorem ipsum
+        if let Some(generator) = generators::parse_query(query, &self.config.generators) {
+            return Some(generators::search_results(&generator, chrono::Utc::now()));
+        }
+
+        None
+    }
+
+    /// Recent documents matching `recent` terms
+    fn recent_results(&self, terms: &str) -> Vec<SearchResultData> {
+        let results = recent::search_results(&self.recent_documents, terms, self.config.appearance.max_results);
+        if results.is_empty() {
+            return vec![SearchResultData {
+                name: "No recent documents".to_string(),
+                description: if self.config.search.recent_documents {
+                    "Documents you open show up here".to_string()
+                } else {
+                    "Turn on search.recent_documents in the settings file".to_string()
+                },
+                result_type: "info".to_string(),
+                ..Default::default()
+            }];
+        }
+        results
+    }
+
+    /// Hosts from the SSH config and known_hosts, read fresh since both files are small
+    fn ssh_results(&self, terms: &str) -> Vec<SearchResultData> {
+        let hosts = ssh::ssh_dir().map(|dir| ssh::load_hosts(&dir)).unwrap_or_default();
+        ssh::search_results(&hosts, terms, self.config.appearance.max_results)
+    }
+
+    /// Emoji or Unicode characters matching a picker query, in the configured skin tone
+    fn character_results(&self, query: characters::CharacterQuery) -> Vec<SearchResultData> {
+        let skin_tone = self
+            .config
+            .characters
+            .skin_tone
+            .as_deref()
+            .and_then(characters::skin_tone_from_name);
+        characters::search_results(query, &self.config.recent_characters, skin_tone)
+    }
+
+    /// Running processes matching `ps` or `kill` terms, as of the last background sample
+    fn process_results(&self, terms: &str) -> Vec<SearchResultData> {
+        match self.processes.processes() {
+            Some(Ok(processes)) => processes::search_results(processes, terms),
+            Some(Err(e)) => vec![SearchResultData {
+                name: "Failed to list processes".to_string(),
+                description: e,
+                result_type: "info".to_string(),
+                ..Default::default()
+            }],
+            None => vec![SearchResultData {
+                name: "Listing processes…".to_string(),
+                description: "Results appear in a moment".to_string(),
+                result_type: "info".to_string(),
+                ..Default::default()
+            }],
+        }
+    }
+
     fn record_usage(&mut self, name: &str) {
         self.config.record_usage(name);
     }
+
+    /// Run a secondary action picked from a result's actions menu
+    fn run_result_action(&mut self, action: ResultAction, result: &SearchResultData) {
+        let outcome = match action {
+            ResultAction::CopyPath | ResultAction::CopyUrl => self.clipboard.copy(&result.path.to_string_lossy()),
+            ResultAction::CopyOutput => self.clipboard.copy(result.output.as_deref().unwrap_or_default()),
+            ResultAction::Pin | ResultAction::Unpin => {
+                self.config.set_pinned(&result.name, action == ResultAction::Pin);
+                Ok(())
+            }
+            ResultAction::StopCommand => {
+                self.shell_runner.kill();
+                Ok(())
+            }
+            ResultAction::MarkDone | ResultAction::DeleteTodo => match &self.notes {
+                Some(notes) if action == ResultAction::MarkDone => notes.complete_todo(&result.name),
+                Some(notes) => notes.delete_todo(&result.name),
+                None => Err("No notes file".into()),
+            },
+            ResultAction::CancelTimer => match result.path.to_string_lossy().parse() {
+                Ok(id) if self.timers.cancel(id).is_some() => Ok(()),
+                _ => Err("Timer already finished".into()),
+            },
+            _ => result_actions::run_shell_action(action, &result.path),
+        };
+
+        match outcome {
+            Ok(()) => log::info!("{}: {}", action.label(), result.name),
+            Err(e) => log::error!("Failed to {} for {}: {}", action.label().to_lowercase(), result.name, e),
+        }
+    }
+}
+
+/// Score a lowercased name against the query: `(true, score)` for prefix, word and
+/// initials matches, `(false, score)` for fuzzy matches, `None` when it doesn't match
+fn match_score(name_lower: &str, query_lower: &str) -> Option<(bool, i64)> {
+    // Check if name starts with query
+    if name_lower.starts_with(query_lower) {
+        // Exact prefix match - highest score
+        return Some((true, 1000 + (100 - name_lower.len() as i64)));
+    }
+
+    // Check if any word starts with query
+    let words: Vec<&str> = name_lower.split_whitespace().collect();
+    if words.iter().any(|word| word.starts_with(query_lower)) {
+        return Some((true, 800));
+    }
+
+    // Check initials match (e.g., "vsc" matches "Visual Studio Code")
+    if query_lower.len() >= 2 {
+        let initials: String = words
+            .iter()
+            .filter_map(|w| w.chars().next())
+            .collect();
+        if initials.starts_with(query_lower) {
+            return Some((true, 700));
+        }
+    }
+
+    // === TIER 2: Fuzzy Matching (Fallback) ===
+    // Check if query is a subsequence of name
+    if is_subsequence(query_lower, name_lower) {
+        Some((false, 300))
+    } else if name_lower.contains(query_lower) {
+        // Substring match
+        Some((false, 200))
+    } else {
+        None
+    }
+}
+
+/// Replace the results shown in the launcher without a new search
+fn show_results(launcher_weak: &slint::Weak<Launcher>, results: &[SearchResultData]) {
+    let slint_results: Vec<SearchResult> = results.iter().map(|r| r.into()).collect();
+    let _ = launcher_weak.upgrade_in_event_loop(move |launcher: Launcher| {
+        let model: slint::ModelRc<SearchResult> = std::rc::Rc::new(VecModel::from(slint_results)).into();
+        launcher.set_results(model);
+        launcher.set_selected_index(0);
+    });
+}
+
+/// Poll `refresh` every `interval_secs` on a background thread while the app runs, and
+/// store whatever it returns (only when its source changed) with `apply`
+fn spawn_index_watcher<T: Send + 'static>(
+    name: &'static str,
+    interval_secs: u64,
+    state: &Arc<Mutex<LauncherState>>,
+    app_running: &Arc<AtomicBool>,
+    mut refresh: impl FnMut() -> Option<T> + Send + 'static,
+    apply: impl Fn(&mut LauncherState, T) + Send + 'static,
+) {
+    let state = Arc::clone(state);
+    let app_running = Arc::clone(app_running);
+    std::thread::spawn(move || {
+        log::info!("{} watcher started", name);
+        while app_running.load(Ordering::Relaxed) {
+            if let Some(value) = refresh() {
+                if let Ok(mut state) = state.lock() {
+                    apply(&mut state, value);
+                }
+            }
+            std::thread::sleep(std::time::Duration::from_secs(interval_secs));
+        }
+        log::info!("{} watcher shutting down", name);
+    });
 }
 
 /// Check if pattern is a subsequence of text
@@ -315,12 +632,48 @@ fn is_subsequence(pattern: &str, text: &str) -> bool {
 }
 
 /// Search result data for passing between Rust and Slint
-#[derive(Clone)]
+#[derive(Clone, Default)]
 pub struct SearchResultData {
     pub name: String,
     pub description: String,
     pub path: PathBuf,
     pub result_type: String,
+    /// Calculator value, so activating the result can store it as `ans` or a variable
+    pub calc: Option<calc_session::CalcEntry>,
+    /// Registry ID of a built-in command (`commands::COMMANDS`)
+    pub command_id: Option<&'static str>,
+    /// Destructive system command and its countdown, for confirmation and delayed runs
+    pub scheduled: Option<system_commands::ScheduledCommand>,
+    /// Shell command and how to run it, for `>` results
+    pub shell: Option<shell::ShellRun>,
+    /// Captured command output, for `shell-output` results
+    pub output: Option<String>,
+    /// Timer or reminder to start, for `timer` and `remind` results
+    pub timer: Option<timers::TimerRequest>,
+    /// Process to end, for `ps` and `kill` results
+    pub process: Option<processes::ProcessTarget>,
+    /// Color shown in the icon square, for pasted color codes
+    pub swatch: Option<colors::Rgb>,
+    /// Editor that reopens a recent project
+    pub editor: Option<workspaces::Editor>,
+    /// File to hash, for `toolbox-hash` results
+    pub file_hash: Option<toolbox::FileHash>,
+    /// Host to connect to, for `ssh` results
+    pub ssh: Option<ssh::SshHost>,
+}
+
+impl SearchResultData {
+    /// Generated passwords: copied as secrets and never written to the log
+    pub fn is_secret(&self) -> bool {
+        self.result_type == "password"
+    }
+
+    /// Apps, files, bookmarks, workspaces and built-in commands, which are ranked by use.
+    /// Other results carry text the user typed or copied (clipboard entries, shell
+    /// commands, notes, toolbox output) that must stay out of the settings file and log.
+    pub fn is_catalog_entry(&self) -> bool {
+        matches!(self.result_type.as_str(), "app" | "file" | "bookmark" | "workspace") || self.command_id.is_some()
+    }
 }
 
 impl From<&SearchResultData> for SearchResult {
@@ -330,6 +683,11 @@ impl From<&SearchResultData> for SearchResult {
             description: SharedString::from(&data.description),
             icon_path: SharedString::new(),
             result_type: SharedString::from(&data.result_type),
+            has_swatch: data.swatch.is_some(),
+            swatch: data
+                .swatch
+                .map(|rgb| slint::Color::from_rgb_u8(rgb.r, rgb.g, rgb.b))
+                .unwrap_or_default(),
         }
     }
 }
@@ -555,6 +913,73 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         });
     }
 
+    // Index Unicode character names in background for the "u" picker
+    characters::preload_unicode_names();
+
+    // Read browser bookmarks in background, and again whenever a bookmarks file changes
+    if config.bookmarks.enabled {
+        let mut index = bookmarks::BookmarkIndex::new(bookmarks::sources(&config.bookmarks));
+        spawn_index_watcher(
+            "Bookmark",
+            10,
+            &state,
+            &app_running,
+            move || index.refresh_if_changed(),
+            |state, entries| {
+                log::info!("Loaded {} bookmarks", entries.len());
+                state.bookmarks = entries;
+            },
+        );
+    }
+
+    // Read recent editor projects in background, and again whenever a recent list changes
+    if config.workspaces.enabled {
+        let mut index = workspaces::WorkspaceIndex::new(workspaces::sources(&config.workspaces));
+        spawn_index_watcher(
+            "Workspace",
+            10,
+            &state,
+            &app_running,
+            move || index.refresh_if_changed(),
+            |state, entries| {
+                log::info!("Loaded {} recent workspaces", entries.len());
+                state.workspaces = entries;
+            },
+        );
+    }
+
+    // Read recent documents in background, and again whenever the Recent folder changes
+    if let (true, Some(source)) = (config.search.recent_documents, recent::source_path()) {
+        let mut documents = recent::RecentDocuments::new(source);
+        spawn_index_watcher(
+            "Recent documents",
+            5,
+            &state,
+            &app_running,
+            move || documents.refresh_if_changed(),
+            |state, entries| {
+                log::debug!("Loaded {} recent documents", entries.len());
+                state.recent_documents = entries;
+            },
+        );
+    }
+
+    // Read snippets in background, and again whenever the snippets file changes
+    if let Some(path) = snippets::snippets_path() {
+        let mut file = snippets::SnippetFile::new(path);
+        spawn_index_watcher(
+            "Snippet",
+            2,
+            &state,
+            &app_running,
+            move || file.refresh_if_changed(),
+            |state, store| {
+                log::debug!("Reloaded snippets");
+                state.snippets = store;
+            },
+        );
+    }
+
     // Set up global hotkey from config
     let hotkey_manager = GlobalHotKeyManager::new()?;
     let hotkey = create_hotkey_from_config(&config)
@@ -597,6 +1022,9 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                             // Update last shown time to prevent immediate hiding due to focus race
                             *last_shown_time_clone.lock().unwrap() = std::time::Instant::now();
 
+                            // Remember the focused window so results can be pasted back into it
+                            platform_window::remember_foreground_window();
+
                             // Position window correctly (not off-screen)
                             launcher.window().set_position(position);
 
@@ -610,9 +1038,10 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                                 log::warn!("Failed to configure window styles: {}", e);
                             }
 
-                            // Clear search and prepare UI first
+                            // Clear search and prepare UI first, listing recent documents
                             launcher.set_search_text("".into());
                             launcher.invoke_clear_search();
+                            launcher.invoke_search_changed("".into());
                             launcher.set_selected_index(0);
 
                             // Enable focus for the launcher window so it can receive keyboard input
@@ -681,9 +1110,10 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                             log::debug!("Focus enabled successfully (tray)");
                         }
 
-                        // Clear search state
+                        // Clear search state, listing recent documents
                         launcher.set_search_text("".into());
                         launcher.invoke_clear_search();
+                        launcher.invoke_search_changed("".into());
                         launcher.set_selected_index(0);
 
                         // Small delay to ensure Windows focus APIs have taken effect
@@ -766,19 +1196,16 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             log::debug!("Search changed: '{}' (len: {})", query_str, query_str.len());
 
             if query_str.is_empty() {
-                log::debug!("Query is empty, clearing results");
-                // Clear results immediately
+                log::debug!("Query is empty, showing recent documents");
+                let recent = match state.lock() {
+                    Ok(state) => recent::search_results(&state.recent_documents, "", state.config.appearance.max_results),
+                    Err(_) => Vec::new(),
+                };
                 if let Ok(mut results) = current_results.lock() {
-                    results.clear();
+                    *results = recent.clone();
                 }
                 // Update UI immediately
-                let _ = launcher_weak_search.upgrade_in_event_loop(|launcher: Launcher| {
-                    // Always create a fresh model to ensure UI updates properly
-                    let model: slint::ModelRc<SearchResult> = std::rc::Rc::new(VecModel::<SearchResult>::default()).into();
-                    launcher.set_results(model);
-                    launcher.set_selected_index(0);
-                    log::debug!("UI cleared - no results shown");
-                });
+                show_results(&launcher_weak_search, &recent);
                 return;
             }
 
@@ -786,56 +1213,54 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             let search_results = if let Ok(state) = state.lock() {
                 log::debug!("Searching among {} discovered apps", state.apps.len());
 
-                // If no apps are discovered yet, add a placeholder result
-                if state.apps.is_empty() && !query_str.is_empty() {
-                    log::debug!("No apps discovered yet, showing calculator/web search only");
+                // Sample processes off the UI thread, and search again once the sample is in
+                if processes::parse_query(&query_str).is_some() {
+                    let launcher_weak = launcher_weak_search.clone();
+                    state.processes.refresh(move || {
+                        let _ = launcher_weak.upgrade_in_event_loop(|launcher: Launcher| {
+                            let query = launcher.get_search_text();
+                            if processes::parse_query(&query).is_some() {
+                                launcher.invoke_search_changed(query);
+                            }
+                        });
+                    });
+                }
+
+                let mut results = state.search(&query_str);
+                log::debug!("Search for '{}' returned {} results", query_str, results.len());
 
-                    let mut results = Vec::new();
+                // Debug: Log first few results
+                for (i, result) in results.iter().take(3).enumerate().filter(|(_, r)| r.is_catalog_entry()) {
+                    log::debug!("  Result {}: {} ({})", i+1, result.name, result.result_type);
+                }
+                if results.len() > 3 {
+                    log::debug!("  ... and {} more results", results.len() - 3);
+                }
 
-                    // Check for calculator expression
-                    if let Some(calc_result) = actions::try_calculate(&query_str) {
+                // Placeholder results while apps are still being discovered
+                if state.apps.is_empty() && results.is_empty() {
+                    log::debug!("No apps discovered yet, showing placeholder results");
+
+                    // Always add calculator for testing
+                    if let Some(calc_result) = actions::try_calculate("2+2") {
                         results.push(calc_result);
                     }
 
-                    // Check for web search
-                    if let Some(web_result) = actions::check_web_search(&query_str) {
+                    // Add web search for testing
+                    if let Some(web_result) = actions::check_web_search("test") {
                         results.push(web_result);
                     }
 
-                    // Add fallback results for testing if no apps are found
-                    if results.is_empty() {
-                        // Always add calculator for testing
-                        if let Some(calc_result) = actions::try_calculate("2+2") {
-                            results.push(calc_result);
-                        }
-
-                        // Add web search for testing
-                        if let Some(web_result) = actions::check_web_search("test") {
-                            results.push(web_result);
-                        }
-
-                        // Add a status message
-                        results.push(SearchResultData {
-                            name: "Type to search applications...".to_string(),
-                            description: "Calculator and web search are always available".to_string(),
-                            path: std::path::PathBuf::new(),
-                            result_type: "info".to_string(),
-                        });
-                    }
-                    results
-                } else {
-                    let results = state.search(&query_str);
-                    log::debug!("Search for '{}' returned {} results", query_str, results.len());
-
-                    // Debug: Log first few results
-                    for (i, result) in results.iter().take(3).enumerate() {
-                        log::debug!("  Result {}: {} ({})", i+1, result.name, result.result_type);
-                    }
-                    if results.len() > 3 {
-                        log::debug!("  ... and {} more results", results.len() - 3);
-                    }
-                    results
+                    // Add a status message
+                    results.push(SearchResultData {
+                        name: "Type to search applications...".to_string(),
+                        description: "Calculator and web search are always available".to_string(),
+                        path: std::path::PathBuf::new(),
+                        result_type: "info".to_string(),
+                        ..Default::default()
+                    });
                 }
+                results
             } else {
                 log::error!("Failed to lock state for search!");
                 Vec::new()
@@ -869,22 +1294,65 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         let current_results = Arc::clone(&current_results);
         let launcher_weak = launcher_weak.clone();
         
-        launcher.on_result_activated(move |index| {
+        launcher.on_result_activated(move |index, action_id| {
             let index = index as usize;
-            log::info!("Result activated at index: {}", index);
+            let secondary = ResultAction::from_id(&action_id);
+            log::info!("Result activated at index: {} (action: {:?})", index, secondary);
             
-            if let Ok(results) = current_results.lock() {
+            if let Ok(mut results) = current_results.lock() {
                 if let Some(result) = results.get(index) {
-                    log::info!("Launching: {} (type: {})", result.name, result.result_type);
-                    log::info!("Path: {:?}", result.path);
+                    if result.is_catalog_entry() {
+                        log::info!("Launching: {} (type: {})", result.name, result.result_type);
+                        log::info!("Path: {:?}", result.path);
+                    } else {
+                        log::info!("Launching a {} result", result.result_type);
+                    }
                     
-                    // Record usage for MRU
-                    if let Ok(mut state) = state.lock() {
-                        state.record_usage(&result.name);
+                    // Record usage for MRU (secondary actions like copying or pinning don't count)
+                    if secondary.is_none() && result.is_catalog_entry() {
+                        if let Ok(mut state) = state.lock() {
+                            state.record_usage(&result.name);
+                        }
                     }
 
                     // Execute the action with validation
+                    let mut paste_after_hide = false;
+                    let mut caret_back = 0;
+                    // Results to keep the launcher open with (confirmations, running commands)
+                    let mut stay_open: Option<Vec<SearchResultData>> = None;
                     match result.result_type.as_str() {
+                        // Ending a process from the actions menu asks for confirmation first
+                        _ if matches!(secondary, Some(ResultAction::EndTask | ResultAction::EndProcessTree)) => {
+                            if let Some(target) = &result.process {
+                                let target = processes::ProcessTarget {
+                                    tree: secondary == Some(ResultAction::EndProcessTree),
+                                    ..target.clone()
+                                };
+                                stay_open = Some(vec![processes::confirmation_result(target)]);
+                            }
+                        }
+                        // Secondary action picked from the actions menu
+                        _ if secondary.is_some() => {
+                            if let (Some(action), Ok(mut state)) = (secondary, state.lock()) {
+                                state.run_result_action(action, result);
+                            }
+                            // Stopping a command keeps its output in view, and the todo
+                            // list stays open after marking an item done or deleting it
+                            match secondary {
+                                Some(ResultAction::StopCommand) => stay_open = Some(vec![result.clone()]),
+                                Some(ResultAction::MarkDone | ResultAction::DeleteTodo) => {
+                                    if let Ok(state) = state.lock() {
+                                        stay_open = Some(notes::search_results(state.notes.as_ref(), NotesQuery::Todo("")));
+                                    }
+                                }
+                                Some(ResultAction::CancelTimer) => {
+                                    if let Ok(state) = state.lock() {
+                                        stay_open = Some(timers::active_results(&state.timers, &chrono::Local::now()));
+                                    }
+                                }
+                                _ => {}
+                            }
+                        }
                         "app" | "file" => {
                             // Validate path exists before launching
                             if result.path.exists() {
@@ -897,31 +1365,278 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                             }
                         }
                         "calc" => {
-                            // TODO: Copy to clipboard
+                            // Store as ans/variable, add to the calculator history and copy the value
+                            if let Ok(mut state) = state.lock() {
+                                if let Some(entry) = &result.calc {
+                                    state.calc_session.commit(entry);
+                                }
+                                match state.clipboard.copy_result(result) {
+                                    Ok(_) => paste_after_hide = state.config.clipboard.paste_after_copy,
+                                    Err(e) => log::error!("Failed to copy calculator result: {}", e),
+                                }
+                            }
                             log::info!("Calculator result: {}", result.description);
                         }
-                        "web" => {
+                        "clipboard" => {
+                            // Copy the entry again, optionally pasting it into the previous window
+                            if let Ok(mut state) = state.lock() {
+                                match state.clipboard.copy_result(result) {
+                                    Ok(_) => paste_after_hide = state.config.clipboard.paste_after_copy,
+                                    Err(e) => log::error!("Failed to copy clipboard entry: {}", e),
+                                }
+                            }
+                        }
+                        "clipboard-clear" => {
+                            if let Ok(mut state) = state.lock() {
+                                if let Some(history) = state.clipboard_history.as_mut() {
+                                    if let Err(e) = history.clear() {
+                                        log::error!("Failed to clear clipboard history: {}", e);
+                                    }
+                                }
+                            }
+                        }
+                        "web" | "bookmark" => {
                             match open::that(&result.path) {
                                 Ok(_) => log::info!("Opened URL: {:?}", result.path),
                                 Err(e) => log::error!("Failed to open URL: {}", e),
                             }
                         }
                         "action" => {
-                            log::info!("Executing system action: {}", result.name);
-                            actions::execute_system_action(&result.name);
+                            // Destructive commands ask for confirmation before counting down
+                            let scheduled = result.scheduled.or_else(|| {
+                                let command = SystemCommand::from_command_id(result.command_id?)?;
+                                Some(ScheduledCommand {
+                                    command,
+                                    delay: system_commands::DEFAULT_COUNTDOWN,
+                                })
+                            });
+                            match (scheduled, result.command_id) {
+                                (Some(scheduled), _) => {
+                                    stay_open = Some(vec![system_commands::confirmation_result(scheduled)])
+                                }
+                                (None, Some(id)) => commands::execute(id),
+                                (None, None) => log::warn!("Action result without a command: {}", result.name),
+                            }
+                        }
+                        "confirm" => {
+                            if let (Some(scheduled), Ok(mut state)) = (result.scheduled, state.lock()) {
+                                state.scheduler.schedule(scheduled);
+                            }
+                        }
+                        "cancel" => {
+                            if let Ok(mut state) = state.lock() {
+                                state.scheduler.cancel();
+                            }
+                        }
+                        "shell" => {
+                            if let (Some(run), Ok(mut state)) = (&result.shell, state.lock()) {
+                                let state = &mut *state;
+                                if state.config.shell.confirm && !run.confirmed {
+                                    stay_open = Some(vec![shell::confirmation_result(run)]);
+                                } else if run.in_terminal {
+                                    if let Err(e) = shell::open_in_terminal(&state.config.shell, &run.command) {
+                                        log::error!("Failed to open terminal: {}", e);
+                                    }
+                                } else {
+                                    match state.shell_runner.start(&state.config.shell, &run.command) {
+                                        Ok(running) => {
+                                            stay_open = Some(vec![shell::running_result(&run.command)]);
+                                            let max_lines = state.config.shell.output_lines;
+                                            let current_results = Arc::clone(&current_results);
+                                            let launcher_weak = launcher_weak.clone();
+                                            std::thread::spawn(move || {
+                                                let command = running.command().to_string();
+                                                let output = running.wait();
+                                                let output_results = shell::output_results(&command, &output, max_lines);
+
+                                                // Only replace the results if they still show this command
+                                                if let Ok(mut results) = current_results.lock() {
+                                                    if results.first().is_some_and(|r| {
+                                                        r.result_type == "shell-running" && r.path.as_os_str() == command.as_str()
+                                                    }) {
+                                                        *results = output_results.clone();
+                                                        show_results(&launcher_weak, &output_results);
+                                                    }
+                                                }
+                                            });
+                                        }
+                                        Err(e) => log::error!("Failed to run '{}': {}", run.command, e),
+                                    }
+                                }
+                            }
+                        }
+                        "note-add" | "todo-add" => {
+                            if let Ok(state) = state.lock() {
+                                let text = result.path.to_string_lossy();
+                                let added = match (&state.notes, result.result_type.as_str()) {
+                                    (Some(notes), "note-add") => notes.add_note(&text, chrono::Local::now().naive_local()),
+                                    (Some(notes), _) => notes.add_todo(&text),
+                                    (None, _) => Err("No notes file".into()),
+                                };
+                                if let Err(e) = added {
+                                    log::error!("Failed to add to notes: {}", e);
+                                }
+                            }
+                        }
+                        "todo" => {
+                            // Mark done and keep the remaining todos in view
+                            if let Ok(mut state) = state.lock() {
+                                state.run_result_action(ResultAction::MarkDone, result);
+                                stay_open = Some(notes::search_results(state.notes.as_ref(), NotesQuery::Todo("")));
+                            }
+                        }
+                        "process" => {
+                            if let Some(target) = &result.process {
+                                stay_open = Some(vec![processes::confirmation_result(target.clone())]);
+                            }
+                        }
+                        "process-end" => {
+                            if let (Some(target), Ok(state)) = (&result.process, state.lock()) {
+                                let name = target.name.clone();
+                                state.processes.end(target.clone(), move |outcome| {
+                                    if let Err(e) = outcome {
+                                        log::error!("Failed to end {}: {}", name, e);
+                                    }
+                                });
+                            }
+                        }
+                        "timer-add" => {
+                            if let (Some(request), Ok(mut state)) = (&result.timer, state.lock()) {
+                                state.timers.start(request, &chrono::Local::now());
+                            }
+                        }
+                        "timer" => {
+                            // Cancel and keep the remaining timers in view
+                            if let Ok(mut state) = state.lock() {
+                                state.run_result_action(ResultAction::CancelTimer, result);
+                                stay_open = Some(timers::active_results(&state.timers, &chrono::Local::now()));
+                            }
+                        }
+                        "ssh" => {
+                            // Connect in a new terminal window, passing the host to ssh directly
+                            if let Some(host) = &result.ssh {
+                                if let Err(e) = ssh::connect(host) {
+                                    log::error!("Failed to open terminal for {}: {}", host.command(), e);
+                                }
+                            }
+                        }
+                        "workspace" => {
+                            // Reopen the project in the editor it was last used in
+                            if let (Some(editor), Ok(state)) = (&result.editor, state.lock()) {
+                                match editor.open(&result.path, &state.apps) {
+                                    Ok(_) => log::info!("Opened {:?} in {}", result.path, editor.name()),
+                                    Err(e) => log::error!("Failed to open {:?} in {}: {}", result.path, editor.name(), e),
+                                }
+                            }
+                        }
+                        "color" | "toolbox" | "generated" | "password" => {
+                            // Copy the color notation, toolbox output or generated value
+                            if let Ok(mut state) = state.lock() {
+                                match state.clipboard.copy_result(result) {
+                                    Ok(_) => paste_after_hide = state.config.clipboard.paste_after_copy,
+                                    Err(e) => log::error!("Failed to copy {}: {}", result.result_type, e),
+                                }
+                            }
+                        }
+                        "character" => {
+                            // Copy the character and remember it as recently used
+                            if let Ok(mut state) = state.lock() {
+                                match state.clipboard.copy_result(result) {
+                                    Ok(_) => paste_after_hide = state.config.clipboard.paste_after_copy,
+                                    Err(e) => log::error!("Failed to copy character: {}", e),
+                                }
+                                let character = result.path.to_string_lossy();
+                                state.config.record_character(&character, characters::MAX_RECENT);
+                            }
+                        }
+                        "snippet" => {
+                            // Expand placeholders and copy, pasting when configured
+                            match state.lock() {
+                                Ok(mut state) => {
+                                    let keyword = result.path.to_string_lossy();
+                                    match state.snippets.get(&keyword).map(|snippet| snippet.body.clone()) {
+                                        Some(body) => {
+                                            let clipboard = state.clipboard.text().ok().flatten().unwrap_or_default();
+                                            let now = chrono::Local::now().naive_local();
+                                            let expansion = snippets::expand(&body, now, &clipboard);
+                                            match state.clipboard.copy(&expansion.text) {
+                                                Ok(()) => {
+                                                    paste_after_hide = state.config.clipboard.paste_after_copy;
+                                                    caret_back = expansion.caret_back;
+                                                }
+                                                Err(e) => log::error!("Failed to copy snippet: {}", e),
+                                            }
+                                        }
+                                        None => log::warn!("Snippet not found: {}", keyword),
+                                    }
+                                }
+                                Err(_) => log::error!("Failed to lock state for snippet"),
+                            }
+                        }
+                        "toolbox-hash" => {
+                            // Hash in background and show the digest once it is ready
+                            if let Some(file) = result.file_hash.clone() {
+                                stay_open = Some(vec![toolbox::hashing_result(&file)]);
+                                let current_results = Arc::clone(&current_results);
+                                let launcher_weak = launcher_weak.clone();
+                                std::thread::spawn(move || {
+                                    let hash_results = toolbox::hash_file(&file);
+
+                                    // Only replace the results if they still show this file
+                                    if let Ok(mut results) = current_results.lock() {
+                                        if results
+                                            .first()
+                                            .is_some_and(|r| r.result_type == "toolbox-hashing" && r.path == file.path)
+                                        {
+                                            *results = hash_results.clone();
+                                            show_results(&launcher_weak, &hash_results);
+                                        }
+                                    }
+                                });
+                            }
+                        }
+                        "toolbox-hashing" => stay_open = Some(vec![result.clone()]),
+                        "shell-running" => {
+                            if let Ok(mut state) = state.lock() {
+                                state.shell_runner.kill();
+                            }
+                            stay_open = Some(vec![result.clone()]);
+                        }
+                        "shell-output" => {
+                            if let Ok(mut state) = state.lock() {
+                                match state.clipboard.copy_result(result) {
+                                    Ok(_) => paste_after_hide = state.config.clipboard.paste_after_copy,
+                                    Err(e) => log::error!("Failed to copy command output: {}", e),
+                                }
+                            }
                         }
                         _ => {
                             log::warn!("Unknown result type: {}", result.result_type);
                         }
                     }
 
+                    // Keep the launcher open, showing the confirmation or the running command
+                    if let Some(stay_open) = stay_open {
+                        show_results(&launcher_weak, &stay_open);
+                        *results = stay_open;
+                        return;
+                    }
+
                     // Hide launcher after successful launch (expected behavior for a launcher)
-                    let _ = launcher_weak.upgrade_in_event_loop(|launcher: Launcher| {
+                    let _ = launcher_weak.upgrade_in_event_loop(move |launcher: Launcher| {
                         // Move off-screen and hide, but keep "shown" to prevent event loop exit
                         launcher.window().set_position(slint::LogicalPosition::new(-10000.0, -10000.0));
                         launcher.hide().ok();
                         launcher.set_is_visible(false);
                         log::debug!("Window hidden after launch (moved off-screen)");
+
+                        if paste_after_hide {
+                            std::thread::spawn(move || {
+                                if let Err(e) = platform_window::paste_into_previous_window(caret_back) {
+                                    log::warn!("Failed to paste into previous window: {}", e);
+                                }
+                            });
+                        }
                     });
                 } else {
                     log::warn!("No result found at index {}", index);
@@ -930,6 +1645,63 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         });
     }
 
+    // Handle actions menu - list the secondary actions of the selected result
+    {
+        let state = Arc::clone(&state);
+        let current_results = Arc::clone(&current_results);
+        let launcher_weak = launcher_weak.clone();
+
+        launcher.on_show_actions(move |index| {
+            let actions = match (current_results.lock(), state.lock()) {
+                (Ok(results), Ok(state)) => results
+                    .get(index as usize)
+                    .map(|result| result_actions::actions_for(result, state.config.is_pinned(&result.name)))
+                    .unwrap_or_default(),
+                _ => Vec::new(),
+            };
+
+            if actions.is_empty() {
+                log::debug!("No secondary actions for result at index {}", index);
+                return;
+            }
+
+            let items: Vec<ActionItem> = actions
+                .iter()
+                .map(|action| ActionItem {
+                    id: SharedString::from(action.id()),
+                    label: SharedString::from(action.label()),
+                })
+                .collect();
+
+            let _ = launcher_weak.upgrade_in_event_loop(move |launcher: Launcher| {
+                let model: slint::ModelRc<ActionItem> = std::rc::Rc::new(VecModel::from(items)).into();
+                launcher.set_actions(model);
+                launcher.set_selected_action(0);
+                launcher.set_actions_open(true);
+            });
+        });
+    }
+
+    // Handle copy shortcut - copy the selected result's value, path or URL
+    {
+        let state = Arc::clone(&state);
+        let current_results = Arc::clone(&current_results);
+
+        launcher.on_copy_result(move |index| {
+            if let Ok(results) = current_results.lock() {
+                if let Some(result) = results.get(index as usize) {
+                    if let Ok(mut state) = state.lock() {
+                        match state.clipboard.copy_result(result) {
+                            Ok(Some(_)) => {}
+                            Ok(None) => log::debug!("Nothing to copy for: {}", result.name),
+                            Err(e) => log::error!("Failed to copy {}: {}", result.name, e),
+                        }
+                    }
+                }
+            }
+        });
+    }
+
     // Handle escape key - hide window but DON'T exit
     {
         let launcher_weak = launcher_weak.clone();
@@ -987,9 +1759,62 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         });
     }
 
+    // Clipboard history monitor - records copied text while clipboard history is enabled
+    if config.clipboard.history_enabled {
+        let state = Arc::clone(&state);
+        let app_running_clipboard = Arc::clone(&app_running);
+        std::thread::spawn(move || {
+            log::info!("Clipboard history monitor started");
+            let mut monitor = ClipboardService::system();
+
+            while app_running_clipboard.load(Ordering::Relaxed) {
+                std::thread::sleep(std::time::Duration::from_millis(500));
+
+                let content = match monitor.read_if_changed() {
+                    Ok(Some(content)) => content,
+                    Ok(None) => continue,
+                    Err(e) => {
+                        log::debug!("Failed to read clipboard: {}", e);
+                        continue;
+                    }
+                };
+
+                if let Ok(mut state) = state.lock() {
+                    if let Some(history) = state.clipboard_history.as_mut() {
+                        if history.record(&content, chrono::Utc::now().timestamp()) {
+                            if let Err(e) = history.save() {
+                                log::warn!("Failed to save clipboard history: {}", e);
+                            }
+                        }
+                    }
+                }
+            }
+            log::info!("Clipboard history monitor shutting down");
+        });
+    }
+
+    // Timer service - shows notifications for timers and reminders as they come due
+    {
+        let state = Arc::clone(&state);
+        let app_running_timers = Arc::clone(&app_running);
+        std::thread::spawn(move || {
+            log::info!("Timer service started");
+            let notifier = SystemNotifier;
+
+            while app_running_timers.load(Ordering::Relaxed) {
+                if let Ok(mut state) = state.lock() {
+                    state.timers.fire_due(chrono::Utc::now().timestamp(), &notifier);
+                }
+                std::thread::sleep(std::time::Duration::from_secs(1));
+            }
+            log::info!("Timer service shutting down");
+        });
+    }
+
     // Background update checker thread - checks for updates every 24 hours
     {
         let app_running_updater = Arc::clone(&app_running);
+        let calculator_config = config.calculator.clone();
         std::thread::spawn(move || {
             log::info!("Background update checker started");
 
@@ -1014,6 +1839,15 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                     }
                 }
 
+                // Refresh exchange rates if an update URL is configured and they are stale
+                if let Some(url) = &calculator_config.rates_url {
+                    if currency::rates_need_update(calculator_config.rates_max_age_days) {
+                        if let Err(e) = currency::update_rates(url) {
+                            log::warn!("Failed to update exchange rates: {}", e);
+                        }
+                    }
+                }
+
                 // Wait 24 hours before next check
                 for _ in 0..(24 * 60 * 2) { // Check every 30 seconds for 24 hours
                     std::thread::sleep(std::time::Duration::from_secs(30));
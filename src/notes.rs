@@ -0,0 +1,324 @@
+//! Notes module
+//! Quick notes and todos appended to a Markdown file: `note buy cables` adds a
+//! timestamped bullet, `todo review PR 42` adds a `- [ ]` item and `todo` alone lists
+//! open items. The file may be edited outside Nexus at any time, so every change
+//! re-reads it and finds items by their text rather than by line number.
+
+use chrono::NaiveDateTime;
+use std::error::Error;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use crate::config::{AppConfig, NotesConfig};
+use crate::SearchResultData;
+
+/// Default file name of the notes file inside the configuration directory
+pub const NOTES_FILE_NAME: &str = "notes.md";
+
+/// Attempts to rewrite the file when it keeps changing underneath us
+const MAX_UPDATE_ATTEMPTS: usize = 3;
+
+const OPEN_MARK: &str = "[ ] ";
+const DONE_MARK: &str = "[x] ";
+
+/// A `note` or `todo` query
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NotesQuery<'a> {
+    Note(&'a str),
+    Todo(&'a str),
+}
+
+/// Parse "note buy cables", "todo review PR 42" or a bare "todo"
+pub fn parse_query(query: &str) -> Option<NotesQuery<'_>> {
+    let query = query.trim();
+    let (keyword, text) = query.split_once(char::is_whitespace).unwrap_or((query, ""));
+    match keyword.to_lowercase().as_str() {
+        "note" => Some(NotesQuery::Note(text.trim())),
+        "todo" => Some(NotesQuery::Todo(text.trim())),
+        _ => None,
+    }
+}
+
+/// Text of an open todo line ("- [ ] review PR 42"), ignoring indentation and the bullet style
+fn open_todo_text(line: &str) -> Option<&str> {
+    let line = line.trim_start();
+    let item = line.strip_prefix("- ").or_else(|| line.strip_prefix("* "))?;
+    item.strip_prefix(OPEN_MARK).map(str::trim_end)
+}
+
+/// The Markdown notes file
+#[derive(Debug, Clone)]
+pub struct NotesFile {
+    path: PathBuf,
+}
+
+impl NotesFile {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    /// File from the settings, or `notes.md` in the configuration directory
+    pub fn from_config(config: &NotesConfig) -> Option<Self> {
+        let path = match &config.file {
+            Some(file) => file.clone(),
+            None => AppConfig::config_dir(crate::single_instance::detect_portable_mode())?.join(NOTES_FILE_NAME),
+        };
+        Some(Self::new(path))
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Append a timestamped note
+    pub fn add_note(&self, text: &str, now: NaiveDateTime) -> Result<(), Box<dyn Error>> {
+        self.append_line(&format!("- {} {}", now.format("%Y-%m-%d %H:%M"), text.trim()))
+    }
+
+    /// Append an open todo item
+    pub fn add_todo(&self, text: &str) -> Result<(), Box<dyn Error>> {
+        self.append_line(&format!("- {}{}", OPEN_MARK, text.trim()))
+    }
+
+    /// Open todo items in file order
+    pub fn open_todos(&self) -> Result<Vec<String>, Box<dyn Error>> {
+        if !self.path.exists() {
+            return Ok(Vec::new());
+        }
+        let content = fs::read_to_string(&self.path)?;
+        Ok(content
+            .lines()
+            .filter_map(open_todo_text)
+            .map(str::to_string)
+            .collect())
+    }
+
+    /// Tick the first open todo with this text
+    pub fn complete_todo(&self, text: &str) -> Result<(), Box<dyn Error>> {
+        self.update_todo(text, |line| Some(line.replacen(OPEN_MARK, DONE_MARK, 1)))
+    }
+
+    /// Remove the first open todo with this text
+    pub fn delete_todo(&self, text: &str) -> Result<(), Box<dyn Error>> {
+        self.update_todo(text, |_| None)
+    }
+
+    /// Append a line, starting on a new line if the file doesn't end with one.
+    /// Appending never rewrites the file, so it can't lose concurrent edits.
+    fn append_line(&self, line: &str) -> Result<(), Box<dyn Error>> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let needs_newline = fs::read(&self.path)
+            .map(|bytes| bytes.last().is_some_and(|&b| b != b'\n'))
+            .unwrap_or(false);
+
+        let mut file = OpenOptions::new().create(true).append(true).open(&self.path)?;
+        if needs_newline {
+            writeln!(file)?;
+        }
+        writeln!(file, "{}", line)?;
+        log::info!("Added to {:?}: {}", self.path, line);
+        Ok(())
+    }
+
+    /// Replace (or with `None`, remove) the first open todo line with this text.
+    /// The file is re-read for every change and written through a temporary file; if it
+    /// was modified while we worked on it, the change is retried on the new contents.
+    fn update_todo(&self, text: &str, change: impl Fn(&str) -> Option<String>) -> Result<(), Box<dyn Error>> {
+        for _ in 0..MAX_UPDATE_ATTEMPTS {
+            let before = modified(&self.path)?;
+            let content = fs::read_to_string(&self.path)?;
+
+            let mut lines: Vec<String> = content.lines().map(str::to_string).collect();
+            let index = lines
+                .iter()
+                .position(|line| open_todo_text(line) == Some(text.trim()))
+                .ok_or_else(|| format!("Todo not found in {:?}: {}", self.path, text))?;
+            match change(&lines[index]) {
+                Some(line) => lines[index] = line,
+                None => {
+                    lines.remove(index);
+                }
+            }
+
+            let mut updated = lines.join(if content.contains("\r\n") { "\r\n" } else { "\n" });
+            if content.ends_with('\n') {
+                updated.push_str(if content.ends_with("\r\n") { "\r\n" } else { "\n" });
+            }
+
+            if modified(&self.path)? != before {
+                log::debug!("{:?} changed while updating, retrying", self.path);
+                continue;
+            }
+            let temp = self.path.with_extension("md.tmp");
+            fs::write(&temp, updated)?;
+            fs::rename(&temp, &self.path)?;
+            return Ok(());
+        }
+        Err(format!("{:?} kept changing, todo not updated", self.path).into())
+    }
+}
+
+/// Modification time and size, to notice edits made by other programs
+fn modified(path: &Path) -> Result<(Option<SystemTime>, u64), Box<dyn Error>> {
+    let metadata = fs::metadata(path)?;
+    Ok((metadata.modified().ok(), metadata.len()))
+}
+
+/// Results for a `note` or `todo` query; `file` is `None` when no location is known
+pub fn search_results(file: Option<&NotesFile>, query: NotesQuery) -> Vec<SearchResultData> {
+    let Some(file) = file else {
+        return vec![info_result("No notes file", "Set notes.file in the settings file")];
+    };
+    let open_file = SearchResultData {
+        name: "Open notes file".to_string(),
+        description: file.path().display().to_string(),
+        path: file.path().to_path_buf(),
+        result_type: "file".to_string(),
+        ..Default::default()
+    };
+
+    match query {
+        NotesQuery::Note("") => vec![
+            info_result("Type a note", "e.g. note buy cables"),
+            open_file,
+        ],
+        NotesQuery::Note(text) => vec![SearchResultData {
+            name: format!("Add note: {}", text),
+            description: format!("Append to {}", file.path().display()),
+            path: PathBuf::from(text),
+            result_type: "note-add".to_string(),
+            ..Default::default()
+        }],
+        NotesQuery::Todo("") => {
+            let todos = match file.open_todos() {
+                Ok(todos) => todos,
+                Err(e) => return vec![info_result("Failed to read todos", &e.to_string())],
+            };
+            if todos.is_empty() {
+                return vec![info_result("No open todos", "Add one with todo review PR 42"), open_file];
+            }
+            todo_results(&todos)
+        }
+        NotesQuery::Todo(text) => vec![SearchResultData {
+            name: format!("Add todo: {}", text),
+            description: format!("Append to {}", file.path().display()),
+            path: PathBuf::from(text),
+            result_type: "todo-add".to_string(),
+            ..Default::default()
+        }],
+    }
+}
+
+/// One result per open todo; Enter marks it done
+pub fn todo_results(todos: &[String]) -> Vec<SearchResultData> {
+    todos
+        .iter()
+        .map(|todo| SearchResultData {
+            name: todo.clone(),
+            description: "Enter to mark done · Tab for more".to_string(),
+            path: PathBuf::from(todo),
+            result_type: "todo".to_string(),
+            ..Default::default()
+        })
+        .collect()
+}
+
+fn info_result(name: &str, description: &str) -> SearchResultData {
+    SearchResultData {
+        name: name.to_string(),
+        description: description.to_string(),
+        result_type: "info".to_string(),
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn temp_file(name: &str) -> NotesFile {
+        let path = std::env::temp_dir().join(format!("nexus_notes_{}_{}.md", name, std::process::id()));
+        let _ = fs::remove_file(&path);
+        NotesFile::new(path)
+    }
+
+    #[test]
+    fn test_parse_query() {
+        assert_eq!(parse_query("note buy cables"), Some(NotesQuery::Note("buy cables")));
+        assert_eq!(parse_query("TODO  review PR 42 "), Some(NotesQuery::Todo("review PR 42")));
+        assert_eq!(parse_query("todo"), Some(NotesQuery::Todo("")));
+        assert_eq!(parse_query("notepad"), None);
+    }
+
+    #[test]
+    fn test_add_and_list() {
+        let file = temp_file("add");
+        let now = NaiveDate::from_ymd_opt(2026, 10, 18).unwrap().and_hms_opt(9, 30, 0).unwrap();
+        file.add_note("buy cables", now).unwrap();
+        file.add_todo("review PR 42").unwrap();
+        file.add_todo("update docs").unwrap();
+
+        assert_eq!(
+            fs::read_to_string(file.path()).unwrap(),
+            "- 2026-10-18 09:30 buy cables\n- [ ] review PR 42\n- [ ] update docs\n"
+        );
+        assert_eq!(file.open_todos().unwrap(), ["review PR 42", "update docs"]);
+
+        file.complete_todo("review PR 42").unwrap();
+        file.delete_todo("update docs").unwrap();
+        assert!(file.open_todos().unwrap().is_empty());
+        assert_eq!(
+            fs::read_to_string(file.path()).unwrap(),
+            "- 2026-10-18 09:30 buy cables\n- [x] review PR 42\n"
+        );
+        fs::remove_file(file.path()).unwrap();
+    }
+
+    #[test]
+    fn test_external_edits() {
+        let file = temp_file("external");
+        file.add_todo("first").unwrap();
+        file.add_todo("second").unwrap();
+        let listed = file.open_todos().unwrap();
+
+        // Another editor reorders the items, adds a heading and leaves no trailing newline
+        fs::write(file.path(), "# Inbox\r\n* [ ] second\r\n  - [ ] first").unwrap();
+
+        // Items are found by text, not by the line they were listed at
+        file.complete_todo(&listed[0]).unwrap();
+        assert_eq!(fs::read_to_string(file.path()).unwrap(), "# Inbox\r\n* [ ] second\r\n  - [x] first");
+
+        // Appending starts a new line and keeps the external content
+        file.add_todo("third").unwrap();
+        assert_eq!(file.open_todos().unwrap(), ["second", "third"]);
+
+        // An item removed elsewhere is reported instead of touching another line
+        fs::write(file.path(), "- [ ] third\n").unwrap();
+        assert!(file.complete_todo(&listed[1]).is_err());
+        assert_eq!(fs::read_to_string(file.path()).unwrap(), "- [ ] third\n");
+        fs::remove_file(file.path()).unwrap();
+    }
+
+    #[test]
+    fn test_search_results() {
+        let file = temp_file("results");
+        let empty = search_results(Some(&file), NotesQuery::Todo(""));
+        assert_eq!(empty[0].name, "No open todos");
+        assert_eq!(empty[1].result_type, "file");
+
+        file.add_todo("call Alex").unwrap();
+        let listed = search_results(Some(&file), NotesQuery::Todo(""));
+        assert_eq!(listed[0].name, "call Alex");
+        assert_eq!(listed[0].result_type, "todo");
+
+        let add = search_results(Some(&file), NotesQuery::Note("idea"));
+        assert_eq!(add[0].result_type, "note-add");
+        assert_eq!(add[0].path, PathBuf::from("idea"));
+        fs::remove_file(file.path()).unwrap();
+    }
+}
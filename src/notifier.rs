@@ -0,0 +1,46 @@
+//! Notifier module
+//! Shows notifications from the background service, behind a trait so callers can be
+//! tested with a fake
+
+use std::error::Error;
+
+/// Something that can show a notification to the user
+pub trait Notifier: Send + Sync {
+    fn notify(&self, title: &str, message: &str) -> Result<(), Box<dyn Error>>;
+}
+
+/// Notifications shown by the operating system
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SystemNotifier;
+
+impl Notifier for SystemNotifier {
+    /// Show a topmost message box on its own thread, so the caller never waits for it
+    #[cfg(windows)]
+    fn notify(&self, title: &str, message: &str) -> Result<(), Box<dyn Error>> {
+        use windows::core::PCWSTR;
+        use windows::Win32::Foundation::HWND;
+        use windows::Win32::UI::WindowsAndMessaging::{
+            MessageBoxW, MB_ICONINFORMATION, MB_OK, MB_SETFOREGROUND, MB_TOPMOST,
+        };
+
+        let title: Vec<u16> = title.encode_utf16().chain(std::iter::once(0)).collect();
+        let message: Vec<u16> = message.encode_utf16().chain(std::iter::once(0)).collect();
+        std::thread::Builder::new()
+            .name("notification".to_string())
+            .spawn(move || unsafe {
+                MessageBoxW(
+                    HWND::default(),
+                    PCWSTR(message.as_ptr()),
+                    PCWSTR(title.as_ptr()),
+                    MB_OK | MB_ICONINFORMATION | MB_TOPMOST | MB_SETFOREGROUND,
+                );
+            })?;
+        Ok(())
+    }
+
+    #[cfg(not(windows))]
+    fn notify(&self, title: &str, message: &str) -> Result<(), Box<dyn Error>> {
+        log::info!("Notification: {}: {}", title, message);
+        Ok(())
+    }
+}
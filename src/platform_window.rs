@@ -2,7 +2,12 @@
 //! Applies Win32 window styles to Slint windows for launcher behavior
 
 use slint::Window;
+use std::sync::atomic::{AtomicIsize, Ordering};
 use windows::Win32::Foundation::HWND;
+use windows::Win32::UI::Input::KeyboardAndMouse::{
+    SendInput, INPUT, INPUT_0, INPUT_KEYBOARD, KEYBDINPUT, KEYBD_EVENT_FLAGS, KEYEVENTF_KEYUP,
+    VIRTUAL_KEY, VK_CONTROL, VK_LEFT, VK_V,
+};
 use windows::Win32::UI::WindowsAndMessaging::*;
 use raw_window_handle::{HasWindowHandle, RawWindowHandle};
 
@@ -144,3 +149,61 @@ pub fn enable_launcher_focus(window: &Window) -> Result<(), Box<dyn std::error::
     }
 }
 
+/// Window that had focus before the launcher was shown, as a raw HWND value
+static PREVIOUS_WINDOW: AtomicIsize = AtomicIsize::new(0);
+
+/// Remember the currently focused window; call before showing the launcher
+pub fn remember_foreground_window() {
+    let hwnd = unsafe { GetForegroundWindow() };
+    PREVIOUS_WINDOW.store(hwnd.0 as isize, Ordering::Relaxed);
+}
+
+/// Give focus back to the previously focused window and send Ctrl+V to it, then move
+/// the caret `caret_back` characters to the left (for a snippet's `{cursor}`).
+/// Call after the launcher has been hidden.
+pub fn paste_into_previous_window(caret_back: usize) -> Result<(), Box<dyn std::error::Error>> {
+    let hwnd = HWND(PREVIOUS_WINDOW.load(Ordering::Relaxed) as *mut _);
+    if hwnd.0.is_null() {
+        return Err("No previously focused window".into());
+    }
+
+    unsafe {
+        if !SetForegroundWindow(hwnd).as_bool() {
+            return Err("Failed to focus the previous window".into());
+        }
+
+        // Let the target window process the focus change before it receives input
+        std::thread::sleep(std::time::Duration::from_millis(50));
+
+        let key = |vk: VIRTUAL_KEY, flags: KEYBD_EVENT_FLAGS| INPUT {
+            r#type: INPUT_KEYBOARD,
+            Anonymous: INPUT_0 {
+                ki: KEYBDINPUT {
+                    wVk: vk,
+                    wScan: 0,
+                    dwFlags: flags,
+                    time: 0,
+                    dwExtraInfo: 0,
+                },
+            },
+        };
+        let mut inputs = vec![
+            key(VK_CONTROL, KEYBD_EVENT_FLAGS(0)),
+            key(VK_V, KEYBD_EVENT_FLAGS(0)),
+            key(VK_V, KEYEVENTF_KEYUP),
+            key(VK_CONTROL, KEYEVENTF_KEYUP),
+        ];
+        for _ in 0..caret_back {
+            inputs.push(key(VK_LEFT, KEYBD_EVENT_FLAGS(0)));
+            inputs.push(key(VK_LEFT, KEYEVENTF_KEYUP));
+        }
+
+        let sent = SendInput(&inputs, std::mem::size_of::<INPUT>() as i32);
+        if sent as usize != inputs.len() {
+            return Err("Failed to send paste keystrokes".into());
+        }
+    }
+
+    log::debug!("Pasted into previous window: {:?}", hwnd);
+    Ok(())
+}
@@ -0,0 +1,696 @@
+//! Processes module
+//! Lists running processes for `ps node` and `kill chrome` with their PID, memory and
+//! CPU use, and ends them (or their whole process tree) after confirmation.
+//! Enumeration goes through `ProcessSource`, implemented with Toolhelp on Windows and
+//! `/proc` on Linux.
+
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::SearchResultData;
+
+/// Most processes listed for a query
+const MAX_RESULTS: usize = 20;
+
+/// CPU use is measured over at least this long
+const MIN_SAMPLE_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Older samples are taken again before measuring CPU use
+const MAX_SAMPLE_AGE: Duration = Duration::from_secs(5);
+
+/// Searches within this long of the last sample reuse it
+const REFRESH_INTERVAL: Duration = Duration::from_secs(1);
+
+/// A running process as reported by a `ProcessSource`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProcessInfo {
+    pub pid: u32,
+    pub parent_pid: u32,
+    pub name: String,
+
+    /// Resident memory (working set on Windows)
+    pub memory_bytes: u64,
+
+    /// User and kernel time used since the process started
+    pub cpu_time: Duration,
+
+    /// When the process started, from a platform-specific epoch; `None` if unknown
+    pub start_time: Option<Duration>,
+}
+
+/// Enumerates and terminates processes
+pub trait ProcessSource: Send + Sync {
+    fn processes(&self) -> Result<Vec<ProcessInfo>, Box<dyn Error>>;
+    fn terminate(&self, pid: u32) -> Result<(), Box<dyn Error>>;
+}
+
+/// A process to end, carried from the result to its confirmation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProcessTarget {
+    pub pid: u32,
+    pub name: String,
+
+    /// End the child processes too
+    pub tree: bool,
+}
+
+/// Search terms of "ps node" or "kill chrome"; `ps` and `kill` alone list everything
+pub fn parse_query(query: &str) -> Option<&str> {
+    let query = query.trim_start();
+    let (keyword, rest) = query.split_once(char::is_whitespace).unwrap_or((query, ""));
+    matches!(keyword.to_lowercase().as_str(), "ps" | "kill").then(|| rest.trim())
+}
+
+/// A process with its CPU use over the last sample interval
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProcessUsage {
+    pub process: ProcessInfo,
+
+    /// Percent of total CPU capacity (all cores)
+    pub cpu_percent: f64,
+}
+
+/// The processes of the last sample, shared with the thread taking the next one
+#[derive(Default)]
+struct Snapshot {
+    /// When the sample was taken and what it found
+    sample: Option<(Instant, Result<Vec<ProcessUsage>, String>)>,
+
+    /// A sample is being taken
+    refreshing: bool,
+}
+
+/// Lists processes with CPU use measured between samples. Samples are taken on a
+/// background thread, so searches never wait for the process table.
+pub struct ProcessMonitor {
+    source: Arc<dyn ProcessSource>,
+    cores: usize,
+    snapshot: Arc<Mutex<Snapshot>>,
+}
+
+impl ProcessMonitor {
+    pub fn new(source: Box<dyn ProcessSource>) -> Self {
+        Self {
+            source: Arc::from(source),
+            cores: std::thread::available_parallelism().map_or(1, |n| n.get()),
+            snapshot: Arc::new(Mutex::new(Snapshot::default())),
+        }
+    }
+
+    /// Monitor for the processes of this machine
+    pub fn system() -> Self {
+        Self::new(Box::new(SystemProcesses))
+    }
+
+    /// Running processes except Nexus itself as of the last sample; `None` before the first
+    pub fn processes(&self) -> Option<Result<Vec<ProcessUsage>, String>> {
+        let snapshot = self.snapshot.lock().ok()?;
+        snapshot.sample.as_ref().map(|(_, processes)| processes.clone())
+    }
+
+    /// Take a new sample on a background thread unless the last one is recent or one is
+    /// already being taken. `done` runs once the new sample is available.
+    pub fn refresh(&self, done: impl FnOnce() + Send + 'static) {
+        let previous = {
+            let Ok(mut snapshot) = self.snapshot.lock() else {
+                return;
+            };
+            let recent = snapshot
+                .sample
+                .as_ref()
+                .is_some_and(|(taken, _)| taken.elapsed() < REFRESH_INTERVAL);
+            if recent || snapshot.refreshing {
+                return;
+            }
+            snapshot.refreshing = true;
+
+            // CPU use is measured against the last sample while it is recent enough
+            match &snapshot.sample {
+                Some((taken, Ok(usage))) if taken.elapsed() <= MAX_SAMPLE_AGE => Some((
+                    *taken,
+                    usage.iter().map(|u| (u.process.pid, u.process.cpu_time)).collect(),
+                )),
+                _ => None,
+            }
+        };
+
+        let source = Arc::clone(&self.source);
+        let snapshot = Arc::clone(&self.snapshot);
+        let cores = self.cores;
+        std::thread::spawn(move || {
+            let sample = sample(source.as_ref(), previous, cores).map_err(|e| {
+                log::error!("Failed to list processes: {}", e);
+                e.to_string()
+            });
+            if let Ok(mut snapshot) = snapshot.lock() {
+                snapshot.sample = Some((Instant::now(), sample));
+                snapshot.refreshing = false;
+            }
+            done();
+        });
+    }
+
+    /// End a process, or with `tree` its descendants first and then the process itself,
+    /// on a background thread since listing the tree takes a full snapshot. `done` runs
+    /// with the outcome.
+    pub fn end(&self, target: ProcessTarget, done: impl FnOnce(Result<(), String>) + Send + 'static) {
+        let source = Arc::clone(&self.source);
+        std::thread::spawn(move || done(end_process(source.as_ref(), &target).map_err(|e| e.to_string())));
+    }
+}
+
+fn end_process(source: &dyn ProcessSource, target: &ProcessTarget) -> Result<(), Box<dyn Error>> {
+    let pids = if target.tree {
+        tree_pids(&source.processes()?, target.pid)
+    } else {
+        vec![target.pid]
+    };
+
+    let mut failed = Vec::new();
+    for pid in pids {
+        if let Err(e) = source.terminate(pid) {
+            // Children may exit on their own while the tree is being ended
+            log::warn!("Failed to end process {}: {}", pid, e);
+            if pid == target.pid {
+                failed.push(e.to_string());
+            }
+        }
+    }
+    if failed.is_empty() {
+        log::info!("Ended {} (PID {})", target.name, target.pid);
+        Ok(())
+    } else {
+        Err(failed.join("; ").into())
+    }
+}
+
+fn cpu_times(processes: &[ProcessInfo]) -> HashMap<u32, Duration> {
+    processes.iter().map(|p| (p.pid, p.cpu_time)).collect()
+}
+
+/// Processes with CPU use since `previous`. Without a previous sample, one is taken
+/// first and the call waits briefly.
+fn sample(
+    source: &dyn ProcessSource,
+    previous: Option<(Instant, HashMap<u32, Duration>)>,
+    cores: usize,
+) -> Result<Vec<ProcessUsage>, Box<dyn Error>> {
+    let (taken, previous) = match previous {
+        Some(previous) => previous,
+        None => {
+            let previous = (Instant::now(), cpu_times(&source.processes()?));
+            std::thread::sleep(MIN_SAMPLE_INTERVAL);
+            previous
+        }
+    };
+    let processes = source.processes()?;
+    Ok(processes_with_usage(processes, &previous, taken.elapsed(), cores))
+}
+
+/// Attach CPU use to the processes, comparing with the CPU times of a previous sample
+fn processes_with_usage(
+    processes: Vec<ProcessInfo>,
+    previous: &HashMap<u32, Duration>,
+    elapsed: Duration,
+    cores: usize,
+) -> Vec<ProcessUsage> {
+    let own_pid = std::process::id();
+    let capacity = elapsed.as_secs_f64() * cores.max(1) as f64;
+
+    processes
+        .into_iter()
+        .filter(|process| process.pid != own_pid)
+        .map(|process| {
+            let used = previous
+                .get(&process.pid)
+                .map_or(Duration::ZERO, |before| process.cpu_time.saturating_sub(*before));
+            let cpu_percent = if capacity > 0.0 {
+                (used.as_secs_f64() / capacity * 100.0).min(100.0)
+            } else {
+                0.0
+            };
+            ProcessUsage { process, cpu_percent }
+        })
+        .collect()
+}
+
+/// The process and all its descendants, children before their parents
+pub fn tree_pids(processes: &[ProcessInfo], root: u32) -> Vec<u32> {
+    let start_times: HashMap<u32, Option<Duration>> = processes.iter().map(|p| (p.pid, p.start_time)).collect();
+    let mut children: HashMap<u32, Vec<u32>> = HashMap::new();
+    for process in processes {
+        // A process older than its parent was started by an ended process whose PID
+        // has since been reused, so it is not part of this tree
+        let parent_start = start_times.get(&process.parent_pid).copied().flatten();
+        let orphaned = process.start_time.zip(parent_start).is_some_and(|(child, parent)| child < parent);
+        if process.pid != process.parent_pid && !orphaned {
+            children.entry(process.parent_pid).or_default().push(process.pid);
+        }
+    }
+
+    // Depth-first, recording each process after its children; PIDs can be reused,
+    // so a visited set guards against cycles in stale parent links
+    let mut order = Vec::new();
+    let mut visited = HashSet::new();
+    let mut stack = vec![(root, false)];
+    while let Some((pid, expanded)) = stack.pop() {
+        if expanded {
+            order.push(pid);
+        } else if visited.insert(pid) {
+            stack.push((pid, true));
+            for &child in children.get(&pid).into_iter().flatten() {
+                stack.push((child, false));
+            }
+        }
+    }
+    order
+}
+
+/// Results for a `ps` or `kill` query: matching processes by name or PID, largest first
+pub fn search_results(processes: Vec<ProcessUsage>, terms: &str) -> Vec<SearchResultData> {
+    let terms = terms.to_lowercase();
+    let mut matches: Vec<ProcessUsage> = processes
+        .into_iter()
+        .filter(|usage| usage.process.name.to_lowercase().contains(&terms) || usage.process.pid.to_string() == terms)
+        .collect();
+    matches.sort_by_key(|usage| std::cmp::Reverse(usage.process.memory_bytes));
+
+    if matches.is_empty() {
+        return vec![SearchResultData {
+            name: format!("No process matching \"{}\"", terms),
+            description: "Search by process name or PID".to_string(),
+            result_type: "info".to_string(),
+            ..Default::default()
+        }];
+    }
+
+    matches
+        .into_iter()
+        .take(MAX_RESULTS)
+        .map(|usage| {
+            let process = usage.process;
+            SearchResultData {
+                description: format!(
+                    "PID {} · {} · CPU {:.1}% · Enter to end task",
+                    process.pid,
+                    format_memory(process.memory_bytes),
+                    usage.cpu_percent
+                ),
+                path: PathBuf::from(process.pid.to_string()),
+                result_type: "process".to_string(),
+                process: Some(ProcessTarget {
+                    pid: process.pid,
+                    name: process.name.clone(),
+                    tree: false,
+                }),
+                name: process.name,
+                ..Default::default()
+            }
+        })
+        .collect()
+}
+
+/// Confirmation shown before ending a process
+pub fn confirmation_result(target: ProcessTarget) -> SearchResultData {
+    let name = if target.tree {
+        format!("End {} (PID {}) and its child processes?", target.name, target.pid)
+    } else {
+        format!("End {} (PID {})?", target.name, target.pid)
+    };
+    SearchResultData {
+        name,
+        description: "Press Enter to confirm · unsaved work in the process is lost".to_string(),
+        path: PathBuf::from(target.pid.to_string()),
+        result_type: "process-end".to_string(),
+        process: Some(target),
+        ..Default::default()
+    }
+}
+
+/// "850 KB", "312 MB", "1.2 GB"
+fn format_memory(bytes: u64) -> String {
+    const KB: f64 = 1024.0;
+    let bytes = bytes as f64;
+    if bytes >= KB * KB * KB {
+        format!("{:.1} GB", bytes / (KB * KB * KB))
+    } else if bytes >= KB * KB {
+        format!("{:.0} MB", bytes / (KB * KB))
+    } else {
+        format!("{:.0} KB", bytes / KB)
+    }
+}
+
+/// Processes of this machine
+pub struct SystemProcesses;
+
+#[cfg(windows)]
+impl ProcessSource for SystemProcesses {
+    fn processes(&self) -> Result<Vec<ProcessInfo>, Box<dyn Error>> {
+        use windows::Win32::Foundation::CloseHandle;
+        use windows::Win32::System::Diagnostics::ToolHelp::{
+            CreateToolhelp32Snapshot, Process32FirstW, Process32NextW, PROCESSENTRY32W, TH32CS_SNAPPROCESS,
+        };
+
+        let mut processes = Vec::new();
+        unsafe {
+            let snapshot = CreateToolhelp32Snapshot(TH32CS_SNAPPROCESS, 0)?;
+            let mut entry = PROCESSENTRY32W {
+                dwSize: std::mem::size_of::<PROCESSENTRY32W>() as u32,
+                ..Default::default()
+            };
+
+            let mut found = Process32FirstW(snapshot, &mut entry).is_ok();
+            while found {
+                let len = entry.szExeFile.iter().position(|&c| c == 0).unwrap_or(entry.szExeFile.len());
+                let (memory_bytes, cpu_time, start_time) = windows_usage(entry.th32ProcessID);
+                processes.push(ProcessInfo {
+                    pid: entry.th32ProcessID,
+                    parent_pid: entry.th32ParentProcessID,
+                    name: String::from_utf16_lossy(&entry.szExeFile[..len]),
+                    memory_bytes,
+                    cpu_time,
+                    start_time,
+                });
+                found = Process32NextW(snapshot, &mut entry).is_ok();
+            }
+            let _ = CloseHandle(snapshot);
+        }
+        Ok(processes)
+    }
+
+    fn terminate(&self, pid: u32) -> Result<(), Box<dyn Error>> {
+        use windows::Win32::Foundation::CloseHandle;
+        use windows::Win32::System::Threading::{OpenProcess, TerminateProcess, PROCESS_TERMINATE};
+
+        unsafe {
+            let process = OpenProcess(PROCESS_TERMINATE, false, pid)?;
+            let result = TerminateProcess(process, 1);
+            let _ = CloseHandle(process);
+            result?;
+        }
+        Ok(())
+    }
+}
+
+/// Working set, CPU time and creation time of a process; zero and `None` for
+/// processes we may not query
+#[cfg(windows)]
+fn windows_usage(pid: u32) -> (u64, Duration, Option<Duration>) {
+    use windows::Win32::Foundation::{CloseHandle, FILETIME};
+    use windows::Win32::System::ProcessStatus::{K32GetProcessMemoryInfo, PROCESS_MEMORY_COUNTERS};
+    use windows::Win32::System::Threading::{GetProcessTimes, OpenProcess, PROCESS_QUERY_LIMITED_INFORMATION};
+
+    unsafe {
+        let Ok(process) = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, pid) else {
+            return (0, Duration::ZERO, None);
+        };
+
+        let mut counters = PROCESS_MEMORY_COUNTERS {
+            cb: std::mem::size_of::<PROCESS_MEMORY_COUNTERS>() as u32,
+            ..Default::default()
+        };
+        let memory = if K32GetProcessMemoryInfo(process, &mut counters, counters.cb).as_bool() {
+            counters.WorkingSetSize as u64
+        } else {
+            0
+        };
+
+        let (mut created, mut exited, mut kernel, mut user) =
+            (FILETIME::default(), FILETIME::default(), FILETIME::default(), FILETIME::default());
+        let (cpu_time, start_time) = match GetProcessTimes(process, &mut created, &mut exited, &mut kernel, &mut user) {
+            // FILETIME counts 100 ns intervals; the creation time counts from 1601
+            Ok(()) => {
+                let ticks = |t: FILETIME| ((t.dwHighDateTime as u64) << 32) | t.dwLowDateTime as u64;
+                (
+                    Duration::from_nanos((ticks(kernel) + ticks(user)) * 100),
+                    Some(Duration::from_micros(ticks(created) / 10)),
+                )
+            }
+            Err(_) => (Duration::ZERO, None),
+        };
+
+        let _ = CloseHandle(process);
+        (memory, cpu_time, start_time)
+    }
+}
+
+#[cfg(target_os = "linux")]
+impl ProcessSource for SystemProcesses {
+    fn processes(&self) -> Result<Vec<ProcessInfo>, Box<dyn Error>> {
+        let mut processes = Vec::new();
+        for entry in std::fs::read_dir("/proc")?.flatten() {
+            let Some(pid) = entry.file_name().to_str().and_then(|name| name.parse::<u32>().ok()) else {
+                continue;
+            };
+            // Processes can exit between listing /proc and reading their files
+            let Ok(stat) = std::fs::read_to_string(entry.path().join("stat")) else {
+                continue;
+            };
+            let Some((name, parent_pid, cpu_time, start_time)) = parse_proc_stat(&stat) else {
+                continue;
+            };
+            let memory_bytes = std::fs::read_to_string(entry.path().join("status"))
+                .ok()
+                .and_then(|status| parse_vm_rss(&status))
+                .unwrap_or(0);
+            processes.push(ProcessInfo {
+                pid,
+                parent_pid,
+                name,
+                memory_bytes,
+                cpu_time,
+                start_time,
+            });
+        }
+        Ok(processes)
+    }
+
+    fn terminate(&self, pid: u32) -> Result<(), Box<dyn Error>> {
+        let status = std::process::Command::new("kill")
+            .args(["-KILL", &pid.to_string()])
+            .status()?;
+        if status.success() {
+            Ok(())
+        } else {
+            Err(format!("kill exited with {}", status).into())
+        }
+    }
+}
+
+#[cfg(not(any(windows, target_os = "linux")))]
+impl ProcessSource for SystemProcesses {
+    fn processes(&self) -> Result<Vec<ProcessInfo>, Box<dyn Error>> {
+        Err("Listing processes is not supported on this platform".into())
+    }
+
+    fn terminate(&self, _pid: u32) -> Result<(), Box<dyn Error>> {
+        Err("Ending processes is not supported on this platform".into())
+    }
+}
+
+/// Name, parent PID, CPU time and start time since boot from `/proc/<pid>/stat`. The name
+/// is in parentheses and may itself contain spaces and parentheses, so fields are read
+/// after the last `)`.
+#[cfg(target_os = "linux")]
+fn parse_proc_stat(stat: &str) -> Option<(String, u32, Duration, Option<Duration>)> {
+    // Clock ticks per second (USER_HZ), 100 on all mainstream kernels
+    const TICKS_PER_SEC: u64 = 100;
+
+    let name = stat.get(stat.find('(')? + 1..stat.rfind(')')?)?.to_string();
+    let fields: Vec<&str> = stat[stat.rfind(')')? + 1..].split_whitespace().collect();
+    let parent_pid = fields.get(1)?.parse().ok()?;
+    let user: u64 = fields.get(11)?.parse().ok()?;
+    let system: u64 = fields.get(12)?.parse().ok()?;
+    let cpu_time = Duration::from_millis((user + system) * 1000 / TICKS_PER_SEC);
+    let start_time = fields
+        .get(19)
+        .and_then(|ticks| ticks.parse::<u64>().ok())
+        .map(|ticks| Duration::from_millis(ticks * 1000 / TICKS_PER_SEC));
+    Some((name, parent_pid, cpu_time, start_time))
+}
+
+/// Resident memory from `/proc/<pid>/status` ("VmRSS:   12345 kB")
+#[cfg(target_os = "linux")]
+fn parse_vm_rss(status: &str) -> Option<u64> {
+    let line = status.lines().find(|line| line.starts_with("VmRSS:"))?;
+    let kb: u64 = line.split_whitespace().nth(1)?.parse().ok()?;
+    Some(kb * 1024)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Fixed process table that records which PIDs were terminated
+    struct FakeProcesses {
+        processes: Vec<ProcessInfo>,
+        terminated: Arc<Mutex<Vec<u32>>>,
+    }
+
+    impl ProcessSource for FakeProcesses {
+        fn processes(&self) -> Result<Vec<ProcessInfo>, Box<dyn Error>> {
+            Ok(self.processes.clone())
+        }
+
+        fn terminate(&self, pid: u32) -> Result<(), Box<dyn Error>> {
+            self.terminated.lock().unwrap().push(pid);
+            Ok(())
+        }
+    }
+
+    fn process(pid: u32, parent_pid: u32, name: &str, memory_mb: u64, cpu_ms: u64) -> ProcessInfo {
+        ProcessInfo {
+            pid,
+            parent_pid,
+            name: name.to_string(),
+            memory_bytes: memory_mb * 1024 * 1024,
+            cpu_time: Duration::from_millis(cpu_ms),
+            start_time: None,
+        }
+    }
+
+    fn table() -> Vec<ProcessInfo> {
+        vec![
+            process(100, 1, "chrome.exe", 300, 5_000),
+            process(101, 100, "chrome.exe", 150, 1_000),
+            process(102, 101, "chrome.exe", 80, 200),
+            process(200, 1, "node.exe", 90, 700),
+            process(300, 1, "Code.exe", 500, 9_000),
+        ]
+    }
+
+    #[test]
+    fn test_parse_query() {
+        assert_eq!(parse_query("kill chrome"), Some("chrome"));
+        assert_eq!(parse_query("PS node "), Some("node"));
+        assert_eq!(parse_query("ps"), Some(""));
+        assert_eq!(parse_query("psql"), None);
+        assert_eq!(parse_query("killer queen"), None);
+    }
+
+    #[test]
+    fn test_cpu_usage() {
+        let previous = cpu_times(&table());
+        let mut later = table();
+        later[0].cpu_time += Duration::from_millis(500);
+
+        // Half a second of CPU over one second on two cores is 25%
+        let usage = processes_with_usage(later, &previous, Duration::from_secs(1), 2);
+        assert_eq!(usage[0].cpu_percent, 25.0);
+        assert_eq!(usage[1].cpu_percent, 0.0);
+
+        // A process missing from the previous sample shows no CPU use yet
+        let usage = processes_with_usage(table(), &HashMap::new(), Duration::from_secs(1), 2);
+        assert!(usage.iter().all(|u| u.cpu_percent == 0.0));
+    }
+
+    #[test]
+    fn test_search_results() {
+        let usage = processes_with_usage(table(), &HashMap::new(), Duration::from_secs(1), 1);
+        let results = search_results(usage.clone(), "CHROME");
+        let pids: Vec<_> = results.iter().map(|r| r.path.clone()).collect();
+        assert_eq!(pids, [PathBuf::from("100"), PathBuf::from("101"), PathBuf::from("102")]);
+        assert_eq!(results[0].description, "PID 100 · 300 MB · CPU 0.0% · Enter to end task");
+        assert_eq!(results[0].result_type, "process");
+
+        assert_eq!(search_results(usage.clone(), "200")[0].name, "node.exe");
+        assert_eq!(search_results(usage.clone(), "")[0].name, "Code.exe");
+        assert_eq!(search_results(usage, "firefox")[0].result_type, "info");
+    }
+
+    #[test]
+    fn test_end_process_tree() {
+        let terminated = Arc::new(Mutex::new(Vec::new()));
+        let monitor = ProcessMonitor::new(Box::new(FakeProcesses {
+            processes: table(),
+            terminated: Arc::clone(&terminated),
+        }));
+        let target = |tree| ProcessTarget {
+            pid: 100,
+            name: "chrome.exe".to_string(),
+            tree,
+        };
+
+        let end = |target: ProcessTarget| {
+            let (sender, receiver) = std::sync::mpsc::channel();
+            monitor.end(target, move |outcome| sender.send(outcome).unwrap());
+            receiver.recv_timeout(Duration::from_secs(5)).unwrap()
+        };
+
+        end(target(false)).unwrap();
+        assert_eq!(*terminated.lock().unwrap(), [100]);
+
+        // Children end before their parents
+        terminated.lock().unwrap().clear();
+        end(target(true)).unwrap();
+        assert_eq!(*terminated.lock().unwrap(), [102, 101, 100]);
+
+        let confirm = confirmation_result(target(true));
+        assert_eq!(confirm.name, "End chrome.exe (PID 100) and its child processes?");
+        assert_eq!(confirm.result_type, "process-end");
+    }
+
+    #[test]
+    fn test_tree_with_reused_pids() {
+        // A stale parent link pointing back into the tree must not loop forever
+        let processes = vec![process(10, 11, "a", 1, 0), process(11, 10, "b", 1, 0)];
+        assert_eq!(tree_pids(&processes, 10), [11, 10]);
+
+        // A process started before its parent had an earlier owner of the parent's PID
+        let started = |mut process: ProcessInfo, secs| {
+            process.start_time = Some(Duration::from_secs(secs));
+            process
+        };
+        let processes = vec![
+            started(process(10, 1, "new", 1, 0), 50),
+            started(process(11, 10, "orphan", 1, 0), 20),
+            started(process(12, 10, "child", 1, 0), 60),
+        ];
+        assert_eq!(tree_pids(&processes, 10), [12, 10]);
+    }
+
+    #[test]
+    fn test_background_sample() {
+        let monitor = ProcessMonitor::new(Box::new(FakeProcesses {
+            processes: table(),
+            terminated: Arc::new(Mutex::new(Vec::new())),
+        }));
+        assert!(monitor.processes().is_none());
+
+        // A refresh while one is running is ignored, so `done` runs once
+        let (sender, receiver) = std::sync::mpsc::channel();
+        for _ in 0..2 {
+            let sender = sender.clone();
+            monitor.refresh(move || sender.send(()).unwrap());
+        }
+        receiver.recv_timeout(Duration::from_secs(5)).unwrap();
+        assert_eq!(monitor.processes().unwrap().unwrap().len(), table().len());
+
+        // A recent sample is reused
+        let sender = sender.clone();
+        monitor.refresh(move || sender.send(()).unwrap());
+        assert!(receiver.recv_timeout(Duration::from_millis(300)).is_err());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_linux_proc_parsing() {
+        let stat = "4242 (tmux: server (1)) S 1 4242 4242 0 -1 4194560 500 0 0 0 150 50 0 0 20 0 1 0 12345";
+        assert_eq!(
+            parse_proc_stat(stat),
+            Some((
+                "tmux: server (1)".to_string(),
+                1,
+                Duration::from_secs(2),
+                Some(Duration::from_millis(123_450))
+            ))
+        );
+        assert_eq!(parse_vm_rss("Name:\tbash\nVmRSS:\t    4096 kB\n"), Some(4 * 1024 * 1024));
+
+        let own = SystemProcesses.processes().unwrap();
+        assert!(own.iter().any(|p| p.pid == std::process::id()));
+    }
+}
@@ -0,0 +1,483 @@
+//! Programmer calculator module
+//! Exact integer arithmetic with hex/oct/bin literals, bitwise operators,
+//! fixed-width integer types and base-conversion output
+
+/// Fixed-width integer type used to wrap and display a result
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IntType {
+    I8,
+    I16,
+    I32,
+    I64,
+    I128,
+    U8,
+    U16,
+    U32,
+    U64,
+    U128,
+}
+
+impl IntType {
+    fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "i8" => Some(IntType::I8),
+            "i16" => Some(IntType::I16),
+            "i32" => Some(IntType::I32),
+            "i64" => Some(IntType::I64),
+            "i128" => Some(IntType::I128),
+            "u8" => Some(IntType::U8),
+            "u16" => Some(IntType::U16),
+            "u32" => Some(IntType::U32),
+            "u64" => Some(IntType::U64),
+            "u128" => Some(IntType::U128),
+            _ => None,
+        }
+    }
+
+    pub fn bits(&self) -> u32 {
+        match self {
+            IntType::I8 | IntType::U8 => 8,
+            IntType::I16 | IntType::U16 => 16,
+            IntType::I32 | IntType::U32 => 32,
+            IntType::I64 | IntType::U64 => 64,
+            IntType::I128 | IntType::U128 => 128,
+        }
+    }
+
+    pub fn is_signed(&self) -> bool {
+        matches!(self, IntType::I8 | IntType::I16 | IntType::I32 | IntType::I64 | IntType::I128)
+    }
+
+    fn mask(&self) -> u128 {
+        if self.bits() == 128 {
+            u128::MAX
+        } else {
+            (1u128 << self.bits()) - 1
+        }
+    }
+}
+
+impl std::fmt::Display for IntType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let prefix = if self.is_signed() { "i" } else { "u" };
+        write!(f, "{}{}", prefix, self.bits())
+    }
+}
+
+/// Output base for the main result
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Base {
+    Dec,
+    Hex,
+    Oct,
+    Bin,
+}
+
+impl Base {
+    fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "dec" | "decimal" => Some(Base::Dec),
+            "hex" | "hexadecimal" => Some(Base::Hex),
+            "oct" | "octal" => Some(Base::Oct),
+            "bin" | "binary" => Some(Base::Bin),
+            _ => None,
+        }
+    }
+}
+
+/// An integer result: raw bits interpreted through an integer type
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IntValue {
+    pub bits: u128,
+    pub ty: IntType,
+    pub base: Base,
+}
+
+impl IntValue {
+    /// Wrap an exact value into a type (two's complement truncation)
+    fn wrap(value: i128, ty: IntType, base: Base) -> Self {
+        Self { bits: (value as u128) & ty.mask(), ty, base }
+    }
+
+    /// Decimal representation, sign-extended for signed types
+    pub fn decimal(&self) -> String {
+        if self.ty.is_signed() {
+            let shift = 128 - self.ty.bits();
+            (((self.bits << shift) as i128) >> shift).to_string()
+        } else {
+            self.bits.to_string()
+        }
+    }
+
+    pub fn hex(&self) -> String {
+        format!("0x{:X}", self.bits)
+    }
+
+    pub fn octal(&self) -> String {
+        format!("0o{:o}", self.bits)
+    }
+
+    /// Binary representation grouped in nibbles, e.g. "0b1_1111_1111"
+    pub fn binary(&self) -> String {
+        let digits = format!("{:b}", self.bits);
+        let mut grouped = String::with_capacity(digits.len() * 5 / 4);
+        for (i, digit) in digits.chars().enumerate() {
+            if i > 0 && (digits.len() - i) % 4 == 0 {
+                grouped.push('_');
+            }
+            grouped.push(digit);
+        }
+        format!("0b{}", grouped)
+    }
+
+    /// Main result in the requested base
+    pub fn formatted(&self) -> String {
+        match self.base {
+            Base::Dec => self.decimal(),
+            Base::Hex => self.hex(),
+            Base::Oct => self.octal(),
+            Base::Bin => self.binary(),
+        }
+    }
+
+    /// All representations for the result description
+    pub fn representations(&self) -> String {
+        format!(
+            "{} · dec {} · hex {} · oct {} · bin {}",
+            self.ty,
+            self.decimal(),
+            self.hex(),
+            self.octal(),
+            self.binary()
+        )
+    }
+}
+
+/// Whether a query should be handled by the programmer calculator rather than `meval`:
+/// it uses a prefixed literal, a bitwise operator or an explicit base/type suffix
+pub fn is_programmer_query(query: &str) -> bool {
+    let lower = query.to_lowercase();
+    let has_prefixed_literal = lower
+        .split(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+        .any(|word| {
+            ["0x", "0b", "0o"]
+                .iter()
+                .any(|p| word.len() > 2 && word.starts_with(p))
+        });
+    let has_bitwise = ["<<", ">>", "&", "|", "~"].iter().any(|op| lower.contains(op));
+    has_prefixed_literal || has_bitwise || parse_suffix(query).1.is_some()
+}
+
+/// Evaluate a programmer query such as "0xFF | 0x100", "1 << 20" or "255 in hex"
+pub fn evaluate(query: &str) -> Option<IntValue> {
+    if !is_programmer_query(query) {
+        return None;
+    }
+
+    let (expression, suffix) = parse_suffix(query);
+    let tokens = tokenize(expression)?;
+    let mut parser = Parser { tokens: &tokens, position: 0 };
+    let value = parser.parse_or()?;
+    if parser.position != tokens.len() {
+        return None;
+    }
+
+    // Default to the base of the first literal and the narrowest of i64/i128, or u128
+    // when a literal only fits unsigned
+    let literal_base = tokens.iter().find_map(|t| match t {
+        Token::Number(_, base, _) => Some(*base),
+        _ => None,
+    })?;
+    let default_type = if tokens.iter().any(|t| matches!(t, Token::Number(_, _, true))) {
+        IntType::U128
+    } else if i64::try_from(value).is_ok() {
+        IntType::I64
+    } else {
+        IntType::I128
+    };
+
+    let (ty, base) = match suffix {
+        Some(Suffix::Base(base)) => (default_type, base),
+        Some(Suffix::Type(ty)) => (ty, literal_base),
+        None => (default_type, literal_base),
+    };
+
+    Some(IntValue::wrap(value, ty, base))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Suffix {
+    Base(Base),
+    Type(IntType),
+}
+
+/// Split "expr in hex" / "expr as u32" into the expression and its suffix
+fn parse_suffix(query: &str) -> (&str, Option<Suffix>) {
+    let trimmed = query.trim();
+    if let Some((expression, last)) = trimmed.rsplit_once(char::is_whitespace) {
+        if let Some((expression, keyword)) = expression.trim_end().rsplit_once(char::is_whitespace) {
+            let keyword = keyword.to_lowercase();
+            if keyword == "in" || keyword == "to" || keyword == "as" {
+                if let Some(base) = Base::from_name(last) {
+                    return (expression, Some(Suffix::Base(base)));
+                }
+                if let Some(ty) = IntType::from_name(last) {
+                    return (expression, Some(Suffix::Type(ty)));
+                }
+            }
+        }
+    }
+    (trimmed, None)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    /// Value, base, and whether the literal is above `i128::MAX` (kept as two's complement)
+    Number(i128, Base, bool),
+    Op(&'static str),
+    Open,
+    Close,
+}
+
+const OPERATORS: &[&str] = &["<<", ">>", "|", "^", "&", "+", "-", "*", "/", "%", "~"];
+
+fn tokenize(expression: &str) -> Option<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut rest = expression.trim_start();
+
+    while !rest.is_empty() {
+        if let Some(after) = rest.strip_prefix('(') {
+            tokens.push(Token::Open);
+            rest = after;
+        } else if let Some(after) = rest.strip_prefix(')') {
+            tokens.push(Token::Close);
+            rest = after;
+        } else if let Some(op) = OPERATORS.iter().find(|op| rest.starts_with(**op)) {
+            tokens.push(Token::Op(op));
+            rest = &rest[op.len()..];
+        } else if rest.starts_with(|c: char| c.is_ascii_digit()) {
+            let len = rest
+                .find(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+                .unwrap_or(rest.len());
+            tokens.push(parse_literal(&rest[..len])?);
+            rest = &rest[len..];
+        } else {
+            // Word operators: "and", "or", "xor", "not"
+            let len = rest.find(|c: char| !c.is_ascii_alphabetic()).unwrap_or(rest.len());
+            let op = match rest[..len].to_lowercase().as_str() {
+                "and" => "&",
+                "or" => "|",
+                "xor" => "^",
+                "not" => "~",
+                _ => return None,
+            };
+            tokens.push(Token::Op(op));
+            rest = &rest[len..];
+        }
+        rest = rest.trim_start();
+    }
+
+    Some(tokens)
+}
+
+/// Parse "0xFF", "0b1010", "0o17" or "1_000_000"
+fn parse_literal(text: &str) -> Option<Token> {
+    let clean = text.replace('_', "");
+    let lower = clean.to_lowercase();
+    let (digits, radix, base) = if let Some(d) = lower.strip_prefix("0x") {
+        (d, 16, Base::Hex)
+    } else if let Some(d) = lower.strip_prefix("0b") {
+        (d, 2, Base::Bin)
+    } else if let Some(d) = lower.strip_prefix("0o") {
+        (d, 8, Base::Oct)
+    } else {
+        (lower.as_str(), 10, Base::Dec)
+    };
+
+    if digits.is_empty() {
+        return None;
+    }
+    // Accept the full unsigned 128-bit range for literals and reinterpret as two's complement
+    let value = u128::from_str_radix(digits, radix).ok()?;
+    Some(Token::Number(value as i128, base, value > i128::MAX as u128))
+}
+
+/// Recursive descent parser with C operator precedence:
+/// `|` < `^` < `&` < shifts < additive < multiplicative < unary
+struct Parser<'a> {
+    tokens: &'a [Token],
+    position: usize,
+}
+
+impl Parser<'_> {
+    fn peek_op(&self, ops: &[&str]) -> Option<&'static str> {
+        match self.tokens.get(self.position) {
+            Some(Token::Op(op)) if ops.contains(op) => Some(*op),
+            _ => None,
+        }
+    }
+
+    fn binary(
+        &mut self,
+        ops: &[&str],
+        next: fn(&mut Self) -> Option<i128>,
+    ) -> Option<i128> {
+        let mut left = next(self)?;
+        while let Some(op) = self.peek_op(ops) {
+            self.position += 1;
+            let right = next(self)?;
+            left = apply(op, left, right)?;
+        }
+        Some(left)
+    }
+
+    fn parse_or(&mut self) -> Option<i128> {
+        self.binary(&["|"], Self::parse_xor)
+    }
+
+    fn parse_xor(&mut self) -> Option<i128> {
+        self.binary(&["^"], Self::parse_and)
+    }
+
+    fn parse_and(&mut self) -> Option<i128> {
+        self.binary(&["&"], Self::parse_shift)
+    }
+
+    fn parse_shift(&mut self) -> Option<i128> {
+        self.binary(&["<<", ">>"], Self::parse_additive)
+    }
+
+    fn parse_additive(&mut self) -> Option<i128> {
+        self.binary(&["+", "-"], Self::parse_multiplicative)
+    }
+
+    fn parse_multiplicative(&mut self) -> Option<i128> {
+        self.binary(&["*", "/", "%"], Self::parse_unary)
+    }
+
+    fn parse_unary(&mut self) -> Option<i128> {
+        if let Some(op) = self.peek_op(&["-", "~", "+"]) {
+            self.position += 1;
+            let value = self.parse_unary()?;
+            return match op {
+                "-" => value.checked_neg(),
+                "~" => Some(!value),
+                _ => Some(value),
+            };
+        }
+        self.parse_primary()
+    }
+
+    fn parse_primary(&mut self) -> Option<i128> {
+        match self.tokens.get(self.position)? {
+            Token::Number(value, _, _) => {
+                self.position += 1;
+                Some(*value)
+            }
+            Token::Open => {
+                self.position += 1;
+                let value = self.parse_or()?;
+                if self.tokens.get(self.position) != Some(&Token::Close) {
+                    return None;
+                }
+                self.position += 1;
+                Some(value)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Apply a binary operator with overflow and division-by-zero checks
+fn apply(op: &str, left: i128, right: i128) -> Option<i128> {
+    match op {
+        "|" => Some(left | right),
+        "^" => Some(left ^ right),
+        "&" => Some(left & right),
+        "<<" => left.checked_shl(u32::try_from(right).ok()?),
+        ">>" => left.checked_shr(u32::try_from(right).ok()?),
+        "+" => left.checked_add(right),
+        "-" => left.checked_sub(right),
+        "*" => left.checked_mul(right),
+        "/" => left.checked_div(right),
+        "%" => left.checked_rem(right),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn eval(query: &str) -> String {
+        evaluate(query)
+            .unwrap_or_else(|| panic!("query should evaluate: {}", query))
+            .formatted()
+    }
+
+    #[test]
+    fn test_literals_and_bitwise_ops() {
+        assert_eq!(eval("0xFF | 0x100"), "0x1FF");
+        assert_eq!(eval("0b1010"), "0b1010");
+        assert_eq!(eval("0o17 + 1"), "0o20");
+        assert_eq!(eval("1 << 20"), "1048576");
+        assert_eq!(eval("0xF0 & 0x3C"), "0x30");
+        assert_eq!(eval("0xFF ^ 0x0F"), "0xF0");
+        assert_eq!(eval("6 xor 3 in dec"), "5");
+        assert_eq!(eval("1_000_000 >> 3"), "125000");
+    }
+
+    #[test]
+    fn test_precedence() {
+        // Shifts bind looser than addition, as in C
+        assert_eq!(eval("1 << 2 + 1"), "8");
+        assert_eq!(eval("0x1 | 0x2 & 0x3"), "0x3");
+        assert_eq!(eval("(0x1 | 0x2) & 0x2"), "0x2");
+        assert_eq!(eval("2 * 3 + 4 << 1"), "20");
+    }
+
+    #[test]
+    fn test_base_conversion() {
+        assert_eq!(eval("255 in hex"), "0xFF");
+        assert_eq!(eval("255 in bin"), "0b1111_1111");
+        assert_eq!(eval("255 to oct"), "0o377");
+        assert_eq!(eval("0xFF in dec"), "255");
+
+        let value = evaluate("511 in hex").unwrap();
+        assert_eq!(value.representations(), "i64 · dec 511 · hex 0x1FF · oct 0o777 · bin 0b1_1111_1111");
+    }
+
+    #[test]
+    fn test_integer_types() {
+        assert_eq!(eval("-1 as u32"), "4294967295");
+        assert_eq!(eval("0xFF as i8"), "0xFF");
+        assert_eq!(evaluate("0xFF as i8").unwrap().decimal(), "-1");
+        assert_eq!(eval("300 as u8"), "44");
+        assert_eq!(eval("~0 in hex"), "0xFFFFFFFFFFFFFFFF");
+        assert_eq!(eval("~0 as u16"), "65535");
+    }
+
+    #[test]
+    fn test_exact_128_bit() {
+        assert_eq!(eval("1 << 100"), "1267650600228229401496703205376");
+        assert_eq!(evaluate("1 << 100").unwrap().ty, IntType::I128);
+        assert_eq!(eval("0xFFFFFFFFFFFFFFFF + 1 in hex"), "0x10000000000000000");
+        assert_eq!(eval("9007199254740993 | 0"), "9007199254740993");
+
+        // Full-width unsigned literals are not read back as negative i128
+        let max = evaluate("0xFFFF_FFFF_FFFF_FFFF_FFFF_FFFF_FFFF_FFFF").unwrap();
+        assert_eq!(max.ty, IntType::U128);
+        assert_eq!(max.decimal(), u128::MAX.to_string());
+    }
+
+    #[test]
+    fn test_rejects_invalid() {
+        assert!(evaluate("2+2").is_none()); // plain arithmetic is left to meval
+        assert!(evaluate("1 / 0 | 0").is_none());
+        assert!(evaluate("1 << 200").is_none());
+        assert!(evaluate("0xZZ").is_none());
+        assert!(evaluate("(1 | 2").is_none());
+        assert!(evaluate("1.5 | 2").is_none());
+        assert!(evaluate("hello | world").is_none());
+    }
+}
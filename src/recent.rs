@@ -0,0 +1,355 @@
+//! Recent documents module
+//! Recently opened documents from the Windows Recent folder (shortcuts updated by
+//! Explorer on every open) or, on Linux, `recently-used.xbel`. Listed newest first
+//! under the `recent` keyword and on the empty query.
+
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use crate::{AppEntry, AppType, SearchResultData};
+
+/// Keyword that lists recent documents
+const KEYWORD: &str = "recent";
+
+/// Most documents kept in the index
+const MAX_DOCUMENTS: usize = 200;
+
+/// A recently opened file and when it was last used (Unix timestamp in seconds)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecentItem {
+    pub path: PathBuf,
+    pub used: i64,
+}
+
+/// Search terms of "recent" or "recent budget"
+pub fn parse_query(query: &str) -> Option<&str> {
+    let query = query.trim_start();
+    let (keyword, rest) = query.split_once(char::is_whitespace).unwrap_or((query, ""));
+    keyword.eq_ignore_ascii_case(KEYWORD).then(|| rest.trim())
+}
+
+/// Where recent documents are recorded on this platform
+pub fn source_path() -> Option<PathBuf> {
+    if cfg!(windows) {
+        dirs::data_dir().map(|dir| dir.join("Microsoft").join("Windows").join("Recent"))
+    } else {
+        dirs::data_dir().map(|dir| dir.join("recently-used.xbel"))
+    }
+}
+
+/// Shortcuts in the Recent folder, resolved to the files they point to. Folders and
+/// files that no longer exist are left out.
+pub fn read_recent_folder(dir: &Path) -> Vec<RecentItem> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("lnk")))
+        .filter_map(|link| {
+            let used = fs::metadata(&link).and_then(|m| m.modified()).ok()?;
+            let path = match resolve_shortcut(&link) {
+                Some(target) if target.is_file() => target,
+                Some(_) => return None,
+                // Opening the shortcut itself still opens the document
+                None => link,
+            };
+            Some(RecentItem {
+                path,
+                used: unix_seconds(used),
+            })
+        })
+        .collect()
+}
+
+/// Target of a `.lnk` shortcut
+#[cfg(windows)]
+fn resolve_shortcut(link: &Path) -> Option<PathBuf> {
+    use std::os::windows::ffi::OsStrExt;
+    use windows::core::{Interface, PCWSTR};
+    use windows::Win32::System::Com::{
+        CoCreateInstance, CoInitializeEx, IPersistFile, CLSCTX_INPROC_SERVER, COINIT_APARTMENTTHREADED, STGM_READ,
+    };
+    use windows::Win32::UI::Shell::{IShellLinkW, ShellLink};
+
+    let wide: Vec<u16> = link.as_os_str().encode_wide().chain(std::iter::once(0)).collect();
+    unsafe {
+        // Already initialized on this thread is fine
+        let _ = CoInitializeEx(None, COINIT_APARTMENTTHREADED);
+        let shell_link: IShellLinkW = CoCreateInstance(&ShellLink, None, CLSCTX_INPROC_SERVER).ok()?;
+        shell_link.cast::<IPersistFile>().ok()?.Load(PCWSTR(wide.as_ptr()), STGM_READ).ok()?;
+
+        let mut buffer = [0u16; 260];
+        shell_link.GetPath(&mut buffer, std::ptr::null_mut(), 0).ok()?;
+        let len = buffer.iter().position(|&c| c == 0).unwrap_or(buffer.len());
+        (len > 0).then(|| PathBuf::from(String::from_utf16_lossy(&buffer[..len])))
+    }
+}
+
+#[cfg(not(windows))]
+fn resolve_shortcut(_link: &Path) -> Option<PathBuf> {
+    None
+}
+
+/// Files in a freedesktop `recently-used.xbel`, with the later of their modified and
+/// visited times. Entries that aren't local files are skipped.
+pub fn parse_xbel(xml: &str) -> Vec<RecentItem> {
+    xml.split("<bookmark ")
+        .skip(1)
+        .filter_map(|element| {
+            // Keep the space before the first attribute, so every one is found the same way
+            let tag = format!(" {}", &element[..element.find('>')?]);
+            let path = file_url_to_path(&xml_unescape(attribute(&tag, "href")?))?;
+            let used = ["modified", "visited", "added"]
+                .iter()
+                .filter_map(|name| attribute(&tag, name))
+                .filter_map(|time| chrono::DateTime::parse_from_rfc3339(time).ok())
+                .map(|time| time.timestamp())
+                .max()
+                .unwrap_or(0);
+            Some(RecentItem { path, used })
+        })
+        .collect()
+}
+
+/// Value of `name="value"` in a start tag
+pub fn attribute<'a>(tag: &'a str, name: &str) -> Option<&'a str> {
+    let start = tag.find(&format!(" {}=\"", name))? + name.len() + 3;
+    let len = tag[start..].find('"')?;
+    Some(&tag[start..start + len])
+}
+
+pub fn xml_unescape(text: &str) -> String {
+    text.replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&amp;", "&")
+}
+
+/// Path of a percent-encoded `file://` URL; "file:///c%3A/dev" is "c:/dev"
+pub fn file_url_to_path(url: &str) -> Option<PathBuf> {
+    let encoded = url.strip_prefix("file://")?.as_bytes();
+    let mut bytes = Vec::with_capacity(encoded.len());
+    let mut i = 0;
+    while i < encoded.len() {
+        let decoded = (encoded[i] == b'%')
+            .then(|| std::str::from_utf8(encoded.get(i + 1..i + 3)?).ok())
+            .flatten()
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match decoded {
+            Some(byte) => {
+                bytes.push(byte);
+                i += 3;
+            }
+            None => {
+                bytes.push(encoded[i]);
+                i += 1;
+            }
+        }
+    }
+    let path = String::from_utf8(bytes).ok()?;
+    let drive_path =
+        path.starts_with('/') && path.get(2..3) == Some(":") && path.as_bytes()[1].is_ascii_alphabetic();
+    Some(PathBuf::from(if drive_path { &path[1..] } else { &path }))
+}
+
+/// Catalog entries for recent items, newest first and once per file
+pub fn to_entries(mut items: Vec<RecentItem>) -> Vec<AppEntry> {
+    items.sort_by_key(|item| std::cmp::Reverse(item.used));
+    let mut seen = HashSet::new();
+    items
+        .into_iter()
+        .filter(|item| seen.insert(item.path.clone()))
+        .take(MAX_DOCUMENTS)
+        .filter_map(|item| {
+            let file_name = item.path.file_name()?.to_string_lossy();
+            // Unresolved shortcuts are named after their document ("report.docx.lnk")
+            let name = file_name.strip_suffix(".lnk").unwrap_or(&file_name).to_string();
+            let description = item
+                .path
+                .parent()
+                .map_or_else(String::new, |dir| dir.display().to_string());
+            Some(AppEntry {
+                name,
+                path: item.path,
+                description,
+                app_type: AppType::File,
+            })
+        })
+        .collect()
+}
+
+/// Results for a `recent` query (or the empty query when `terms` is empty)
+pub fn search_results(documents: &[AppEntry], terms: &str, max_results: usize) -> Vec<SearchResultData> {
+    let terms = terms.to_lowercase();
+    documents
+        .iter()
+        .filter(|document| document.name.to_lowercase().contains(&terms))
+        .take(max_results)
+        .map(|document| SearchResultData {
+            name: document.name.clone(),
+            description: document.description.clone(),
+            path: document.path.clone(),
+            result_type: "file".to_string(),
+            ..Default::default()
+        })
+        .collect()
+}
+
+fn unix_seconds(time: SystemTime) -> i64 {
+    time.duration_since(SystemTime::UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs() as i64)
+}
+
+/// Entry count and newest modification time of the Recent folder's entries, or the
+/// modification time of the xbel file. Reopening a document rewrites its existing
+/// shortcut, which leaves the folder's own modification time unchanged on NTFS.
+fn source_stamp(source: &Path) -> (usize, Option<SystemTime>) {
+    match fs::read_dir(source) {
+        Ok(entries) => {
+            let times: Vec<SystemTime> = entries
+                .flatten()
+                .filter_map(|entry| entry.metadata().and_then(|m| m.modified()).ok())
+                .collect();
+            (times.len(), times.into_iter().max())
+        }
+        Err(_) => (0, fs::metadata(source).and_then(|m| m.modified()).ok()),
+    }
+}
+
+/// Recent documents, re-read when the Recent folder or the xbel file changes
+#[derive(Debug)]
+pub struct RecentDocuments {
+    source: PathBuf,
+    stamp: Option<(usize, Option<SystemTime>)>,
+}
+
+impl RecentDocuments {
+    pub fn new(source: PathBuf) -> Self {
+        Self { source, stamp: None }
+    }
+
+    /// All recent documents when the source changed since the last call (always on the first call)
+    pub fn refresh_if_changed(&mut self) -> Option<Vec<AppEntry>> {
+        let stamp = source_stamp(&self.source);
+        if self.stamp == Some(stamp) {
+            return None;
+        }
+        self.stamp = Some(stamp);
+
+        let items = if self.source.is_dir() {
+            read_recent_folder(&self.source)
+        } else {
+            // The xbel file also lists files that were deleted since
+            fs::read_to_string(&self.source)
+                .map(|xml| parse_xbel(&xml))
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|item| item.path.is_file())
+                .collect()
+        };
+        Some(to_entries(items))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    const XBEL: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<xbel version="1.0" xmlns:bookmark="http://www.freedesktop.org/standards/desktop-bookmarks">
+  <bookmark href="file:///home/sam/Documents/Q3%20budget.ods" added="2026-10-01T08:00:00Z" modified="2026-10-17T16:20:00.123456Z" visited="2026-10-01T08:00:00Z">
+    <info><metadata owner="http://freedesktop.org"><mime:mime-type type="application/vnd.oasis.opendocument.spreadsheet"/></metadata></info>
+  </bookmark>
+  <bookmark href="file:///home/sam/notes/todo&amp;ideas.md" added="2026-10-18T09:00:00Z" modified="2026-10-18T09:00:00Z" visited="2026-10-18T09:30:00Z"/>
+  <bookmark href="https://example.com/report.pdf" added="2026-10-18T10:00:00Z" modified="2026-10-18T10:00:00Z" visited="2026-10-18T10:00:00Z"/>
+</xbel>"#;
+
+    fn item(path: &str, used: i64) -> RecentItem {
+        RecentItem {
+            path: PathBuf::from(path),
+            used,
+        }
+    }
+
+    #[test]
+    fn test_parse_query() {
+        assert_eq!(parse_query("recent"), Some(""));
+        assert_eq!(parse_query("Recent budget "), Some("budget"));
+        assert_eq!(parse_query("recently"), None);
+    }
+
+    #[test]
+    fn test_parse_xbel() {
+        let items = parse_xbel(XBEL);
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].path, PathBuf::from("/home/sam/Documents/Q3 budget.ods"));
+        assert_eq!(items[1].path, PathBuf::from("/home/sam/notes/todo&ideas.md"));
+
+        // The latest of the recorded times counts
+        assert!(items[1].used > items[0].used);
+        assert_eq!(
+            items[1].used,
+            chrono::DateTime::parse_from_rfc3339("2026-10-18T09:30:00Z").unwrap().timestamp()
+        );
+    }
+
+    #[test]
+    fn test_file_url_to_path() {
+        assert_eq!(file_url_to_path("file:///home/sam/a%20b"), Some(PathBuf::from("/home/sam/a b")));
+        assert_eq!(file_url_to_path("file:///c%3A/dev/nexus"), Some(PathBuf::from("c:/dev/nexus")));
+        assert_eq!(file_url_to_path("vscode-remote://ssh-remote+box/srv"), None);
+    }
+
+    #[test]
+    fn test_entries_by_recency() {
+        let entries = to_entries(vec![
+            item("/docs/old.txt", 100),
+            item("/docs/new.txt", 300),
+            item("/docs/old.txt", 200),
+            item("/recent/report.docx.lnk", 150),
+        ]);
+        let names: Vec<&str> = entries.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, ["new.txt", "old.txt", "report.docx"]);
+        assert_eq!(entries[0].app_type, AppType::File);
+        assert_eq!(entries[0].description, "/docs");
+
+        let results = search_results(&entries, "OLD", 10);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].result_type, "file");
+        assert_eq!(search_results(&entries, "", 2).len(), 2);
+    }
+
+    #[test]
+    fn test_recent_folder() {
+        let dir = std::env::temp_dir().join(format!("nexus_recent_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let now = SystemTime::now();
+        let touch = |name: &str, time: SystemTime| {
+            let path = dir.join(name);
+            fs::write(&path, b"").unwrap();
+            fs::File::options().write(true).open(&path).unwrap().set_modified(time).unwrap();
+        };
+        for (name, age) in [("older.xlsx.lnk", 60), ("newer.pdf.lnk", 0), ("desktop.ini", 0)] {
+            touch(name, now - Duration::from_secs(age));
+        }
+
+        let mut documents = RecentDocuments::new(dir.clone());
+        let entries = documents.refresh_if_changed().unwrap();
+        let names: Vec<&str> = entries.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, ["newer.pdf", "older.xlsx"]);
+        assert!(documents.refresh_if_changed().is_none());
+
+        // Reopening a document rewrites its shortcut without adding a new one
+        touch("older.xlsx.lnk", now + Duration::from_secs(60));
+        let entries = documents.refresh_if_changed().unwrap();
+        assert_eq!(entries[0].name, "older.xlsx");
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
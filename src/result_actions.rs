@@ -0,0 +1,222 @@
+//! Result actions module
+//! Secondary actions offered per result type, opened with Tab or Shift+Enter
+
+use std::error::Error;
+use std::path::Path;
+use std::process::Command;
+
+use crate::SearchResultData;
+
+/// A secondary action on a result
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResultAction {
+    RunAsAdmin,
+    OpenContainingFolder,
+    CopyPath,
+    Pin,
+    Unpin,
+    OpenWith,
+    CopyUrl,
+    CopyOutput,
+    StopCommand,
+    MarkDone,
+    DeleteTodo,
+    CancelTimer,
+    EndTask,
+    EndProcessTree,
+}
+
+const ALL_ACTIONS: &[ResultAction] = &[
+    ResultAction::RunAsAdmin,
+    ResultAction::OpenContainingFolder,
+    ResultAction::CopyPath,
+    ResultAction::Pin,
+    ResultAction::Unpin,
+    ResultAction::OpenWith,
+    ResultAction::CopyUrl,
+    ResultAction::CopyOutput,
+    ResultAction::StopCommand,
+    ResultAction::MarkDone,
+    ResultAction::DeleteTodo,
+    ResultAction::CancelTimer,
+    ResultAction::EndTask,
+    ResultAction::EndProcessTree,
+];
+
+impl ResultAction {
+    /// Stable identifier passed through the UI
+    pub fn id(self) -> &'static str {
+        match self {
+            ResultAction::RunAsAdmin => "run-as-admin",
+            ResultAction::OpenContainingFolder => "open-folder",
+            ResultAction::CopyPath => "copy-path",
+            ResultAction::Pin => "pin",
+            ResultAction::Unpin => "unpin",
+            ResultAction::OpenWith => "open-with",
+            ResultAction::CopyUrl => "copy-url",
+            ResultAction::CopyOutput => "copy-output",
+            ResultAction::StopCommand => "stop-command",
+            ResultAction::MarkDone => "mark-done",
+            ResultAction::DeleteTodo => "delete-todo",
+            ResultAction::CancelTimer => "cancel-timer",
+            ResultAction::EndTask => "end-task",
+            ResultAction::EndProcessTree => "end-process-tree",
+        }
+    }
+
+    /// Label shown in the actions menu
+    pub fn label(self) -> &'static str {
+        match self {
+            ResultAction::RunAsAdmin => "Run as administrator",
+            ResultAction::OpenContainingFolder => "Open containing folder",
+            ResultAction::CopyPath => "Copy path",
+            ResultAction::Pin => "Pin",
+            ResultAction::Unpin => "Unpin",
+            ResultAction::OpenWith => "Open with…",
+            ResultAction::CopyUrl => "Copy URL",
+            ResultAction::CopyOutput => "Copy output",
+            ResultAction::StopCommand => "Stop command",
+            ResultAction::MarkDone => "Mark done",
+            ResultAction::DeleteTodo => "Delete",
+            ResultAction::CancelTimer => "Cancel timer",
+            ResultAction::EndTask => "End task",
+            ResultAction::EndProcessTree => "End process tree",
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<Self> {
+        ALL_ACTIONS.iter().copied().find(|action| action.id() == id)
+    }
+}
+
+/// Secondary actions available for a result
+pub fn actions_for(result: &SearchResultData, pinned: bool) -> Vec<ResultAction> {
+    match result.result_type.as_str() {
+        "app" => {
+            let mut actions = vec![ResultAction::RunAsAdmin];
+            // Packaged (UWP) apps have no folder on disk to show
+            if result.path.is_absolute() {
+                actions.push(ResultAction::OpenContainingFolder);
+            }
+            actions.push(ResultAction::CopyPath);
+            actions.push(if pinned { ResultAction::Unpin } else { ResultAction::Pin });
+            actions
+        }
+        "file" => vec![ResultAction::OpenWith, ResultAction::CopyPath],
+        "workspace" => vec![ResultAction::OpenContainingFolder, ResultAction::CopyPath],
+        "web" | "bookmark" => vec![ResultAction::CopyUrl],
+        "shell-output" => vec![ResultAction::CopyOutput],
+        "shell-running" => vec![ResultAction::StopCommand],
+        "todo" => vec![ResultAction::MarkDone, ResultAction::DeleteTodo],
+        "timer" => vec![ResultAction::CancelTimer],
+        "process" => vec![ResultAction::EndTask, ResultAction::EndProcessTree],
+        _ => Vec::new(),
+    }
+}
+
+/// Run an action that hands the path to the Windows shell
+pub fn run_shell_action(action: ResultAction, path: &Path) -> Result<(), Box<dyn Error>> {
+    match action {
+        ResultAction::RunAsAdmin => run_as_admin(path),
+        ResultAction::OpenContainingFolder => {
+            // explorer.exe expects "/select," and the path as one argument
+            let mut select = std::ffi::OsString::from("/select,");
+            select.push(path);
+            Command::new("explorer.exe").arg(select).spawn()?;
+            Ok(())
+        }
+        ResultAction::OpenWith => {
+            Command::new("rundll32.exe")
+                .arg("shell32.dll,OpenAs_RunDLL")
+                .arg(path)
+                .spawn()?;
+            Ok(())
+        }
+        _ => Err(format!("Not a shell action: {}", action.id()).into()),
+    }
+}
+
+/// Launch with the "runas" verb, which shows the UAC elevation prompt
+#[cfg(windows)]
+fn run_as_admin(path: &Path) -> Result<(), Box<dyn Error>> {
+    use std::os::windows::ffi::OsStrExt;
+    use windows::core::{w, PCWSTR};
+    use windows::Win32::Foundation::HWND;
+    use windows::Win32::UI::Shell::ShellExecuteW;
+    use windows::Win32::UI::WindowsAndMessaging::SW_SHOWNORMAL;
+
+    let wide: Vec<u16> = path.as_os_str().encode_wide().chain(std::iter::once(0)).collect();
+    let result = unsafe {
+        ShellExecuteW(
+            HWND::default(),
+            w!("runas"),
+            PCWSTR(wide.as_ptr()),
+            PCWSTR::null(),
+            PCWSTR::null(),
+            SW_SHOWNORMAL,
+        )
+    };
+
+    // ShellExecuteW reports success with a value greater than 32
+    if result.0 as usize > 32 {
+        Ok(())
+    } else {
+        Err(format!("ShellExecuteW failed with code {}", result.0 as usize).into())
+    }
+}
+
+#[cfg(not(windows))]
+fn run_as_admin(_path: &Path) -> Result<(), Box<dyn Error>> {
+    Err("Running as administrator is only supported on Windows".into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn result(result_type: &str, path: &str) -> SearchResultData {
+        SearchResultData {
+            name: "Result".to_string(),
+            path: PathBuf::from(path),
+            result_type: result_type.to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn labels(actions: Vec<ResultAction>) -> Vec<&'static str> {
+        actions.into_iter().map(ResultAction::label).collect()
+    }
+
+    #[test]
+    fn test_actions_per_result_type() {
+        let app = result("app", if cfg!(windows) { r"C:\Apps\app.exe" } else { "/apps/app" });
+        assert_eq!(
+            labels(actions_for(&app, false)),
+            ["Run as administrator", "Open containing folder", "Copy path", "Pin"]
+        );
+        assert_eq!(actions_for(&app, true).last(), Some(&ResultAction::Unpin));
+
+        let uwp = result("app", r"shell:AppsFolder\Microsoft.WindowsCalculator");
+        assert!(!actions_for(&uwp, false).contains(&ResultAction::OpenContainingFolder));
+
+        assert_eq!(labels(actions_for(&result("file", "notes.txt"), false)), ["Open with…", "Copy path"]);
+        assert_eq!(labels(actions_for(&result("web", "https://example.com"), false)), ["Copy URL"]);
+        assert_eq!(labels(actions_for(&result("bookmark", "https://example.com"), false)), ["Copy URL"]);
+        assert_eq!(
+            labels(actions_for(&result("workspace", "/home/sam/dev/api"), false)),
+            ["Open containing folder", "Copy path"]
+        );
+        assert_eq!(labels(actions_for(&result("shell-running", "ping"), false)), ["Stop command"]);
+        assert_eq!(labels(actions_for(&result("process", "1234"), false)), ["End task", "End process tree"]);
+        assert!(actions_for(&result("calc", "4"), false).is_empty());
+    }
+
+    #[test]
+    fn test_action_ids_round_trip() {
+        for action in ALL_ACTIONS {
+            assert_eq!(ResultAction::from_id(action.id()), Some(*action));
+        }
+        assert_eq!(ResultAction::from_id(""), None);
+    }
+}
@@ -0,0 +1,677 @@
+//! Shell module
+//! Runs `>` commands through the configured shell, either in a new terminal window or
+//! captured, showing the first output lines and the exit code as results
+
+use std::error::Error;
+use std::io::Read;
+use std::path::PathBuf;
+use std::process::{Child, Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+use crate::config::ShellConfig;
+use crate::SearchResultData;
+
+/// Prefix that runs the rest of the query as a shell command
+const PREFIX: char = '>';
+
+/// Captured output beyond this size is dropped
+const MAX_OUTPUT_BYTES: usize = 1 << 20;
+
+/// How often a captured command is checked for exit, timeout or kill
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// How long to wait for output still in the pipes after the command ended
+const DRAIN_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// Shell that runs commands
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShellKind {
+    Cmd,
+    PowerShell,
+    Pwsh,
+    Bash,
+}
+
+impl ShellKind {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "cmd" | "cmd.exe" => Some(ShellKind::Cmd),
+            "powershell" | "powershell.exe" => Some(ShellKind::PowerShell),
+            "pwsh" | "pwsh.exe" => Some(ShellKind::Pwsh),
+            "bash" | "bash.exe" => Some(ShellKind::Bash),
+            _ => None,
+        }
+    }
+
+    /// Shell from the settings, falling back to the platform default
+    pub fn from_config(config: &ShellConfig) -> Self {
+        Self::from_name(&config.shell).unwrap_or_else(|| {
+            log::warn!("Unknown shell '{}', using the default", config.shell);
+            if cfg!(windows) {
+                ShellKind::PowerShell
+            } else {
+                ShellKind::Bash
+            }
+        })
+    }
+
+    fn program(self) -> &'static str {
+        match self {
+            ShellKind::Cmd => "cmd",
+            ShellKind::PowerShell => "powershell",
+            ShellKind::Pwsh => "pwsh",
+            ShellKind::Bash => "bash",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ShellKind::Cmd => "Command Prompt",
+            ShellKind::PowerShell => "Windows PowerShell",
+            ShellKind::Pwsh => "PowerShell",
+            ShellKind::Bash => "bash",
+        }
+    }
+
+    /// Arguments that run a command and exit
+    fn run_args(self, command: &str) -> Vec<String> {
+        match self {
+            ShellKind::Cmd => vec!["/C".into(), command.into()],
+            ShellKind::PowerShell | ShellKind::Pwsh => {
+                vec!["-NoProfile".into(), "-Command".into(), command.into()]
+            }
+            ShellKind::Bash => vec!["-c".into(), command.into()],
+        }
+    }
+
+    /// Arguments that run a command and keep the shell open
+    fn terminal_args(self, command: &str) -> Vec<String> {
+        match self {
+            ShellKind::Cmd => vec!["/K".into(), command.into()],
+            ShellKind::PowerShell | ShellKind::Pwsh => vec!["-NoExit".into(), "-Command".into(), command.into()],
+            ShellKind::Bash => vec!["-c".into(), format!("{}; exec bash", command)],
+        }
+    }
+
+    /// The shell with `args`. cmd reads its own command line and doesn't understand the
+    /// `\"` escapes Rust adds to quoted arguments, so its command goes in as typed.
+    fn command(self, args: Vec<String>) -> Command {
+        let mut process = Command::new(self.program());
+        #[cfg(windows)]
+        if let (ShellKind::Cmd, [switch, command]) = (self, args.as_slice()) {
+            use std::os::windows::process::CommandExt;
+            process.arg(switch).raw_arg(command);
+            return process;
+        }
+        process.args(args);
+        process
+    }
+}
+
+/// A command to run, carried in its search result
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShellRun {
+    pub command: String,
+    pub in_terminal: bool,
+
+    /// Already confirmed, so it runs even when the settings require confirmation
+    pub confirmed: bool,
+}
+
+/// Command text of a `>` query ("> git status")
+pub fn parse_query(query: &str) -> Option<&str> {
+    query.trim_start().strip_prefix(PREFIX).map(str::trim)
+}
+
+/// Results for a `>` query: run with the configured mode first, the other mode second
+pub fn preview_results(command: &str, config: &ShellConfig) -> Vec<SearchResultData> {
+    let shell = ShellKind::from_config(config);
+    if command.is_empty() {
+        return vec![SearchResultData {
+            name: format!("Type a command to run in {}", shell.label()),
+            description: "Enter shows the output here; the second result opens a terminal window".to_string(),
+            result_type: "info".to_string(),
+            ..Default::default()
+        }];
+    }
+
+    let run = |in_terminal: bool| SearchResultData {
+        name: command.to_string(),
+        description: if in_terminal {
+            format!("Run in a new {} window", shell.label())
+        } else {
+            format!("Run in {} and show the output", shell.label())
+        },
+        path: PathBuf::from(command),
+        result_type: "shell".to_string(),
+        shell: Some(ShellRun {
+            command: command.to_string(),
+            in_terminal,
+            confirmed: false,
+        }),
+        ..Default::default()
+    };
+    vec![run(config.open_in_terminal), run(!config.open_in_terminal)]
+}
+
+/// Confirmation shown before running when the settings ask for it
+pub fn confirmation_result(run: &ShellRun) -> SearchResultData {
+    SearchResultData {
+        name: format!("Run \"{}\"?", run.command),
+        description: "Press Enter to confirm".to_string(),
+        path: PathBuf::from(&run.command),
+        result_type: "shell".to_string(),
+        shell: Some(ShellRun {
+            confirmed: true,
+            ..run.clone()
+        }),
+        ..Default::default()
+    }
+}
+
+/// Shown while a captured command runs
+pub fn running_result(command: &str) -> SearchResultData {
+    SearchResultData {
+        name: format!("Running \"{}\"…", command),
+        description: "Press Enter to stop the command".to_string(),
+        path: PathBuf::from(command),
+        result_type: "shell-running".to_string(),
+        ..Default::default()
+    }
+}
+
+/// Run a command in a new terminal window that stays open
+pub fn open_in_terminal(config: &ShellConfig, command: &str) -> Result<(), Box<dyn Error>> {
+    let shell = ShellKind::from_config(config);
+
+    // The shell gets its own console window directly; going through `start` would have
+    // an outer cmd parse the command a second time
+    #[cfg(windows)]
+    {
+        use std::os::windows::process::CommandExt;
+        const CREATE_NEW_CONSOLE: u32 = 0x0000_0010;
+        shell
+            .command(shell.terminal_args(command))
+            .creation_flags(CREATE_NEW_CONSOLE)
+            .spawn()?;
+    }
+
+    #[cfg(not(windows))]
+    {
+        Command::new("x-terminal-emulator")
+            .arg("-e")
+            .arg(shell.program())
+            .args(shell.terminal_args(command))
+            .spawn()?;
+    }
+
+    log::info!("Opened terminal for: {}", command);
+    Ok(())
+}
+
+/// How a captured command ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Ending {
+    Exited(Option<i32>),
+    TimedOut,
+    Killed,
+}
+
+/// Output of a captured command
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommandOutput {
+    pub stdout: String,
+    pub stderr: String,
+    pub ending: Ending,
+}
+
+/// Stop requests and the end of a captured command, shared with its runner
+#[derive(Default)]
+struct RunState {
+    cancel: AtomicBool,
+    finished: AtomicBool,
+}
+
+/// A captured command that is still running
+pub struct RunningCommand {
+    command: String,
+    child: Child,
+    tree: ProcessTree,
+    stdout: (Arc<Mutex<Vec<u8>>>, JoinHandle<()>),
+    stderr: (Arc<Mutex<Vec<u8>>>, JoinHandle<()>),
+    state: Arc<RunState>,
+    timeout: Duration,
+}
+
+impl RunningCommand {
+    pub fn command(&self) -> &str {
+        &self.command
+    }
+
+    /// Block until the command exits, times out or is killed
+    pub fn wait(mut self) -> CommandOutput {
+        let started = Instant::now();
+        let ending = loop {
+            match self.child.try_wait() {
+                Ok(Some(status)) => break Ending::Exited(status.code()),
+                Ok(None) => {}
+                Err(e) => {
+                    log::error!("Failed to wait for '{}': {}", self.command, e);
+                    break Ending::Exited(None);
+                }
+            }
+
+            let ending = if self.state.cancel.load(Ordering::Relaxed) {
+                Ending::Killed
+            } else if started.elapsed() >= self.timeout {
+                Ending::TimedOut
+            } else {
+                std::thread::sleep(POLL_INTERVAL);
+                continue;
+            };
+            self.tree.kill(&mut self.child);
+            let _ = self.child.wait();
+            break ending;
+        };
+        self.state.finished.store(true, Ordering::Relaxed);
+
+        // Programs started by the command may keep the pipes open, so don't wait forever
+        let drain_until = Instant::now() + DRAIN_TIMEOUT;
+        while !(self.stdout.1.is_finished() && self.stderr.1.is_finished()) && Instant::now() < drain_until {
+            std::thread::sleep(Duration::from_millis(10));
+        }
+
+        CommandOutput {
+            stdout: take_output(&self.stdout.0),
+            stderr: take_output(&self.stderr.0),
+            ending,
+        }
+    }
+}
+
+/// The shell of a captured command and every program it starts. Killing only the shell
+/// would leave programs such as `ping -t` running.
+struct ProcessTree {
+    #[cfg(windows)]
+    job: Option<JobObject>,
+}
+
+/// Job object holding the shell and the processes it starts
+#[cfg(windows)]
+struct JobObject(windows::Win32::Foundation::HANDLE);
+
+/// The shell starts suspended, so nothing it starts can run before it is in the job
+#[cfg(windows)]
+const CREATE_SUSPENDED: u32 = 0x0000_0004;
+
+// The handle is only used to terminate and close the job, which any thread may do
+#[cfg(windows)]
+unsafe impl Send for JobObject {}
+
+#[cfg(windows)]
+impl Drop for JobObject {
+    fn drop(&mut self) {
+        unsafe {
+            let _ = windows::Win32::Foundation::CloseHandle(self.0);
+        }
+    }
+}
+
+impl ProcessTree {
+    /// Put a shell started with `CREATE_SUSPENDED` into a job object, which its children
+    /// join as well, then let it run. Programs still running when the command ends
+    /// normally (`start notepad`) are left alone.
+    #[cfg(windows)]
+    fn new(child: &Child) -> Result<Self, Box<dyn Error>> {
+        let job = Self::job_for(child)
+            .map_err(|e| log::warn!("No job object for the command, only the shell can be stopped: {}", e))
+            .ok();
+        resume_threads(child.id())?;
+        Ok(Self { job })
+    }
+
+    #[cfg(windows)]
+    fn job_for(child: &Child) -> Result<JobObject, Box<dyn Error>> {
+        use std::os::windows::io::AsRawHandle;
+        use windows::core::PCWSTR;
+        use windows::Win32::Foundation::HANDLE;
+        use windows::Win32::System::JobObjects::{AssignProcessToJobObject, CreateJobObjectW};
+
+        unsafe {
+            let job = JobObject(CreateJobObjectW(None, PCWSTR::null())?);
+            AssignProcessToJobObject(job.0, HANDLE(child.as_raw_handle()))?;
+            Ok(job)
+        }
+    }
+
+    /// The shell leads its own process group (see `ShellRunner::start`), which its
+    /// children join
+    #[cfg(not(windows))]
+    fn new(_child: &Child) -> Result<Self, Box<dyn Error>> {
+        Ok(Self {})
+    }
+
+    /// Stop the shell and everything it started
+    fn kill(&mut self, child: &mut Child) {
+        #[cfg(windows)]
+        {
+            use windows::Win32::System::JobObjects::TerminateJobObject;
+            if let Some(job) = self.job.take() {
+                if unsafe { TerminateJobObject(job.0, 1) }.is_ok() {
+                    return;
+                }
+            }
+        }
+
+        #[cfg(not(windows))]
+        {
+            let group = format!("-{}", child.id());
+            if Command::new("kill").args(["-KILL", "--", &group]).status().is_ok_and(|s| s.success()) {
+                return;
+            }
+        }
+
+        let _ = child.kill();
+    }
+}
+
+/// Resume a process started with `CREATE_SUSPENDED`. `Child` doesn't expose the handle
+/// of the main thread, so the process's threads are looked up in a Toolhelp snapshot.
+#[cfg(windows)]
+fn resume_threads(pid: u32) -> Result<(), Box<dyn Error>> {
+    use windows::Win32::Foundation::CloseHandle;
+    use windows::Win32::System::Diagnostics::ToolHelp::{
+        CreateToolhelp32Snapshot, Thread32First, Thread32Next, TH32CS_SNAPTHREAD, THREADENTRY32,
+    };
+    use windows::Win32::System::Threading::{OpenThread, ResumeThread, THREAD_SUSPEND_RESUME};
+
+    let mut resumed = false;
+    unsafe {
+        let snapshot = CreateToolhelp32Snapshot(TH32CS_SNAPTHREAD, 0)?;
+        let mut entry = THREADENTRY32 {
+            dwSize: std::mem::size_of::<THREADENTRY32>() as u32,
+            ..Default::default()
+        };
+        let mut found = Thread32First(snapshot, &mut entry).is_ok();
+        while found {
+            if entry.th32OwnerProcessID == pid {
+                if let Ok(thread) = OpenThread(THREAD_SUSPEND_RESUME, false, entry.th32ThreadID) {
+                    resumed |= ResumeThread(thread) != u32::MAX;
+                    let _ = CloseHandle(thread);
+                }
+            }
+            found = Thread32Next(snapshot, &mut entry).is_ok();
+        }
+        let _ = CloseHandle(snapshot);
+    }
+    if resumed {
+        Ok(())
+    } else {
+        Err(format!("Failed to resume process {}", pid).into())
+    }
+}
+
+fn take_output(buffer: &Mutex<Vec<u8>>) -> String {
+    buffer
+        .lock()
+        .map(|bytes| String::from_utf8_lossy(&bytes).into_owned())
+        .unwrap_or_default()
+}
+
+/// Copy a pipe into a shared buffer on a background thread
+fn collect(mut pipe: impl Read + Send + 'static) -> (Arc<Mutex<Vec<u8>>>, JoinHandle<()>) {
+    let buffer = Arc::new(Mutex::new(Vec::new()));
+    let target = Arc::clone(&buffer);
+    let handle = std::thread::spawn(move || {
+        let mut chunk = [0u8; 4096];
+        while let Ok(read) = pipe.read(&mut chunk) {
+            if read == 0 {
+                break;
+            }
+            if let Ok(mut bytes) = target.lock() {
+                let room = MAX_OUTPUT_BYTES.saturating_sub(bytes.len());
+                bytes.extend_from_slice(&chunk[..read.min(room)]);
+            }
+        }
+    });
+    (buffer, handle)
+}
+
+/// Starts captured commands and stops the one currently running
+#[derive(Default)]
+pub struct ShellRunner {
+    running: Option<Arc<RunState>>,
+}
+
+impl ShellRunner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start a captured command, stopping the previous one
+    pub fn start(&mut self, config: &ShellConfig, command: &str) -> Result<RunningCommand, Box<dyn Error>> {
+        self.kill();
+
+        let shell = ShellKind::from_config(config);
+        let mut process = shell.command(shell.run_args(command));
+        process
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+
+        #[cfg(windows)]
+        {
+            use std::os::windows::process::CommandExt;
+            const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+            process.creation_flags(CREATE_NO_WINDOW | CREATE_SUSPENDED);
+        }
+
+        #[cfg(not(windows))]
+        {
+            use std::os::unix::process::CommandExt;
+            process.process_group(0);
+        }
+
+        let mut child = process.spawn()?;
+        let tree = match ProcessTree::new(&child) {
+            Ok(tree) => tree,
+            Err(e) => {
+                let _ = child.kill();
+                return Err(e);
+            }
+        };
+        let stdout = collect(child.stdout.take().ok_or("No stdout pipe")?);
+        let stderr = collect(child.stderr.take().ok_or("No stderr pipe")?);
+        let state = Arc::new(RunState::default());
+        self.running = Some(Arc::clone(&state));
+
+        log::info!("Running in {}: {}", shell.label(), command);
+        Ok(RunningCommand {
+            command: command.to_string(),
+            child,
+            tree,
+            stdout,
+            stderr,
+            state,
+            timeout: Duration::from_secs(config.timeout_secs),
+        })
+    }
+
+    /// Stop the running command; returns whether one was still running
+    pub fn kill(&mut self) -> bool {
+        match self.running.take() {
+            Some(state) if !state.finished.load(Ordering::Relaxed) => !state.cancel.swap(true, Ordering::Relaxed),
+            _ => false,
+        }
+    }
+}
+
+/// Results for a finished command: a summary holding the full output, then its first lines.
+/// The output is kept in `output`, so copying it never goes through a path.
+pub fn output_results(command: &str, output: &CommandOutput, max_lines: usize) -> Vec<SearchResultData> {
+    let lines: Vec<(&str, &str)> = output
+        .stdout
+        .lines()
+        .map(|line| (line, "stdout"))
+        .chain(output.stderr.lines().map(|line| (line, "stderr")))
+        .filter(|(line, _)| !line.trim().is_empty())
+        .collect();
+
+    let status = match output.ending {
+        Ending::Exited(Some(code)) => format!("Exit code {}", code),
+        Ending::Exited(None) => "Exited".to_string(),
+        Ending::TimedOut => "Timed out".to_string(),
+        Ending::Killed => "Stopped".to_string(),
+    };
+    let full_output = [output.stdout.trim_end(), output.stderr.trim_end()]
+        .iter()
+        .filter(|text| !text.is_empty())
+        .copied()
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let mut results = vec![SearchResultData {
+        name: format!("{} · {}", status, command),
+        description: match lines.len() {
+            0 => "No output".to_string(),
+            1 => "1 line of output · Enter copies it".to_string(),
+            n => format!("{} lines of output · Enter copies all", n),
+        },
+        result_type: "shell-output".to_string(),
+        output: Some(full_output),
+        ..Default::default()
+    }];
+
+    results.extend(lines.iter().take(max_lines).map(|(line, stream)| SearchResultData {
+        name: line.trim_end().to_string(),
+        description: format!("{} · Enter copies this line", stream),
+        result_type: "shell-output".to_string(),
+        output: Some(line.trim_end().to_string()),
+        ..Default::default()
+    }));
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> ShellConfig {
+        ShellConfig {
+            timeout_secs: 5,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_parse_query() {
+        assert_eq!(parse_query("> git status "), Some("git status"));
+        assert_eq!(parse_query(">"), Some(""));
+        assert_eq!(parse_query("git > out.txt"), None);
+    }
+
+    #[test]
+    fn test_preview_results() {
+        let results = preview_results("dir", &config());
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].shell.as_ref().map(|run| run.in_terminal), Some(false));
+        assert_eq!(results[1].shell.as_ref().map(|run| run.in_terminal), Some(true));
+
+        let confirm = confirmation_result(results[0].shell.as_ref().unwrap());
+        assert_eq!(confirm.name, "Run \"dir\"?");
+        assert!(confirm.shell.unwrap().confirmed);
+
+        assert_eq!(ShellKind::from_name("PWSH.exe"), Some(ShellKind::Pwsh));
+        assert_eq!(ShellKind::from_name("fish"), None);
+    }
+
+    #[test]
+    fn test_output_results() {
+        let output = CommandOutput {
+            stdout: "one\n\ntwo\nthree\n".to_string(),
+            stderr: "warning\n".to_string(),
+            ending: Ending::Exited(Some(1)),
+        };
+        let results = output_results("build", &output, 2);
+        let names: Vec<&str> = results.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(names, ["Exit code 1 · build", "one", "two"]);
+        assert_eq!(results[0].description, "4 lines of output · Enter copies all");
+        assert_eq!(results[0].output.as_deref(), Some("one\n\ntwo\nthree\nwarning"));
+        assert_eq!(results[1].output.as_deref(), Some("one"));
+
+        let killed = CommandOutput {
+            stdout: String::new(),
+            stderr: String::new(),
+            ending: Ending::Killed,
+        };
+        assert_eq!(output_results("x", &killed, 5)[0].description, "No output");
+    }
+
+    #[test]
+    fn test_run_captured() {
+        let mut config = config();
+        config.shell = if cfg!(windows) { "cmd" } else { "bash" }.to_string();
+
+        let mut runner = ShellRunner::new();
+        let output = runner.start(&config, "echo hello").unwrap().wait();
+        assert_eq!(output.stdout.trim(), "hello");
+        assert_eq!(output.ending, Ending::Exited(Some(0)));
+
+        // Nothing left to stop once the command ended
+        assert!(!runner.kill());
+    }
+
+    #[test]
+    fn test_timeout_and_kill() {
+        let mut config = config();
+        config.shell = if cfg!(windows) { "cmd" } else { "bash" }.to_string();
+        config.timeout_secs = 0;
+        let slow = if cfg!(windows) { "ping -n 10 127.0.0.1" } else { "sleep 10" };
+
+        let mut runner = ShellRunner::new();
+        assert_eq!(runner.start(&config, slow).unwrap().wait().ending, Ending::TimedOut);
+
+        config.timeout_secs = 30;
+        let running = runner.start(&config, slow).unwrap();
+        assert!(runner.kill());
+        assert_eq!(running.wait().ending, Ending::Killed);
+        assert!(!runner.kill());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_timeout_stops_started_programs() {
+        let mut config = config();
+        config.shell = "bash".to_string();
+        config.timeout_secs = 1;
+
+        let output = ShellRunner::new().start(&config, "sleep 30 & echo $!; wait").unwrap().wait();
+        assert_eq!(output.ending, Ending::TimedOut);
+
+        // The background sleep went down with the shell (at most a zombie is left)
+        let stat = std::fs::read_to_string(format!("/proc/{}/stat", output.stdout.trim())).unwrap_or_default();
+        assert!(stat.is_empty() || stat.contains(") Z "), "still running: {}", stat);
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn test_timeout_stops_started_programs() {
+        let mut config = config();
+        config.shell = "powershell".to_string();
+        config.timeout_secs = 5;
+
+        let command = "(Start-Process ping -ArgumentList '-n','60','127.0.0.1' -WindowStyle Hidden -PassThru).Id; \
+                       Start-Sleep 60";
+        let output = ShellRunner::new().start(&config, command).unwrap().wait();
+        assert_eq!(output.ending, Ending::TimedOut);
+
+        // The ping the shell started went down with it
+        let filter = format!("PID eq {}", output.stdout.trim());
+        let listed = Command::new("tasklist").args(["/FI", &filter, "/NH"]).output().unwrap();
+        let listed = String::from_utf8_lossy(&listed.stdout).to_lowercase();
+        assert!(!listed.contains("ping.exe"), "still running: {}", listed);
+    }
+}
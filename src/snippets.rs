@@ -0,0 +1,304 @@
+//! Snippets module
+//! Reusable text (addresses, SQL, ticket templates) stored in the configuration
+//! directory, searched under the `;` prefix and copied with placeholders expanded
+
+use chrono::NaiveDateTime;
+use serde::Deserialize;
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use crate::config::AppConfig;
+use crate::SearchResultData;
+
+/// File name of the snippets store inside the configuration directory
+pub const SNIPPETS_FILE_NAME: &str = "snippets.json";
+
+/// Prefix that searches snippets
+const PREFIX: char = ';';
+
+/// Characters of a snippet body shown in the result description
+const PREVIEW_CHARS: usize = 60;
+
+/// A stored snippet
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Snippet {
+    /// Short trigger typed after `;` (e.g. "addr")
+    pub keyword: String,
+    pub title: String,
+
+    /// Text to copy; may contain `{date}`, `{time}`, `{clipboard}` and `{cursor}`
+    pub body: String,
+}
+
+/// Snippet text with its placeholders filled in
+#[derive(Debug, Clone, PartialEq)]
+pub struct Expansion {
+    pub text: String,
+
+    /// Characters after the `{cursor}` mark, i.e. how far to move the caret back after pasting
+    pub caret_back: usize,
+}
+
+/// All stored snippets
+#[derive(Debug, Default)]
+pub struct SnippetStore {
+    snippets: Vec<Snippet>,
+}
+
+impl SnippetStore {
+    pub fn from_snippets(snippets: Vec<Snippet>) -> Self {
+        Self { snippets }
+    }
+
+    /// Load snippets from a file; a missing file means no snippets
+    pub fn load_from(path: &Path) -> Result<Self, Box<dyn Error>> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let snippets: Vec<Snippet> = serde_json::from_str(&fs::read_to_string(path)?)?;
+        Ok(Self::from_snippets(snippets))
+    }
+
+    /// Look up a snippet by keyword (case-insensitive)
+    pub fn get(&self, keyword: &str) -> Option<&Snippet> {
+        self.snippets
+            .iter()
+            .find(|snippet| snippet.keyword.eq_ignore_ascii_case(keyword))
+    }
+
+    /// Snippets matching the terms: keyword matches first, then titles, then bodies
+    pub fn search(&self, terms: &str) -> Vec<&Snippet> {
+        let terms = terms.to_lowercase();
+        let mut matches: Vec<(u8, &Snippet)> = self
+            .snippets
+            .iter()
+            .filter_map(|snippet| {
+                let rank = if snippet.keyword.to_lowercase().starts_with(&terms) {
+                    0
+                } else if snippet.title.to_lowercase().contains(&terms) {
+                    1
+                } else if snippet.body.to_lowercase().contains(&terms) {
+                    2
+                } else {
+                    return None;
+                };
+                Some((rank, snippet))
+            })
+            .collect();
+
+        // Stable sort keeps the file order within each rank
+        matches.sort_by_key(|(rank, _)| *rank);
+        matches.into_iter().map(|(_, snippet)| snippet).collect()
+    }
+}
+
+/// The snippets file, re-read when it changes
+#[derive(Debug)]
+pub struct SnippetFile {
+    path: PathBuf,
+    /// Modification time at the last read; `None` before the first read
+    modified: Option<Option<SystemTime>>,
+}
+
+impl SnippetFile {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path, modified: None }
+    }
+
+    /// All snippets when the file changed since the last call (always on the first call).
+    /// A file that fails to parse, e.g. while being saved, keeps the previous snippets.
+    pub fn refresh_if_changed(&mut self) -> Option<SnippetStore> {
+        let modified = fs::metadata(&self.path).and_then(|m| m.modified()).ok();
+        if self.modified == Some(modified) {
+            return None;
+        }
+        self.modified = Some(modified);
+
+        SnippetStore::load_from(&self.path)
+            .map_err(|e| log::warn!("Failed to load snippets from {:?}: {}", self.path, e))
+            .ok()
+    }
+}
+
+/// Path of the snippets file in the configuration directory
+pub fn snippets_path() -> Option<PathBuf> {
+    AppConfig::config_dir(crate::single_instance::detect_portable_mode())
+        .map(|dir| dir.join(SNIPPETS_FILE_NAME))
+}
+
+/// Search terms of a `;` query (";addr")
+pub fn parse_query(query: &str) -> Option<&str> {
+    query.trim_start().strip_prefix(PREFIX).map(str::trim)
+}
+
+/// Fill in placeholders: `{date}` (2026-10-18), `{time}` (14:05), `{clipboard}` and `{cursor}`.
+/// Unknown placeholders are left as typed.
+pub fn expand(body: &str, now: NaiveDateTime, clipboard: &str) -> Expansion {
+    // The body is split at the cursor first, so pasted text is never taken for a placeholder
+    let fill = |part: &str| {
+        part.replace("{date}", &now.format("%Y-%m-%d").to_string())
+            .replace("{time}", &now.format("%H:%M").to_string())
+            .replace("{clipboard}", clipboard)
+    };
+
+    match body.split_once("{cursor}") {
+        Some((before, after)) => {
+            let after = fill(&after.replace("{cursor}", ""));
+            Expansion {
+                caret_back: after.chars().count(),
+                text: format!("{}{}", fill(before), after),
+            }
+        }
+        None => Expansion {
+            text: fill(body),
+            caret_back: 0,
+        },
+    }
+}
+
+/// Results for a `;` query
+pub fn search_results(store: &SnippetStore, terms: &str) -> Vec<SearchResultData> {
+    let results: Vec<SearchResultData> = store
+        .search(terms)
+        .into_iter()
+        .map(|snippet| SearchResultData {
+            name: snippet.title.clone(),
+            description: format!(";{} · {}", snippet.keyword, preview(&snippet.body)),
+            path: PathBuf::from(&snippet.keyword),
+            result_type: "snippet".to_string(),
+            ..Default::default()
+        })
+        .collect();
+
+    if results.is_empty() {
+        let name = if terms.is_empty() {
+            "No snippets yet"
+        } else {
+            "No matching snippets"
+        };
+        return vec![SearchResultData {
+            name: name.to_string(),
+            description: format!("Add snippets to {} in the settings folder", SNIPPETS_FILE_NAME),
+            result_type: "info".to_string(),
+            ..Default::default()
+        }];
+    }
+    results
+}
+
+/// Body on one line, shortened for display
+fn preview(body: &str) -> String {
+    let flat = body.split_whitespace().collect::<Vec<_>>().join(" ");
+    let mut preview: String = flat.chars().take(PREVIEW_CHARS).collect();
+    if flat.chars().count() > PREVIEW_CHARS {
+        preview.push('…');
+    }
+    preview
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn snippet(keyword: &str, title: &str, body: &str) -> Snippet {
+        Snippet {
+            keyword: keyword.to_string(),
+            title: title.to_string(),
+            body: body.to_string(),
+        }
+    }
+
+    fn store() -> SnippetStore {
+        SnippetStore::from_snippets(vec![
+            snippet("sql", "Select recent orders", "SELECT * FROM orders WHERE created > '{date}'"),
+            snippet("addr", "Office address", "1 Main Street\nSpringfield"),
+            snippet("bug", "Bug ticket", "Steps:\n{cursor}\n\nSeen in: {clipboard}"),
+        ])
+    }
+
+    #[test]
+    fn test_parse_query() {
+        assert_eq!(parse_query(";addr "), Some("addr"));
+        assert_eq!(parse_query(";"), Some(""));
+        assert_eq!(parse_query("a;b"), None);
+    }
+
+    #[test]
+    fn test_search_ranking() {
+        let store = store();
+        let titles = |terms| store.search(terms).iter().map(|s| s.title.clone()).collect::<Vec<_>>();
+
+        assert_eq!(titles("addr"), ["Office address"]);
+        assert_eq!(titles("ORDERS"), ["Select recent orders"]);
+        assert_eq!(titles("steps"), ["Bug ticket"]);
+        assert_eq!(titles("").len(), 3);
+        assert_eq!(store.get("BUG").map(|s| s.title.as_str()), Some("Bug ticket"));
+    }
+
+    #[test]
+    fn test_expand_placeholders() {
+        let now = NaiveDate::from_ymd_opt(2026, 10, 18).unwrap().and_hms_opt(14, 5, 0).unwrap();
+
+        let sql = expand(&store().get("sql").unwrap().body, now, "");
+        assert_eq!(sql.text, "SELECT * FROM orders WHERE created > '2026-10-18'");
+        assert_eq!(sql.caret_back, 0);
+
+        let bug = expand(&store().get("bug").unwrap().body, now, "v1.2");
+        assert_eq!(bug.text, "Steps:\n\n\nSeen in: v1.2");
+        assert_eq!(bug.caret_back, "\n\nSeen in: v1.2".chars().count());
+
+        assert_eq!(expand("{time} {unknown}", now, "").text, "14:05 {unknown}");
+
+        // Placeholders in the clipboard text are pasted as they are
+        let pasted = expand("[{clipboard}]{cursor}!", now, "{cursor}{date}");
+        assert_eq!(pasted.text, "[{cursor}{date}]!");
+        assert_eq!(pasted.caret_back, 1);
+    }
+
+    #[test]
+    fn test_search_results() {
+        let results = search_results(&store(), "addr");
+        assert_eq!(results[0].result_type, "snippet");
+        assert_eq!(results[0].description, ";addr · 1 Main Street Springfield");
+        assert_eq!(search_results(&SnippetStore::default(), "")[0].name, "No snippets yet");
+    }
+
+    #[test]
+    fn test_load_from() {
+        let path = std::env::temp_dir().join(format!("nexus_snippets_{}.json", std::process::id()));
+        assert!(SnippetStore::load_from(&path).unwrap().search("").is_empty());
+
+        fs::write(&path, r#"[{"keyword": "sig", "title": "Signature", "body": "Regards"}]"#).unwrap();
+        let store = SnippetStore::load_from(&path).unwrap();
+        assert_eq!(store.get("sig").map(|s| s.body.as_str()), Some("Regards"));
+
+        fs::write(&path, "not json").unwrap();
+        assert!(SnippetStore::load_from(&path).is_err());
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_file_refresh() {
+        let path = std::env::temp_dir().join(format!("nexus_snippet_file_{}.json", std::process::id()));
+        fs::write(&path, r#"[{"keyword": "sig", "title": "Signature", "body": "Regards"}]"#).unwrap();
+
+        let mut file = SnippetFile::new(path.clone());
+        assert!(file.refresh_if_changed().unwrap().get("sig").is_some());
+        assert!(file.refresh_if_changed().is_none());
+
+        // A half-written file keeps the previous snippets
+        fs::write(&path, "[{").unwrap();
+        let later = SystemTime::now() + std::time::Duration::from_secs(5);
+        fs::File::options().write(true).open(&path).unwrap().set_modified(later).unwrap();
+        assert!(file.refresh_if_changed().is_none());
+
+        fs::write(&path, r#"[{"keyword": "hi", "title": "Greeting", "body": "Hello"}]"#).unwrap();
+        let later = SystemTime::now() + std::time::Duration::from_secs(10);
+        fs::File::options().write(true).open(&path).unwrap().set_modified(later).unwrap();
+        assert!(file.refresh_if_changed().unwrap().get("hi").is_some());
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,505 @@
+//! SSH hosts module
+//! Lists hosts from `~/.ssh/config` (following `Include` directives) and `known_hosts`
+//! under the "ssh" keyword; Enter opens a terminal running `ssh <host>`
+
+use std::collections::HashSet;
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::SearchResultData;
+
+/// OpenSSH stops following nested `Include` directives at this depth
+const MAX_INCLUDE_DEPTH: usize = 16;
+
+/// A host that can be connected to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SshHost {
+    /// Name typed after `ssh`: the config alias or the known host name
+    pub alias: String,
+    pub hostname: Option<String>,
+    pub user: Option<String>,
+    pub port: Option<u16>,
+
+    /// Declared in the config, so `ssh <alias>` picks up its settings
+    pub configured: bool,
+}
+
+impl SshHost {
+    fn new(alias: &str, configured: bool) -> Self {
+        SshHost {
+            alias: alias.to_string(),
+            hostname: None,
+            user: None,
+            port: None,
+            configured,
+        }
+    }
+
+    /// Arguments to `ssh` that connect to the host
+    pub fn args(&self) -> Vec<String> {
+        match self.port {
+            // Configured hosts get their port from the config
+            Some(port) if !self.configured => vec!["-p".to_string(), port.to_string(), self.alias.clone()],
+            _ => vec![self.alias.clone()],
+        }
+    }
+
+    /// Command that connects to the host, as shown in the results
+    pub fn command(&self) -> String {
+        format!("ssh {}", self.args().join(" "))
+    }
+
+    /// Where the connection goes, e.g. "admin@203.0.113.10:2222"
+    fn target(&self) -> String {
+        let mut target = self.hostname.clone().unwrap_or_else(|| self.alias.clone());
+        if let Some(user) = &self.user {
+            target = format!("{}@{}", user, target);
+        }
+        if let Some(port) = self.port {
+            target = format!("{}:{}", target, port);
+        }
+        target
+    }
+}
+
+/// Search terms of an "ssh" query ("ssh", "ssh prod")
+pub fn parse_query(query: &str) -> Option<&str> {
+    let query = query.trim_start();
+    let (keyword, rest) = query.split_once(char::is_whitespace).unwrap_or((query, ""));
+    keyword.eq_ignore_ascii_case("ssh").then(|| rest.trim())
+}
+
+/// Whether a host name is safe to pass to `ssh`: letters, digits and `._@:[]-`, and
+/// no leading `-` that ssh would read as an option
+pub fn is_valid_host(host: &str) -> bool {
+    !host.is_empty()
+        && !host.starts_with('-')
+        && host.chars().all(|c| c.is_ascii_alphanumeric() || "._@:[]-".contains(c))
+}
+
+/// Open a terminal window running `ssh` for the host. The arguments go to ssh as they
+/// are, never through a shell.
+pub fn connect(host: &SshHost) -> Result<(), Box<dyn Error>> {
+    #[cfg(windows)]
+    {
+        use std::os::windows::process::CommandExt;
+        const CREATE_NEW_CONSOLE: u32 = 0x0000_0010;
+        Command::new("ssh")
+            .args(host.args())
+            .creation_flags(CREATE_NEW_CONSOLE)
+            .spawn()?;
+    }
+
+    #[cfg(not(windows))]
+    {
+        Command::new("x-terminal-emulator")
+            .args(["-e", "ssh"])
+            .args(host.args())
+            .spawn()?;
+    }
+
+    log::info!("Opened terminal for: {}", host.command());
+    Ok(())
+}
+
+/// `~/.ssh`, where OpenSSH keeps its user config on every platform
+pub fn ssh_dir() -> Option<PathBuf> {
+    dirs::home_dir().map(|home| home.join(".ssh"))
+}
+
+/// Hosts from `config` and `known_hosts` in an SSH directory; configured hosts come
+/// first, and known hosts already covered by the config are left out
+pub fn load_hosts(ssh_dir: &Path) -> Vec<SshHost> {
+    let mut hosts = parse_config(&ssh_dir.join("config"), ssh_dir);
+    if let Ok(text) = fs::read_to_string(ssh_dir.join("known_hosts")) {
+        for known in parse_known_hosts(&text) {
+            let covered = hosts
+                .iter()
+                .any(|host| host.alias == known.alias || host.hostname.as_deref() == Some(known.alias.as_str()));
+            if !covered {
+                hosts.push(known);
+            }
+        }
+    }
+    hosts
+}
+
+/// Hosts declared in an ssh config file. `Host` patterns with wildcards or negations
+/// are skipped since they name no single host; relative `Include` paths are resolved
+/// against `ssh_dir` as OpenSSH does for user configs.
+pub fn parse_config(path: &Path, ssh_dir: &Path) -> Vec<SshHost> {
+    let mut parser = ConfigParser {
+        ssh_dir,
+        hosts: Vec::new(),
+        visited: HashSet::new(),
+    };
+    parser.read(path, 0);
+    parser.hosts
+}
+
+struct ConfigParser<'a> {
+    ssh_dir: &'a Path,
+    hosts: Vec<SshHost>,
+
+    /// Files already read, so include cycles end
+    visited: HashSet<PathBuf>,
+}
+
+impl ConfigParser<'_> {
+    fn read(&mut self, path: &Path, depth: usize) {
+        if depth > MAX_INCLUDE_DEPTH {
+            log::warn!("SSH config includes nested too deeply at {}", path.display());
+            return;
+        }
+        let key = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+        if !self.visited.insert(key) {
+            return;
+        }
+        match fs::read_to_string(path) {
+            Ok(text) => self.parse(&text, depth),
+            Err(e) => log::debug!("Skipping SSH config {}: {}", path.display(), e),
+        }
+    }
+
+    fn parse(&mut self, text: &str, depth: usize) {
+        // Positions in `hosts` of the aliases the current `Host` block applies to
+        let mut block: Vec<usize> = Vec::new();
+
+        for line in text.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            // "Keyword value" or "Keyword=value"
+            let Some((keyword, value)) = line.split_once(|c: char| c.is_whitespace() || c == '=') else {
+                continue;
+            };
+            let args = tokenize(value.trim_start_matches(|c: char| c.is_whitespace() || c == '='));
+
+            match keyword.to_lowercase().as_str() {
+                "host" => {
+                    block = args
+                        .iter()
+                        .filter(|pattern| !pattern.contains(['*', '?', '!']))
+                        .map(|alias| self.host_index(alias))
+                        .collect();
+                }
+                // Options under `Match` depend on runtime conditions
+                "match" => block.clear(),
+                "include" => {
+                    for pattern in &args {
+                        for file in self.expand_include(pattern) {
+                            self.read(&file, depth + 1);
+                        }
+                    }
+                }
+                option @ ("hostname" | "user" | "port") => {
+                    let Some(value) = args.first() else { continue };
+                    for &index in &block {
+                        // As in OpenSSH, the first value given for an option wins
+                        let host = &mut self.hosts[index];
+                        match option {
+                            "hostname" if host.hostname.is_none() => {
+                                host.hostname = Some(value.replace("%h", &host.alias).replace("%%", "%"));
+                            }
+                            "user" if host.user.is_none() => host.user = Some(value.clone()),
+                            "port" if host.port.is_none() => host.port = value.parse().ok(),
+                            _ => {}
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Position of a configured host, added on first sight
+    fn host_index(&mut self, alias: &str) -> usize {
+        match self.hosts.iter().position(|host| host.alias == alias) {
+            Some(index) => index,
+            None => {
+                self.hosts.push(SshHost::new(alias, true));
+                self.hosts.len() - 1
+            }
+        }
+    }
+
+    /// Files an `Include` argument names; wildcards in the file name match in sorted order
+    fn expand_include(&self, pattern: &str) -> Vec<PathBuf> {
+        let path = match pattern.strip_prefix("~/") {
+            Some(rest) => match dirs::home_dir() {
+                Some(home) => home.join(rest),
+                None => return Vec::new(),
+            },
+            None => self.ssh_dir.join(pattern),
+        };
+
+        let name = path.file_name().unwrap_or_default().to_string_lossy().into_owned();
+        if !name.contains(['*', '?']) {
+            return vec![path];
+        }
+
+        let Some(Ok(entries)) = path.parent().map(fs::read_dir) else {
+            return Vec::new();
+        };
+        let mut files: Vec<PathBuf> = entries
+            .flatten()
+            .filter(|entry| wildcard_match(&name, &entry.file_name().to_string_lossy()))
+            .map(|entry| entry.path())
+            .filter(|path| path.is_file())
+            .collect();
+        files.sort();
+        files
+    }
+}
+
+/// Split on whitespace, keeping double-quoted arguments together
+fn tokenize(value: &str) -> Vec<String> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut quoted = false;
+    for c in value.chars() {
+        match c {
+            '"' => quoted = !quoted,
+            c if c.is_whitespace() && !quoted => {
+                if !current.is_empty() {
+                    args.push(std::mem::take(&mut current));
+                }
+            }
+            c => current.push(c),
+        }
+    }
+    if !current.is_empty() {
+        args.push(current);
+    }
+    args
+}
+
+/// Shell-style `*` and `?` matching
+fn wildcard_match(pattern: &str, text: &str) -> bool {
+    fn matches(pattern: &[char], text: &[char]) -> bool {
+        match (pattern.first(), text.first()) {
+            (None, None) => true,
+            (Some('*'), _) => matches(&pattern[1..], text) || (!text.is_empty() && matches(pattern, &text[1..])),
+            (Some('?'), Some(_)) => matches(&pattern[1..], &text[1..]),
+            (Some(p), Some(t)) if p == t => matches(&pattern[1..], &text[1..]),
+            _ => false,
+        }
+    }
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    matches(&pattern, &text)
+}
+
+/// Hosts in a known_hosts file. Hashed names can't be read back and marker lines
+/// (`@cert-authority`, `@revoked`) describe keys rather than hosts, so both are skipped.
+pub fn parse_known_hosts(text: &str) -> Vec<SshHost> {
+    let mut hosts: Vec<SshHost> = Vec::new();
+    for line in text.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') || line.starts_with('@') || line.starts_with('|') {
+            continue;
+        }
+        // "name,address keytype key": the first name is the one that was typed
+        let Some(name) = line.split_whitespace().next().and_then(|names| names.split(',').next()) else {
+            continue;
+        };
+        if name.contains(['*', '?', '!']) {
+            continue;
+        }
+
+        // Non-default ports are written as "[host]:port"
+        let host = match name.strip_prefix('[').and_then(|rest| rest.split_once("]:")) {
+            Some((alias, port)) => SshHost {
+                port: port.parse().ok(),
+                ..SshHost::new(alias, false)
+            },
+            None => SshHost::new(name, false),
+        };
+        if !hosts.iter().any(|known| known.alias == host.alias && known.port == host.port) {
+            hosts.push(host);
+        }
+    }
+    hosts
+}
+
+/// Hosts whose alias or host name contains the terms, plus a direct connection
+/// for terms that name no listed host ("ssh me@new-box")
+pub fn search_results(hosts: &[SshHost], terms: &str, max_results: usize) -> Vec<SearchResultData> {
+    let lowered = terms.to_lowercase();
+    let mut results: Vec<SearchResultData> = hosts
+        .iter()
+        .filter(|host| is_valid_host(&host.alias))
+        .filter(|host| {
+            host.alias.to_lowercase().contains(&lowered)
+                || host.hostname.as_ref().is_some_and(|name| name.to_lowercase().contains(&lowered))
+        })
+        .take(max_results)
+        .map(|host| SearchResultData {
+            name: host.command(),
+            description: format!(
+                "{} · {}",
+                host.target(),
+                if host.configured { "ssh config" } else { "known_hosts" }
+            ),
+            path: PathBuf::from(host.command()),
+            result_type: "ssh".to_string(),
+            ssh: Some(host.clone()),
+            ..Default::default()
+        })
+        .collect();
+
+    let listed = hosts.iter().any(|host| host.alias.eq_ignore_ascii_case(terms));
+    if is_valid_host(terms) && !listed {
+        let host = SshHost::new(terms, false);
+        results.push(SearchResultData {
+            name: host.command(),
+            description: "Connect in a new terminal".to_string(),
+            path: PathBuf::from(host.command()),
+            result_type: "ssh".to_string(),
+            ssh: Some(host),
+            ..Default::default()
+        });
+    }
+
+    if results.is_empty() {
+        results.push(SearchResultData {
+            name: "No SSH hosts".to_string(),
+            description: "Add hosts to ~/.ssh/config, or type ssh user@host".to_string(),
+            result_type: "info".to_string(),
+            ..Default::default()
+        });
+    }
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixtures() -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/ssh")
+    }
+
+    fn host(alias: &str, hostname: Option<&str>, user: Option<&str>, port: Option<u16>) -> SshHost {
+        SshHost {
+            alias: alias.to_string(),
+            hostname: hostname.map(str::to_string),
+            user: user.map(str::to_string),
+            port,
+            configured: true,
+        }
+    }
+
+    #[test]
+    fn test_parse_config() {
+        let hosts = parse_config(&fixtures().join("config"), &fixtures());
+        assert_eq!(
+            hosts,
+            [
+                // Included first: config.d/work, then nested.conf from inside it
+                host("build-server", Some("10.0.0.5"), Some("ci"), Some(22)),
+                host("db", Some("db.internal"), Some("postgres"), None),
+                host("github.com", None, Some("git"), None),
+                host("web1", Some("web1.example.com"), Some("deploy"), None),
+                host("web2", Some("web2.example.com"), Some("deploy"), None),
+                host("prod", Some("203.0.113.10"), Some("admin"), Some(2222)),
+                host("bastion", Some("bastion.example.com"), Some("ops"), None),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_config_text() {
+        let mut parser = ConfigParser {
+            ssh_dir: Path::new("/nonexistent"),
+            hosts: Vec::new(),
+            visited: HashSet::new(),
+        };
+        parser.parse(
+            "Host a\n  Port 22\n  Port 2200\nHost b a\n  User=\"me\"\nMatch all\n  User other\nInclude missing\n",
+            0,
+        );
+        assert_eq!(
+            parser.hosts,
+            [host("a", None, Some("me"), Some(22)), host("b", None, Some("me"), None)]
+        );
+    }
+
+    #[test]
+    fn test_parse_known_hosts() {
+        let text = fs::read_to_string(fixtures().join("known_hosts")).unwrap();
+        let hosts = parse_known_hosts(&text);
+        let aliases: Vec<(&str, Option<u16>)> = hosts.iter().map(|h| (h.alias.as_str(), h.port)).collect();
+        assert_eq!(
+            aliases,
+            [
+                ("github.com", None),
+                ("git.example.com", Some(2200)),
+                ("203.0.113.10", None),
+                ("newbox.lan", None),
+            ]
+        );
+        assert_eq!(hosts[1].command(), "ssh -p 2200 git.example.com");
+    }
+
+    #[test]
+    fn test_load_hosts_merges_known_hosts() {
+        let hosts = load_hosts(&fixtures());
+        let aliases: Vec<&str> = hosts.iter().map(|h| h.alias.as_str()).collect();
+        // github.com is configured and 203.0.113.10 is prod's address
+        assert_eq!(
+            aliases,
+            ["build-server", "db", "github.com", "web1", "web2", "prod", "bastion", "git.example.com", "newbox.lan"]
+        );
+    }
+
+    #[test]
+    fn test_search_results() {
+        let hosts = load_hosts(&fixtures());
+
+        let results = search_results(&hosts, "prod", 10);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].name, "ssh prod");
+        assert_eq!(results[0].description, "admin@203.0.113.10:2222 · ssh config");
+        assert_eq!(results[0].result_type, "ssh");
+
+        let results = search_results(&hosts, "example", 10);
+        let names: Vec<&str> = results.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(
+            names,
+            ["ssh web1", "ssh web2", "ssh bastion", "ssh -p 2200 git.example.com", "ssh example"]
+        );
+
+        assert_eq!(search_results(&hosts, "", 3).len(), 3);
+        assert_eq!(search_results(&hosts, "me@new-box", 10)[0].name, "ssh me@new-box");
+        assert_eq!(search_results(&[], "", 10)[0].result_type, "info");
+
+        // The host reaches ssh as a single argument
+        let direct = search_results(&hosts, "[::1]:22", 10);
+        assert_eq!(direct[0].ssh.as_ref().unwrap().args(), ["[::1]:22"]);
+    }
+
+    #[test]
+    fn test_direct_connect_rejects_shell_syntax() {
+        for terms in ["a;calc", "a&calc", "$(calc)", "a|b", "`calc`", "-oProxyCommand=calc", "a\"b"] {
+            assert!(!is_valid_host(terms), "{}", terms);
+            assert!(search_results(&[], terms, 10).iter().all(|r| r.result_type == "info"), "{}", terms);
+        }
+        assert!(is_valid_host("deploy@web-1.example.com"));
+        assert!(is_valid_host("[2001:db8::1]"));
+    }
+
+    #[test]
+    fn test_wildcards_and_tokens() {
+        assert!(wildcard_match("*.conf", "work.conf"));
+        assert!(wildcard_match("host?", "host1"));
+        assert!(!wildcard_match("*.conf", "work.conf.bak"));
+        assert_eq!(tokenize(r#"a "b c"  d"#), ["a", "b c", "d"]);
+        assert_eq!(parse_query("ssh"), Some(""));
+        assert_eq!(parse_query("SSH prod "), Some("prod"));
+        assert_eq!(parse_query("sshfs"), None);
+    }
+}
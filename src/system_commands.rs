@@ -0,0 +1,348 @@
+//! System commands module
+//! Confirmation, countdown and cancellation for destructive system commands
+//! (shutdown, restart, sign out, empty recycle bin), including delayed forms like
+//! `shutdown in 30m` and a `cancel shutdown` command
+
+use std::path::PathBuf;
+use std::process::Command;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::commands;
+use crate::SearchResultData;
+
+/// Countdown after confirming a command typed without a delay
+pub const DEFAULT_COUNTDOWN: Duration = Duration::from_secs(30);
+
+/// Longest delay accepted by `shutdown /t`
+const MAX_DELAY: Duration = Duration::from_secs(315_360_000);
+
+/// A system command that needs confirmation before it runs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SystemCommand {
+    Shutdown,
+    Restart,
+    SignOut,
+    EmptyRecycleBin,
+}
+
+/// A command together with the delay it runs after
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScheduledCommand {
+    pub command: SystemCommand,
+    pub delay: Duration,
+}
+
+impl SystemCommand {
+    /// The command registered under a registry ID, if it needs confirmation
+    pub fn from_command_id(id: &str) -> Option<Self> {
+        match id {
+            "shutdown" => Some(SystemCommand::Shutdown),
+            "restart" => Some(SystemCommand::Restart),
+            "sign-out" => Some(SystemCommand::SignOut),
+            "empty-recycle-bin" => Some(SystemCommand::EmptyRecycleBin),
+            _ => None,
+        }
+    }
+
+    /// ID of the command in the registry
+    pub fn command_id(self) -> &'static str {
+        match self {
+            SystemCommand::Shutdown => "shutdown",
+            SystemCommand::Restart => "restart",
+            SystemCommand::SignOut => "sign-out",
+            SystemCommand::EmptyRecycleBin => "empty-recycle-bin",
+        }
+    }
+
+    /// Match the command words of a query against the registry ("shut down", "reboot", ...)
+    fn from_keyword(keyword: &str) -> Option<Self> {
+        commands::COMMANDS
+            .iter()
+            .find(|command| command.search_terms().any(|term| term == keyword))
+            .and_then(|command| Self::from_command_id(command.id))
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            SystemCommand::Shutdown => "Shutdown",
+            SystemCommand::Restart => "Restart",
+            SystemCommand::SignOut => "Sign Out",
+            SystemCommand::EmptyRecycleBin => "Empty Recycle Bin",
+        }
+    }
+
+    /// Phrase used in sentences ("Shut down in 30 s?")
+    fn verb(self) -> &'static str {
+        match self {
+            SystemCommand::Shutdown => "Shut down",
+            SystemCommand::Restart => "Restart",
+            SystemCommand::SignOut => "Sign out",
+            SystemCommand::EmptyRecycleBin => "Empty the Recycle Bin",
+        }
+    }
+
+    /// Shutdown and restart use Windows' own countdown, which warns the user
+    /// and is aborted with `shutdown /a`
+    fn has_native_countdown(self) -> bool {
+        matches!(self, SystemCommand::Shutdown | SystemCommand::Restart)
+    }
+}
+
+/// Parse "shutdown in 30m", "restart in 1h 30m" or "sign out in 90 s"
+pub fn parse_delayed(query: &str) -> Option<ScheduledCommand> {
+    let query = query.trim().to_lowercase();
+    let (keyword, delay) = query.split_once(" in ")?;
+    let command = SystemCommand::from_keyword(keyword.trim())?;
+    let delay = parse_duration(delay)?;
+    Some(ScheduledCommand { command, delay })
+}
+
+/// Parse a duration like "30m", "1h 30 min" or "90 seconds"
+pub fn parse_duration(text: &str) -> Option<Duration> {
+    let mut total = 0u64;
+    let mut rest = text.trim();
+    if rest.is_empty() {
+        return None;
+    }
+
+    while !rest.is_empty() {
+        let digits = rest.find(|c: char| !c.is_ascii_digit()).unwrap_or(rest.len());
+        let value: u64 = rest[..digits].parse().ok()?;
+        rest = rest[digits..].trim_start();
+
+        let unit_len = rest.find(|c: char| !c.is_ascii_alphabetic()).unwrap_or(rest.len());
+        let seconds = match &rest[..unit_len] {
+            "s" | "sec" | "secs" | "second" | "seconds" => 1,
+            "m" | "min" | "mins" | "minute" | "minutes" => 60,
+            "h" | "hr" | "hrs" | "hour" | "hours" => 3600,
+            _ => return None,
+        };
+        total = total.checked_add(value.checked_mul(seconds)?)?;
+        rest = rest[unit_len..].trim_start();
+    }
+
+    let delay = Duration::from_secs(total);
+    (delay > Duration::ZERO && delay <= MAX_DELAY).then_some(delay)
+}
+
+/// Whether the query asks to cancel a scheduled command ("cancel shutdown", "abort restart")
+pub fn is_cancel_query(query: &str) -> bool {
+    let query = query.trim().to_lowercase();
+    let Some(keyword) = query
+        .strip_prefix("cancel ")
+        .or_else(|| query.strip_prefix("abort "))
+    else {
+        return false;
+    };
+    SystemCommand::from_keyword(keyword.trim()).is_some()
+}
+
+/// "30 s", "5 min", "1 h 30 min"
+pub fn format_delay(delay: Duration) -> String {
+    let secs = delay.as_secs();
+    let (hours, minutes, seconds) = (secs / 3600, secs % 3600 / 60, secs % 60);
+    let parts: Vec<String> = [(hours, "h"), (minutes, "min"), (seconds, "s")]
+        .iter()
+        .filter(|(value, _)| *value > 0)
+        .map(|(value, unit)| format!("{} {}", value, unit))
+        .collect();
+
+    if parts.is_empty() {
+        "0 s".to_string()
+    } else {
+        parts.join(" ")
+    }
+}
+
+/// Result for a delayed command query like "shutdown in 30m"
+pub fn delayed_result(scheduled: ScheduledCommand) -> SearchResultData {
+    SearchResultData {
+        name: format!("{} in {}", scheduled.command.label(), format_delay(scheduled.delay)),
+        description: format!("{} after {}", scheduled.command.verb(), format_delay(scheduled.delay)),
+        path: PathBuf::from(scheduled.command.command_id()),
+        result_type: "action".to_string(),
+        command_id: Some(scheduled.command.command_id()),
+        scheduled: Some(scheduled),
+        ..Default::default()
+    }
+}
+
+/// Confirmation shown after activating a destructive command
+pub fn confirmation_result(scheduled: ScheduledCommand) -> SearchResultData {
+    SearchResultData {
+        name: format!("{} in {}?", scheduled.command.verb(), format_delay(scheduled.delay)),
+        description: format!(
+            "Press Enter to confirm · cancel later with \"cancel {}\"",
+            scheduled.command.label().to_lowercase()
+        ),
+        path: PathBuf::from(scheduled.command.command_id()),
+        result_type: "confirm".to_string(),
+        command_id: Some(scheduled.command.command_id()),
+        scheduled: Some(scheduled),
+        ..Default::default()
+    }
+}
+
+/// A scheduled command waiting for its countdown
+struct PendingCommand {
+    command: SystemCommand,
+    due: Instant,
+    cancelled: Arc<AtomicBool>,
+}
+
+/// Keeps track of the command currently counting down
+#[derive(Default)]
+pub struct CommandScheduler {
+    pending: Option<PendingCommand>,
+}
+
+impl CommandScheduler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start the countdown for a confirmed command, replacing any pending one
+    pub fn schedule(&mut self, scheduled: ScheduledCommand) {
+        self.cancel();
+
+        let command = scheduled.command;
+        let cancelled = Arc::new(AtomicBool::new(false));
+
+        if command.has_native_countdown() {
+            let flag = if command == SystemCommand::Shutdown { "/s" } else { "/r" };
+            let seconds = scheduled.delay.as_secs().to_string();
+            if let Err(e) = Command::new("shutdown").args([flag, "/t", &seconds]).spawn() {
+                log::error!("Failed to schedule {}: {}", command.label(), e);
+                return;
+            }
+        } else {
+            let cancelled = Arc::clone(&cancelled);
+            let due = Instant::now() + scheduled.delay;
+            std::thread::spawn(move || {
+                while Instant::now() < due {
+                    if cancelled.load(Ordering::Relaxed) {
+                        return;
+                    }
+                    std::thread::sleep(Duration::from_millis(250));
+                }
+                if !cancelled.load(Ordering::Relaxed) {
+                    commands::execute(command.command_id());
+                }
+            });
+        }
+
+        log::info!("{} scheduled in {}", command.label(), format_delay(scheduled.delay));
+        self.pending = Some(PendingCommand {
+            command,
+            due: Instant::now() + scheduled.delay,
+            cancelled,
+        });
+    }
+
+    /// Cancel the pending command, returning it when there was one
+    pub fn cancel(&mut self) -> Option<SystemCommand> {
+        let pending = self.pending.take().filter(|p| p.due > Instant::now())?;
+        pending.cancelled.store(true, Ordering::Relaxed);
+
+        // Waited for, so a countdown scheduled right after is never the one aborted
+        if pending.command.has_native_countdown() {
+            if let Err(e) = Command::new("shutdown").arg("/a").status() {
+                log::error!("Failed to abort {}: {}", pending.command.label(), e);
+            }
+        }
+        log::info!("{} cancelled", pending.command.label());
+        Some(pending.command)
+    }
+
+    /// The pending command and the time left on its countdown
+    pub fn pending(&self) -> Option<(SystemCommand, Duration)> {
+        let pending = self.pending.as_ref()?;
+        let remaining = pending.due.checked_duration_since(Instant::now())?;
+        Some((pending.command, remaining))
+    }
+
+    /// Result for a "cancel shutdown" query
+    pub fn cancel_result(&self) -> SearchResultData {
+        match self.pending() {
+            Some((command, remaining)) => SearchResultData {
+                name: format!("Cancel {}", command.label()),
+                description: format!("{} in {} · press Enter to cancel", command.verb(), format_delay(remaining)),
+                path: PathBuf::from("cancel"),
+                result_type: "cancel".to_string(),
+                ..Default::default()
+            },
+            None => SearchResultData {
+                name: "Nothing to cancel".to_string(),
+                description: "No shutdown, restart or sign-out is scheduled".to_string(),
+                result_type: "info".to_string(),
+                ..Default::default()
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_delayed() {
+        assert_eq!(
+            parse_delayed("shutdown in 30m"),
+            Some(ScheduledCommand {
+                command: SystemCommand::Shutdown,
+                delay: Duration::from_secs(1800),
+            })
+        );
+        assert_eq!(
+            parse_delayed("Restart in 1h 30 min").map(|s| s.delay),
+            Some(Duration::from_secs(5400))
+        );
+        assert_eq!(
+            parse_delayed("sign out in 90 seconds").map(|s| s.command),
+            Some(SystemCommand::SignOut)
+        );
+        assert_eq!(parse_delayed("shutdown in"), None);
+        assert_eq!(parse_delayed("shutdown in 0m"), None);
+        assert_eq!(parse_delayed("shutdown in soon"), None);
+        assert_eq!(parse_delayed("lock in 5m"), None);
+    }
+
+    #[test]
+    fn test_cancel_query() {
+        assert!(is_cancel_query("cancel shutdown"));
+        assert!(is_cancel_query("Abort restart"));
+        assert!(is_cancel_query("cancel sign out"));
+        assert!(is_cancel_query("cancel trash"));
+        assert!(!is_cancel_query("cancel"));
+        assert!(!is_cancel_query("cancel subscription"));
+    }
+
+    #[test]
+    fn test_confirmation_result() {
+        let scheduled = ScheduledCommand {
+            command: SystemCommand::Restart,
+            delay: DEFAULT_COUNTDOWN,
+        };
+        let result = confirmation_result(scheduled);
+        assert_eq!(result.name, "Restart in 30 s?");
+        assert_eq!(result.result_type, "confirm");
+        assert_eq!(result.scheduled, Some(scheduled));
+
+        assert_eq!(format_delay(Duration::from_secs(5400)), "1 h 30 min");
+        assert_eq!(
+            SystemCommand::from_command_id("empty-recycle-bin"),
+            Some(SystemCommand::EmptyRecycleBin)
+        );
+        assert_eq!(SystemCommand::from_command_id("lock"), None);
+    }
+
+    #[test]
+    fn test_nothing_to_cancel() {
+        let mut scheduler = CommandScheduler::new();
+        assert_eq!(scheduler.cancel(), None);
+        assert_eq!(scheduler.cancel_result().result_type, "info");
+    }
+}
@@ -0,0 +1,471 @@
+//! Timers module
+//! Countdown timers (`timer 25m standup`) and clock-time reminders (`remind 14:30 call
+//! vendor`). Pending timers are saved in the configuration directory so they survive a
+//! restart, and fire as notifications from the background service.
+
+use chrono::{DateTime, Days, NaiveTime, TimeZone};
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::config::AppConfig;
+use crate::notifier::Notifier;
+use crate::system_commands::{format_delay, parse_duration};
+use crate::SearchResultData;
+
+/// File name of the pending timers inside the configuration directory
+pub const TIMERS_FILE_NAME: &str = "timers.json";
+
+/// Timers that fired this much later than due were missed while Nexus wasn't running
+const MISSED_AFTER_SECS: i64 = 60;
+
+/// When a new timer fires
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum When {
+    /// After a countdown from the moment it is started
+    In(Duration),
+    /// At the next occurrence of a clock time
+    At(NaiveTime),
+}
+
+/// A timer typed into the launcher but not started yet
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TimerRequest {
+    pub when: When,
+    pub label: String,
+}
+
+impl TimerRequest {
+    /// Unix timestamp the timer is due at when started at `now`
+    pub fn due<Tz: TimeZone>(&self, now: &DateTime<Tz>) -> i64 {
+        match self.when {
+            When::In(delay) => now.timestamp() + delay.as_secs() as i64,
+            When::At(time) => {
+                let today = now.date_naive().and_time(time);
+                let due = |naive| now.timezone().from_local_datetime(&naive).earliest();
+                match due(today) {
+                    Some(at) if at.timestamp() > now.timestamp() => at.timestamp(),
+                    _ => today
+                        .checked_add_days(Days::new(1))
+                        .and_then(due)
+                        .map_or(now.timestamp() + 86_400, |at| at.timestamp()),
+                }
+            }
+        }
+    }
+
+    fn is_reminder(&self) -> bool {
+        matches!(self.when, When::At(_))
+    }
+}
+
+/// A `timer` or `remind` query
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TimerQuery {
+    /// List active timers
+    List,
+    New(TimerRequest),
+    /// The keyword was typed but the time isn't complete or valid yet
+    Incomplete { reminder: bool },
+}
+
+/// Parse "timer", "timer 25m standup", "remind 14:30 call vendor" or "remind in 20m tea"
+pub fn parse_query(query: &str) -> Option<TimerQuery> {
+    let query = query.trim();
+    let (keyword, rest) = query.split_once(char::is_whitespace).unwrap_or((query, ""));
+    let words: Vec<&str> = rest.split_whitespace().collect();
+
+    match keyword.to_lowercase().as_str() {
+        "timer" | "timers" if words.is_empty() => Some(TimerQuery::List),
+        "timer" => Some(parse_countdown(&words).unwrap_or(TimerQuery::Incomplete { reminder: false })),
+        "remind" => {
+            let parsed = match words.split_first() {
+                Some((first, rest)) if first.eq_ignore_ascii_case("in") => parse_countdown(rest),
+                Some((first, rest)) => NaiveTime::parse_from_str(first, "%H:%M").ok().map(|time| {
+                    TimerQuery::New(TimerRequest {
+                        when: When::At(time),
+                        label: rest.join(" "),
+                    })
+                }),
+                None => None,
+            };
+            Some(parsed.unwrap_or(TimerQuery::Incomplete { reminder: true }))
+        }
+        _ => None,
+    }
+}
+
+/// Longest run of leading words that reads as a duration ("1h 30m"), the rest is the label
+fn parse_countdown(words: &[&str]) -> Option<TimerQuery> {
+    (1..=words.len()).rev().find_map(|split| {
+        let delay = parse_duration(&words[..split].join(" "))?;
+        Some(TimerQuery::New(TimerRequest {
+            when: When::In(delay),
+            label: words[split..].join(" "),
+        }))
+    })
+}
+
+/// A started timer
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Timer {
+    pub id: u64,
+    pub label: String,
+
+    /// When the timer fires (Unix timestamp in seconds)
+    pub due: i64,
+
+    /// Set by a clock time (`remind`) rather than a countdown (`timer`)
+    #[serde(default)]
+    pub reminder: bool,
+}
+
+impl Timer {
+    /// Notification title and message
+    fn notification(&self, now: i64) -> (&'static str, String) {
+        let title = if self.reminder { "Reminder" } else { "Timer" };
+        let mut message = if self.label.is_empty() {
+            format!("{} finished", title)
+        } else {
+            self.label.clone()
+        };
+        if now - self.due > MISSED_AFTER_SECS {
+            message.push_str(" (missed while Nexus wasn't running)");
+        }
+        (title, message)
+    }
+}
+
+/// Pending timers, soonest first
+#[derive(Debug, Default)]
+pub struct TimerService {
+    timers: Vec<Timer>,
+    path: Option<PathBuf>,
+}
+
+impl TimerService {
+    /// No timers; they are persisted to `path` when one is given
+    pub fn new(path: Option<PathBuf>) -> Self {
+        Self {
+            timers: Vec::new(),
+            path,
+        }
+    }
+
+    /// Load the timers stored at `path`, starting empty when it is missing or unreadable
+    pub fn load(path: PathBuf) -> Self {
+        let mut service = Self::new(Some(path.clone()));
+        if !path.exists() {
+            return service;
+        }
+
+        match read_timers(&path) {
+            Ok(mut timers) => {
+                timers.sort_by_key(|timer| timer.due);
+                log::info!("Loaded {} pending timers", timers.len());
+                service.timers = timers;
+            }
+            Err(e) => log::warn!("Failed to load timers: {}. Starting empty.", e),
+        }
+        service
+    }
+
+    /// Pending timers, soonest first
+    pub fn active(&self) -> &[Timer] {
+        &self.timers
+    }
+
+    /// Start a timer and save it
+    pub fn start<Tz: TimeZone>(&mut self, request: &TimerRequest, now: &DateTime<Tz>) -> Timer {
+        let timer = Timer {
+            id: self.timers.iter().map(|timer| timer.id + 1).max().unwrap_or(1),
+            label: request.label.clone(),
+            due: request.due(now),
+            reminder: request.is_reminder(),
+        };
+        let index = self.timers.partition_point(|pending| pending.due <= timer.due);
+        self.timers.insert(index, timer.clone());
+        self.persist();
+        log::info!("Timer {} started, due at {}", timer.id, timer.due);
+        timer
+    }
+
+    /// Cancel a pending timer, returning it when it existed
+    pub fn cancel(&mut self, id: u64) -> Option<Timer> {
+        let index = self.timers.iter().position(|timer| timer.id == id)?;
+        let timer = self.timers.remove(index);
+        self.persist();
+        log::info!("Timer {} cancelled", id);
+        Some(timer)
+    }
+
+    /// Notify and remove every timer due at `now` (Unix timestamp); returns the fired timers
+    pub fn fire_due(&mut self, now: i64, notifier: &dyn Notifier) -> Vec<Timer> {
+        let due = self.timers.partition_point(|timer| timer.due <= now);
+        if due == 0 {
+            return Vec::new();
+        }
+
+        let fired: Vec<Timer> = self.timers.drain(..due).collect();
+        for timer in &fired {
+            let (title, message) = timer.notification(now);
+            if let Err(e) = notifier.notify(title, &message) {
+                log::error!("Failed to show notification for timer {}: {}", timer.id, e);
+            }
+        }
+        self.persist();
+        fired
+    }
+
+    /// Save the timers, logging failures
+    fn persist(&self) {
+        if let Err(e) = self.save() {
+            log::warn!("Failed to save timers: {}", e);
+        }
+    }
+
+    /// Write the timers to disk, removing the file when none are pending
+    pub fn save(&self) -> Result<(), Box<dyn Error>> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        if self.timers.is_empty() {
+            if path.exists() {
+                fs::remove_file(path)?;
+            }
+            return Ok(());
+        }
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(&self.timers)?)?;
+        Ok(())
+    }
+}
+
+fn read_timers(path: &Path) -> Result<Vec<Timer>, Box<dyn Error>> {
+    Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+}
+
+/// Path of the timers file in the configuration directory
+pub fn timers_path() -> Option<PathBuf> {
+    AppConfig::config_dir(crate::single_instance::detect_portable_mode())
+        .map(|dir| dir.join(TIMERS_FILE_NAME))
+}
+
+/// Results for a `timer` or `remind` query
+pub fn search_results<Tz: TimeZone>(service: &TimerService, query: TimerQuery, now: &DateTime<Tz>) -> Vec<SearchResultData>
+where
+    Tz::Offset: std::fmt::Display,
+{
+    match query {
+        TimerQuery::List => active_results(service, now),
+        TimerQuery::New(request) => {
+            let label = if request.label.is_empty() {
+                String::new()
+            } else {
+                format!(": {}", request.label)
+            };
+            let name = match request.when {
+                When::In(delay) => format!("Timer {}{}", format_delay(delay), label),
+                When::At(time) => format!("Remind at {}{}", time.format("%H:%M"), label),
+            };
+            vec![SearchResultData {
+                name,
+                description: "Press Enter to start".to_string(),
+                result_type: "timer-add".to_string(),
+                timer: Some(request),
+                ..Default::default()
+            }]
+        }
+        TimerQuery::Incomplete { reminder } => {
+            let (name, example) = if reminder {
+                ("Type a time", "e.g. remind 14:30 call vendor or remind in 20m tea")
+            } else {
+                ("Type a duration", "e.g. timer 25m standup")
+            };
+            vec![info_result(name, example)]
+        }
+    }
+}
+
+/// One result per pending timer; Enter cancels it
+pub fn active_results<Tz: TimeZone>(service: &TimerService, now: &DateTime<Tz>) -> Vec<SearchResultData>
+where
+    Tz::Offset: std::fmt::Display,
+{
+    if service.active().is_empty() {
+        return vec![info_result("No active timers", "Start one with timer 25m standup")];
+    }
+    service
+        .active()
+        .iter()
+        .map(|timer| {
+            let left = Duration::from_secs((timer.due - now.timestamp()).max(0) as u64);
+            let due = now.timezone().timestamp_opt(timer.due, 0).single();
+            let name = if timer.label.is_empty() {
+                if timer.reminder { "Reminder" } else { "Timer" }.to_string()
+            } else {
+                timer.label.clone()
+            };
+            SearchResultData {
+                name,
+                description: format!(
+                    "Due {} · {} left · Enter to cancel",
+                    due.map_or_else(String::new, |due| due.format("%H:%M").to_string()),
+                    format_delay(left)
+                ),
+                path: PathBuf::from(timer.id.to_string()),
+                result_type: "timer".to_string(),
+                ..Default::default()
+            }
+        })
+        .collect()
+}
+
+fn info_result(name: &str, description: &str) -> SearchResultData {
+    SearchResultData {
+        name: name.to_string(),
+        description: description.to_string(),
+        result_type: "info".to_string(),
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{NaiveDate, Utc};
+    use std::sync::Mutex;
+
+    /// Records notifications instead of showing them
+    #[derive(Default)]
+    struct FakeNotifier {
+        shown: Mutex<Vec<(String, String)>>,
+    }
+
+    impl Notifier for FakeNotifier {
+        fn notify(&self, title: &str, message: &str) -> Result<(), Box<dyn Error>> {
+            self.shown.lock().unwrap().push((title.to_string(), message.to_string()));
+            Ok(())
+        }
+    }
+
+    fn at(hour: u32, minute: u32) -> DateTime<Utc> {
+        let naive = NaiveDate::from_ymd_opt(2026, 10, 18).unwrap().and_hms_opt(hour, minute, 0).unwrap();
+        Utc.from_utc_datetime(&naive)
+    }
+
+    fn request(when: When, label: &str) -> TimerRequest {
+        TimerRequest {
+            when,
+            label: label.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_parse_query() {
+        assert_eq!(parse_query("timer"), Some(TimerQuery::List));
+        assert_eq!(
+            parse_query("timer 25m standup"),
+            Some(TimerQuery::New(request(When::In(Duration::from_secs(1500)), "standup")))
+        );
+        assert_eq!(
+            parse_query("timer 1h 30 min deep work"),
+            Some(TimerQuery::New(request(When::In(Duration::from_secs(5400)), "deep work")))
+        );
+        assert_eq!(
+            parse_query("remind 14:30 call vendor"),
+            Some(TimerQuery::New(request(When::At(NaiveTime::from_hms_opt(14, 30, 0).unwrap()), "call vendor")))
+        );
+        assert_eq!(
+            parse_query("Remind in 20m tea"),
+            Some(TimerQuery::New(request(When::In(Duration::from_secs(1200)), "tea")))
+        );
+        assert_eq!(parse_query("timer soon"), Some(TimerQuery::Incomplete { reminder: false }));
+        assert_eq!(parse_query("remind 25:00 x"), Some(TimerQuery::Incomplete { reminder: true }));
+        assert_eq!(parse_query("timetable"), None);
+    }
+
+    #[test]
+    fn test_due_time() {
+        let now = at(15, 0);
+        assert_eq!(request(When::In(Duration::from_secs(60)), "").due(&now), now.timestamp() + 60);
+
+        // A clock time later today fires today, an earlier one tomorrow
+        let later = When::At(NaiveTime::from_hms_opt(16, 30, 0).unwrap());
+        assert_eq!(request(later, "").due(&now), at(16, 30).timestamp());
+        let earlier = When::At(NaiveTime::from_hms_opt(14, 30, 0).unwrap());
+        assert_eq!(request(earlier, "").due(&now), at(14, 30).timestamp() + 86_400);
+    }
+
+    #[test]
+    fn test_fire_due() {
+        let mut service = TimerService::new(None);
+        let now = at(9, 0);
+        let standup = service.start(&request(When::In(Duration::from_secs(600)), "standup"), &now);
+        let tea = service.start(&request(When::In(Duration::from_secs(300)), "tea"), &now);
+        service.start(&request(When::In(Duration::from_secs(3600)), ""), &now);
+        assert_eq!(service.active()[0], tea);
+
+        let notifier = FakeNotifier::default();
+        assert!(service.fire_due(now.timestamp() + 60, &notifier).is_empty());
+
+        assert_eq!(service.fire_due(tea.due, &notifier), [tea]);
+        assert_eq!(service.fire_due(standup.due + 30, &notifier), [standup]);
+        assert_eq!(
+            *notifier.shown.lock().unwrap(),
+            [("Timer".to_string(), "tea".to_string()), ("Timer".to_string(), "standup".to_string())]
+        );
+
+        // A timer that fires long after it was due says so
+        service.fire_due(now.timestamp() + 7200, &notifier);
+        assert_eq!(
+            notifier.shown.lock().unwrap()[2].1,
+            "Timer finished (missed while Nexus wasn't running)"
+        );
+        assert!(service.active().is_empty());
+    }
+
+    #[test]
+    fn test_persist_and_cancel() {
+        let path = std::env::temp_dir().join(format!("nexus_timers_{}.json", std::process::id()));
+        let _ = fs::remove_file(&path);
+
+        let mut service = TimerService::load(path.clone());
+        let now = at(9, 0);
+        let first = service.start(&request(When::In(Duration::from_secs(600)), "standup"), &now);
+        let second = service.start(&request(When::At(NaiveTime::from_hms_opt(14, 30, 0).unwrap()), "vendor"), &now);
+
+        // A restart picks up the pending timers
+        let mut reloaded = TimerService::load(path.clone());
+        assert_eq!(reloaded.active(), [first.clone(), second.clone()]);
+        assert!(reloaded.active()[1].reminder);
+
+        assert_eq!(reloaded.cancel(first.id), Some(first));
+        assert_eq!(reloaded.cancel(99), None);
+        assert_eq!(TimerService::load(path.clone()).active(), std::slice::from_ref(&second));
+
+        reloaded.cancel(second.id);
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_search_results() {
+        let mut service = TimerService::new(None);
+        let now = at(9, 0);
+        assert_eq!(search_results(&service, TimerQuery::List, &now)[0].name, "No active timers");
+
+        let add = search_results(&service, parse_query("timer 25m standup").unwrap(), &now);
+        assert_eq!(add[0].name, "Timer 25 min: standup");
+        assert_eq!(add[0].result_type, "timer-add");
+
+        let timer = service.start(add[0].timer.as_ref().unwrap(), &now);
+        let listed = search_results(&service, TimerQuery::List, &now);
+        assert_eq!(listed[0].name, "standup");
+        assert_eq!(listed[0].description, "Due 09:25 · 25 min left · Enter to cancel");
+        assert_eq!(listed[0].path, PathBuf::from(timer.id.to_string()));
+    }
+}
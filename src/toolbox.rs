@@ -0,0 +1,419 @@
+//! Developer toolbox module
+//! Offline encode, decode and hash commands: "b64 hello", "url a&b", "jwt <token>",
+//! "sha256 <text or path>", "md5 <text or path>" and "json {...}". Files are hashed
+//! on a background thread once their result is activated, never while typing.
+
+use std::error::Error;
+use std::fs::File;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use base64::engine::general_purpose::{STANDARD, STANDARD_NO_PAD, URL_SAFE, URL_SAFE_NO_PAD};
+use base64::Engine;
+use chrono::{DateTime, Utc};
+use md5::Md5;
+use sha2::{Digest, Sha256};
+
+use crate::SearchResultData;
+
+/// A toolbox command
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tool {
+    Base64,
+    Url,
+    Jwt,
+    Sha256,
+    Md5,
+    Json,
+}
+
+/// Keywords for each tool
+const TOOLS: &[(&[&str], Tool)] = &[
+    (&["b64", "base64"], Tool::Base64),
+    (&["url", "urlencode", "urldecode"], Tool::Url),
+    (&["jwt"], Tool::Jwt),
+    (&["sha256"], Tool::Sha256),
+    (&["md5"], Tool::Md5),
+    (&["json"], Tool::Json),
+];
+
+/// Longest preview shown as the result name; the full value is copied
+const MAX_PREVIEW: usize = 80;
+
+/// "b64 hello" -> (Base64, "hello")
+pub fn parse_query(query: &str) -> Option<(Tool, &str)> {
+    let (keyword, input) = query.trim().split_once(char::is_whitespace)?;
+    let keyword = keyword.to_lowercase();
+    let (_, tool) = TOOLS.iter().find(|(keywords, _)| keywords.contains(&keyword.as_str()))?;
+    let input = input.trim();
+    (!input.is_empty()).then_some((*tool, input))
+}
+
+/// A file to hash, carried from its result to activation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileHash {
+    pub tool: Tool,
+    pub path: PathBuf,
+}
+
+/// A computed value and what it is, e.g. ("aGVsbG8=", "Base64 encoded")
+struct Output {
+    value: String,
+    label: String,
+}
+
+impl Output {
+    fn new(value: impl Into<String>, label: impl Into<String>) -> Self {
+        Output {
+            value: value.into(),
+            label: label.into(),
+        }
+    }
+}
+
+pub fn search_results(tool: Tool, input: &str, now: DateTime<Utc>) -> Vec<SearchResultData> {
+    // Only absolute paths name files, so "sha256 README" hashes the text
+    let path = Path::new(input.trim_matches('"'));
+    if matches!(tool, Tool::Sha256 | Tool::Md5) && path.is_absolute() && path.is_file() {
+        return vec![file_hash_result(FileHash {
+            tool,
+            path: path.to_path_buf(),
+        })];
+    }
+
+    let outputs = match tool {
+        Tool::Base64 => Ok(base64_outputs(input)),
+        Tool::Url => Ok(url_outputs(input)),
+        Tool::Jwt => jwt_outputs(input, now),
+        Tool::Sha256 => Ok(text_hash_outputs::<Sha256>("SHA-256", input)),
+        Tool::Md5 => Ok(text_hash_outputs::<Md5>("MD5", input)),
+        Tool::Json => json_outputs(input),
+    };
+    output_results(tool, outputs)
+}
+
+/// Results for computed values, or the error with the tool's usage
+fn output_results(tool: Tool, outputs: Result<Vec<Output>, Box<dyn Error>>) -> Vec<SearchResultData> {
+    match outputs {
+        Ok(outputs) => outputs
+            .into_iter()
+            .map(|output| SearchResultData {
+                name: preview(&output.value),
+                description: format!("{} · Enter to copy", output.label),
+                path: PathBuf::from(output.value),
+                result_type: "toolbox".to_string(),
+                ..Default::default()
+            })
+            .collect(),
+        Err(e) => vec![SearchResultData {
+            name: e.to_string(),
+            description: usage(tool).to_string(),
+            result_type: "info".to_string(),
+            ..Default::default()
+        }],
+    }
+}
+
+fn usage(tool: Tool) -> &'static str {
+    match tool {
+        Tool::Base64 => "b64 <text or base64>",
+        Tool::Url => "url <text or percent-encoded text>",
+        Tool::Jwt => "jwt <header.payload.signature>",
+        Tool::Sha256 => "sha256 <text or absolute file path>",
+        Tool::Md5 => "md5 <text or absolute file path>",
+        Tool::Json => "json <JSON text>",
+    }
+}
+
+/// Single-line preview: whitespace runs collapsed, long values cut with an ellipsis
+pub fn preview(value: &str) -> String {
+    let collapsed = value.split_whitespace().collect::<Vec<_>>().join(" ");
+    match collapsed.char_indices().nth(MAX_PREVIEW) {
+        Some((cut, _)) => format!("{}…", &collapsed[..cut]),
+        None => collapsed,
+    }
+}
+
+/// Encoded form, with the decoded text first when the input is itself valid Base64 text
+fn base64_outputs(input: &str) -> Vec<Output> {
+    let mut outputs = Vec::new();
+    if let Some(decoded) = decode_base64(input).and_then(|bytes| String::from_utf8(bytes).ok()) {
+        if !decoded.chars().any(|c| c.is_control() && !c.is_whitespace()) {
+            outputs.push(Output::new(decoded, "Base64 decoded"));
+        }
+    }
+    outputs.push(Output::new(STANDARD.encode(input), "Base64 encoded"));
+    outputs
+}
+
+/// Standard or URL-safe alphabet, padded or not
+fn decode_base64(input: &str) -> Option<Vec<u8>> {
+    [STANDARD, STANDARD_NO_PAD, URL_SAFE, URL_SAFE_NO_PAD]
+        .iter()
+        .find_map(|engine| engine.decode(input).ok())
+}
+
+/// Percent-encoded form, with the decoded text first when the input contains escapes
+fn url_outputs(input: &str) -> Vec<Output> {
+    let mut outputs = Vec::new();
+    if input.contains('%') || input.contains('+') {
+        outputs.push(Output::new(percent_decode(input), "URL decoded"));
+    }
+    outputs.push(Output::new(crate::actions::percent_encode(input), "URL encoded"));
+    outputs
+}
+
+/// Decode `%XX` escapes and `+` as space; malformed escapes are kept as-is
+fn percent_decode(input: &str) -> String {
+    let bytes = input.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escaped = bytes
+            .get(i + 1..i + 3)
+            .filter(|_| bytes[i] == b'%')
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match (escaped, bytes[i]) {
+            (Some(byte), _) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            (None, b'+') => {
+                decoded.push(b' ');
+                i += 1;
+            }
+            (None, byte) => {
+                decoded.push(byte);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// Header and payload as pretty JSON plus the expiry; the signature is not verified
+fn jwt_outputs(input: &str, now: DateTime<Utc>) -> Result<Vec<Output>, Box<dyn Error>> {
+    let token = input.strip_prefix("Bearer ").unwrap_or(input);
+    let mut parts = token.split('.');
+    let (Some(header), Some(payload)) = (parts.next(), parts.next()) else {
+        return Err("Not a JWT".into());
+    };
+
+    let decode = |part: &str| -> Result<serde_json::Value, Box<dyn Error>> {
+        let bytes = URL_SAFE_NO_PAD
+            .decode(part.trim_end_matches('='))
+            .map_err(|_| "JWT part is not valid Base64URL")?;
+        Ok(serde_json::from_slice(&bytes).map_err(|_| "JWT part is not JSON")?)
+    };
+    let header = decode(header)?;
+    let payload = decode(payload)?;
+
+    let mut payload_label = "JWT payload (signature not verified)".to_string();
+    if let Some(expiry) = payload
+        .get("exp")
+        .and_then(|exp| exp.as_i64())
+        .and_then(|exp| DateTime::<Utc>::from_timestamp(exp, 0))
+    {
+        let state = if expiry <= now { "expired" } else { "expires" };
+        payload_label = format!("{} · {} {}", payload_label, state, expiry.format("%Y-%m-%d %H:%M UTC"));
+    }
+
+    Ok(vec![
+        Output::new(serde_json::to_string_pretty(&payload)?, payload_label),
+        Output::new(serde_json::to_string_pretty(&header)?, "JWT header"),
+    ])
+}
+
+fn text_hash_outputs<D: Digest>(name: &str, input: &str) -> Vec<Output> {
+    vec![Output::new(hex(&D::digest(input.as_bytes())), format!("{} of text", name))]
+}
+
+fn file_hash_outputs<D: Digest + io::Write>(name: &str, path: &Path) -> Result<Vec<Output>, Box<dyn Error>> {
+    let mut hasher = D::new();
+    io::copy(&mut File::open(path)?, &mut hasher)?;
+    let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+    Ok(vec![Output::new(
+        hex(&hasher.finalize()),
+        format!("{} of {}", name, file_name),
+    )])
+}
+
+fn hash_name(tool: Tool) -> &'static str {
+    if tool == Tool::Md5 {
+        "MD5"
+    } else {
+        "SHA-256"
+    }
+}
+
+/// A file named in a hash query, hashed when activated
+fn file_hash_result(file: FileHash) -> SearchResultData {
+    let file_name = file.path.file_name().unwrap_or_default().to_string_lossy();
+    SearchResultData {
+        name: format!("{} of {}", hash_name(file.tool), file_name),
+        description: "Enter to hash the file".to_string(),
+        path: file.path.clone(),
+        result_type: "toolbox-hash".to_string(),
+        file_hash: Some(file),
+        ..Default::default()
+    }
+}
+
+/// Shown while a file is being hashed
+pub fn hashing_result(file: &FileHash) -> SearchResultData {
+    let file_name = file.path.file_name().unwrap_or_default().to_string_lossy();
+    SearchResultData {
+        name: format!("Hashing {}…", file_name),
+        description: hash_name(file.tool).to_string(),
+        path: file.path.clone(),
+        result_type: "toolbox-hashing".to_string(),
+        ..Default::default()
+    }
+}
+
+/// Hash a file, reading all of it; run off the UI thread
+pub fn hash_file(file: &FileHash) -> Vec<SearchResultData> {
+    let outputs = match file.tool {
+        Tool::Md5 => file_hash_outputs::<Md5>("MD5", &file.path),
+        _ => file_hash_outputs::<Sha256>("SHA-256", &file.path),
+    };
+    output_results(file.tool, outputs)
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// Pretty-printed and minified JSON
+fn json_outputs(input: &str) -> Result<Vec<Output>, Box<dyn Error>> {
+    let value: serde_json::Value =
+        serde_json::from_str(input).map_err(|e| format!("Invalid JSON: {}", e))?;
+    let pretty = serde_json::to_string_pretty(&value)?;
+    let lines = pretty.lines().count();
+    Ok(vec![
+        Output::new(pretty, format!("Pretty-printed JSON · {} lines", lines)),
+        Output::new(serde_json::to_string(&value)?, "Minified JSON"),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 10, 18, 12, 0, 0).unwrap()
+    }
+
+    /// (copied value, description) of each result
+    fn run(query: &str) -> Vec<(String, String)> {
+        let (tool, input) = parse_query(query).unwrap();
+        search_results(tool, input, now())
+            .into_iter()
+            .map(|r| (r.path.to_string_lossy().into_owned(), r.description))
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_query() {
+        assert_eq!(parse_query("b64 hello world"), Some((Tool::Base64, "hello world")));
+        assert_eq!(parse_query("SHA256  notes.txt "), Some((Tool::Sha256, "notes.txt")));
+        assert_eq!(parse_query("urldecode a%20b"), Some((Tool::Url, "a%20b")));
+        assert_eq!(parse_query("json"), None);
+        assert_eq!(parse_query("jwt   "), None);
+        assert_eq!(parse_query("base 64"), None);
+    }
+
+    #[test]
+    fn test_base64() {
+        let results = run("b64 hello");
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].0, "aGVsbG8=");
+        assert_eq!(results[0].1, "Base64 encoded · Enter to copy");
+
+        let results = run("b64 aGVsbG8gd29ybGQ");
+        assert_eq!(results[0].0, "hello world");
+        assert_eq!(results[0].1, "Base64 decoded · Enter to copy");
+        assert_eq!(results[1].0, "YUdWc2JHOGdkMjl5YkdR");
+    }
+
+    #[test]
+    fn test_url() {
+        assert_eq!(run("url a&b=c d")[0].0, "a%26b%3Dc%20d");
+
+        let results = run("url caf%C3%A9+au+lait%2");
+        assert_eq!(results[0].0, "café au lait%2");
+        assert_eq!(results[1].0, "caf%25C3%25A9%2Bau%2Blait%252");
+    }
+
+    #[test]
+    fn test_jwt() {
+        let header = URL_SAFE_NO_PAD.encode(r#"{"alg":"HS256","typ":"JWT"}"#);
+        let payload = URL_SAFE_NO_PAD.encode(r#"{"sub":"42","exp":1792238400}"#);
+        let results = run(&format!("jwt Bearer {}.{}.c2lnbmF0dXJl", header, payload));
+        assert_eq!(results[0].0, "{\n  \"exp\": 1792238400,\n  \"sub\": \"42\"\n}");
+        assert_eq!(
+            results[0].1,
+            "JWT payload (signature not verified) · expired 2026-10-17 12:00 UTC · Enter to copy"
+        );
+        assert!(results[1].0.contains("\"alg\": \"HS256\""));
+
+        let (tool, input) = parse_query("jwt not-a-token").unwrap();
+        let results = search_results(tool, input, now());
+        assert_eq!(results[0].result_type, "info");
+        assert_eq!(results[0].name, "Not a JWT");
+    }
+
+    #[test]
+    fn test_hashes() {
+        assert_eq!(
+            run("sha256 abc")[0].0,
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        let results = run("md5 abc");
+        assert_eq!(results[0].0, "900150983cd24fb0d6963f7d28e17f72");
+        assert_eq!(results[0].1, "MD5 of text · Enter to copy");
+
+        let dir = std::env::temp_dir().join(format!("nexus-toolbox-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let file = dir.join("abc.txt");
+        std::fs::write(&file, "abc").unwrap();
+        let query = format!("md5 \"{}\"", file.display());
+        let (tool, input) = parse_query(&query).unwrap();
+        let results = search_results(tool, input, now());
+        assert_eq!(results[0].result_type, "toolbox-hash");
+        assert_eq!(results[0].name, "MD5 of abc.txt");
+
+        // The file is read only when the result is activated
+        let request = results[0].file_hash.clone().unwrap();
+        assert_eq!(hashing_result(&request).name, "Hashing abc.txt…");
+        let results = hash_file(&request);
+        assert_eq!(results[0].path, PathBuf::from("900150983cd24fb0d6963f7d28e17f72"));
+        assert_eq!(results[0].description, "MD5 of abc.txt · Enter to copy");
+
+        std::fs::remove_file(&file).unwrap();
+        assert_eq!(hash_file(&request)[0].result_type, "info");
+        std::fs::remove_dir_all(&dir).ok();
+
+        // Relative names are text, even when a file by that name is in the working directory
+        assert_eq!(run("md5 Cargo.toml")[0].1, "MD5 of text · Enter to copy");
+    }
+
+    #[test]
+    fn test_json() {
+        let results = run(r#"json {"b": [1, 2], "a": null}"#);
+        assert_eq!(results[0].1, "Pretty-printed JSON · 7 lines · Enter to copy");
+        assert_eq!(results[1].0, r#"{"a":null,"b":[1,2]}"#);
+
+        let (tool, input) = parse_query("json {oops").unwrap();
+        assert!(search_results(tool, input, now())[0].name.starts_with("Invalid JSON"));
+    }
+
+    #[test]
+    fn test_preview() {
+        assert_eq!(preview("{\n  \"a\": 1\n}"), "{ \"a\": 1 }");
+        let long = "x".repeat(100);
+        assert_eq!(preview(&long).chars().count(), MAX_PREVIEW + 1);
+    }
+}
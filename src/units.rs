@@ -0,0 +1,362 @@
+//! Unit conversion module
+//! Parses queries like "12 km in mi" or "350F to C" and converts between units
+
+/// Physical quantity a unit measures; only units of the same dimension convert
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Dimension {
+    Length,
+    Mass,
+    Temperature,
+    DataSize,
+    Time,
+    Speed,
+    Area,
+}
+
+impl std::fmt::Display for Dimension {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Dimension::Length => write!(f, "Length"),
+            Dimension::Mass => write!(f, "Mass"),
+            Dimension::Temperature => write!(f, "Temperature"),
+            Dimension::DataSize => write!(f, "Data size"),
+            Dimension::Time => write!(f, "Time"),
+            Dimension::Speed => write!(f, "Speed"),
+            Dimension::Area => write!(f, "Area"),
+        }
+    }
+}
+
+use Dimension::*;
+
+/// A unit definition. Values convert to the dimension's base unit as
+/// `value * factor + offset` (the offset is only non-zero for temperatures).
+#[derive(Debug)]
+pub struct Unit {
+    /// Symbol used when displaying results
+    pub symbol: &'static str,
+    /// Accepted spellings, matched exactly first and then case-insensitively
+    pub aliases: &'static [&'static str],
+    pub dimension: Dimension,
+    pub factor: f64,
+    pub offset: f64,
+}
+
+const fn unit(
+    symbol: &'static str,
+    aliases: &'static [&'static str],
+    dimension: Dimension,
+    factor: f64,
+) -> Unit {
+    Unit { symbol, aliases, dimension, factor, offset: 0.0 }
+}
+
+/// Unit table. Base units: metre, kilogram, kelvin, byte, second, metre per second, square metre.
+/// Order matters for case-insensitive lookups: the first matching entry wins.
+pub static UNITS: &[Unit] = &[
+    // Length
+    unit("m", &["m", "meter", "meters", "metre", "metres"], Length, 1.0),
+    unit("km", &["km", "kilometer", "kilometers", "kilometre", "kilometres"], Length, 1000.0),
+    unit("cm", &["cm", "centimeter", "centimeters", "centimetre", "centimetres"], Length, 0.01),
+    unit("mm", &["mm", "millimeter", "millimeters", "millimetre", "millimetres"], Length, 0.001),
+    unit("µm", &["µm", "um", "micrometer", "micrometers", "micron", "microns"], Length, 1e-6),
+    unit("nm", &["nm", "nanometer", "nanometers"], Length, 1e-9),
+    unit("mi", &["mi", "mile", "miles"], Length, 1609.344),
+    unit("yd", &["yd", "yard", "yards"], Length, 0.9144),
+    unit("ft", &["ft", "foot", "feet", "'"], Length, 0.3048),
+    unit("in", &["in", "inch", "inches", "\""], Length, 0.0254),
+    unit("nmi", &["nmi", "nautical mile", "nautical miles"], Length, 1852.0),
+    // Mass
+    unit("kg", &["kg", "kilogram", "kilograms", "kilo", "kilos"], Mass, 1.0),
+    unit("g", &["g", "gram", "grams"], Mass, 0.001),
+    unit("mg", &["mg", "milligram", "milligrams"], Mass, 1e-6),
+    unit("t", &["t", "tonne", "tonnes", "metric ton", "metric tons"], Mass, 1000.0),
+    unit("lb", &["lb", "lbs", "pound", "pounds"], Mass, 0.453_592_37),
+    unit("oz", &["oz", "ounce", "ounces"], Mass, 0.028_349_523_125),
+    unit("st", &["st", "stone", "stones"], Mass, 6.350_293_18),
+    // Temperature
+    Unit {
+        symbol: "°C",
+        aliases: &["C", "°C", "degC", "celsius", "centigrade"],
+        dimension: Temperature,
+        factor: 1.0,
+        offset: 273.15,
+    },
+    Unit {
+        symbol: "°F",
+        aliases: &["F", "°F", "degF", "fahrenheit"],
+        dimension: Temperature,
+        factor: 5.0 / 9.0,
+        offset: 273.15 - 32.0 * 5.0 / 9.0,
+    },
+    Unit {
+        symbol: "K",
+        aliases: &["K", "kelvin"],
+        dimension: Temperature,
+        factor: 1.0,
+        offset: 0.0,
+    },
+    // Data size (bytes before bits so "mb" means megabytes)
+    unit("B", &["B", "byte", "bytes"], DataSize, 1.0),
+    unit("KB", &["KB", "kB", "kilobyte", "kilobytes"], DataSize, 1e3),
+    unit("MB", &["MB", "megabyte", "megabytes"], DataSize, 1e6),
+    unit("GB", &["GB", "gigabyte", "gigabytes"], DataSize, 1e9),
+    unit("TB", &["TB", "terabyte", "terabytes"], DataSize, 1e12),
+    unit("KiB", &["KiB", "kibibyte", "kibibytes"], DataSize, 1024.0),
+    unit("MiB", &["MiB", "mebibyte", "mebibytes"], DataSize, 1_048_576.0),
+    unit("GiB", &["GiB", "gibibyte", "gibibytes"], DataSize, 1_073_741_824.0),
+    unit("TiB", &["TiB", "tebibyte", "tebibytes"], DataSize, 1_099_511_627_776.0),
+    unit("bit", &["b", "bit", "bits"], DataSize, 0.125),
+    unit("kbit", &["kbit", "kilobit", "kilobits"], DataSize, 125.0),
+    unit("Mbit", &["Mb", "Mbit", "megabit", "megabits"], DataSize, 125e3),
+    unit("Gbit", &["Gb", "Gbit", "gigabit", "gigabits"], DataSize, 125e6),
+    // Time
+    unit("ns", &["ns", "nanosecond", "nanoseconds"], Time, 1e-9),
+    unit("µs", &["µs", "us", "microsecond", "microseconds"], Time, 1e-6),
+    unit("ms", &["ms", "millisecond", "milliseconds"], Time, 1e-3),
+    unit("s", &["s", "sec", "secs", "second", "seconds"], Time, 1.0),
+    unit("min", &["min", "mins", "minute", "minutes"], Time, 60.0),
+    unit("h", &["h", "hr", "hrs", "hour", "hours"], Time, 3600.0),
+    unit("d", &["d", "day", "days"], Time, 86_400.0),
+    unit("wk", &["wk", "week", "weeks"], Time, 604_800.0),
+    unit("yr", &["yr", "yrs", "year", "years"], Time, 31_556_952.0),
+    // Speed
+    unit("m/s", &["m/s", "mps", "meters per second", "metres per second"], Speed, 1.0),
+    unit("km/h", &["km/h", "kmh", "kph", "kmph", "kilometers per hour", "kilometres per hour"], Speed, 1000.0 / 3600.0),
+    unit("mph", &["mph", "mi/h", "miles per hour"], Speed, 0.44704),
+    unit("kn", &["kn", "kt", "knot", "knots"], Speed, 1852.0 / 3600.0),
+    unit("ft/s", &["ft/s", "fps", "feet per second"], Speed, 0.3048),
+    // Area
+    unit("m²", &["m²", "m2", "m^2", "sq m", "square meter", "square meters", "square metre", "square metres"], Area, 1.0),
+    unit("km²", &["km²", "km2", "km^2", "sq km", "square kilometer", "square kilometers"], Area, 1e6),
+    unit("cm²", &["cm²", "cm2", "cm^2", "sq cm"], Area, 1e-4),
+    unit("ha", &["ha", "hectare", "hectares"], Area, 1e4),
+    unit("ac", &["ac", "acre", "acres"], Area, 4_046.856_422_4),
+    unit("ft²", &["ft²", "ft2", "ft^2", "sq ft", "sqft", "square foot", "square feet"], Area, 0.092_903_04),
+    unit("in²", &["in²", "in2", "in^2", "sq in", "square inch", "square inches"], Area, 0.000_645_16),
+    unit("yd²", &["yd²", "yd2", "yd^2", "sq yd", "square yard", "square yards"], Area, 0.836_127_36),
+    unit("mi²", &["mi²", "mi2", "mi^2", "sq mi", "square mile", "square miles"], Area, 2_589_988.110_336),
+];
+
+/// Words separating the source quantity from the target unit
+const CONNECTORS: &[&str] = &["in", "to", "as", "into", "->"];
+
+/// Result of a successful conversion
+#[derive(Debug, Clone, PartialEq)]
+pub struct Conversion {
+    pub value: f64,
+    pub from: &'static Unit,
+    pub result: f64,
+    pub to: &'static Unit,
+}
+
+impl PartialEq for Unit {
+    fn eq(&self, other: &Self) -> bool {
+        self.symbol == other.symbol
+    }
+}
+
+impl Conversion {
+    /// Formatted result, e.g. "7.456454 mi"
+    pub fn result_text(&self) -> String {
+        format!("{} {}", format_number(self.result), self.to.symbol)
+    }
+
+    /// Formatted source quantity, e.g. "12 km"
+    pub fn source_text(&self) -> String {
+        format!("{} {}", format_number(self.value), self.from.symbol)
+    }
+}
+
+/// Look up a unit by name: exact alias match first, then case-insensitive
+pub fn find_unit(name: &str) -> Option<&'static Unit> {
+    let name = name.trim();
+    if name.is_empty() {
+        return None;
+    }
+
+    UNITS
+        .iter()
+        .find(|u| u.aliases.contains(&name))
+        .or_else(|| {
+            let lower = name.to_lowercase();
+            UNITS
+                .iter()
+                .find(|u| u.aliases.iter().any(|a| a.to_lowercase() == lower))
+        })
+}
+
+/// Convert a value between two units of the same dimension
+pub fn convert(value: f64, from: &Unit, to: &Unit) -> Option<f64> {
+    if from.dimension != to.dimension {
+        return None;
+    }
+    let base = value * from.factor + from.offset;
+    Some((base - to.offset) / to.factor)
+}
+
+/// Parse and evaluate a conversion query like "60 mph to m/s"
+pub fn parse_conversion(query: &str) -> Option<Conversion> {
+    let words: Vec<&str> = query.split_whitespace().collect();
+    if words.len() < 2 {
+        return None;
+    }
+
+    // Try connectors from the right so "12 in in cm" splits at the last "in"
+    for i in (1..words.len().saturating_sub(1)).rev() {
+        if !CONNECTORS.iter().any(|c| words[i].eq_ignore_ascii_case(c)) {
+            continue;
+        }
+
+        let source = words[..i].join(" ");
+        let target = words[i + 1..].join(" ");
+
+        if let Some((value, from)) = parse_quantity(&source) {
+            if let Some(to) = find_unit(&target) {
+                if let Some(result) = convert(value, from, to) {
+                    return Some(Conversion { value, from, result, to });
+                }
+            }
+        }
+    }
+
+    None
+}
+
+/// Parse "350F", "2.5 GiB" or "-40 °C" into a value and unit
+fn parse_quantity(text: &str) -> Option<(f64, &'static Unit)> {
+    let text = text.trim();
+    let number_len = text
+        .char_indices()
+        .take_while(|(i, c)| c.is_ascii_digit() || *c == '.' || (*i == 0 && matches!(c, '-' | '+')))
+        .count();
+
+    if number_len == 0 {
+        return None;
+    }
+
+    let value: f64 = text[..number_len].parse().ok()?;
+    let unit = find_unit(&text[number_len..])?;
+    Some((value, unit))
+}
+
+/// Format a converted value: up to 6 decimal places, trailing zeros trimmed,
+/// scientific notation for very large or very small magnitudes
+pub fn format_number(value: f64) -> String {
+    if value == 0.0 {
+        return "0".to_string();
+    }
+    if !value.is_finite() {
+        return value.to_string();
+    }
+
+    let magnitude = value.abs();
+    if !(1e-6..1e15).contains(&magnitude) {
+        let formatted = format!("{:.6e}", value);
+        let (mantissa, exponent) = formatted.split_once('e').unwrap_or((&formatted, "0"));
+        let mantissa = mantissa.trim_end_matches('0').trim_end_matches('.');
+        return format!("{}e{}", mantissa, exponent);
+    }
+
+    let formatted = format!("{:.6}", value);
+    let trimmed = formatted.trim_end_matches('0').trim_end_matches('.');
+    if trimmed == "-0" {
+        "0".to_string()
+    } else {
+        trimmed.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn convert_query(query: &str) -> String {
+        parse_conversion(query)
+            .unwrap_or_else(|| panic!("query should convert: {}", query))
+            .result_text()
+    }
+
+    #[test]
+    fn test_length() {
+        assert_eq!(convert_query("12 km in mi"), "7.456454 mi");
+        assert_eq!(convert_query("1 mi to km"), "1.609344 km");
+        assert_eq!(convert_query("6 ft in cm"), "182.88 cm");
+        assert_eq!(convert_query("12 in in cm"), "30.48 cm");
+        assert_eq!(convert_query("100 meters to yards"), "109.36133 yd");
+    }
+
+    #[test]
+    fn test_mass() {
+        assert_eq!(convert_query("1 kg to lb"), "2.204623 lb");
+        assert_eq!(convert_query("16 oz in lb"), "1 lb");
+        assert_eq!(convert_query("500g to kg"), "0.5 kg");
+    }
+
+    #[test]
+    fn test_temperature() {
+        assert_eq!(convert_query("350F to C"), "176.666667 °C");
+        assert_eq!(convert_query("-40 C to F"), "-40 °F");
+        assert_eq!(convert_query("0 °C in K"), "273.15 K");
+        assert_eq!(convert_query("100 celsius to fahrenheit"), "212 °F");
+        assert_eq!(convert_query("0 K in C"), "-273.15 °C");
+    }
+
+    #[test]
+    fn test_data_size() {
+        assert_eq!(convert_query("2.5 GiB in MB"), "2684.35456 MB");
+        assert_eq!(convert_query("1 GB to MiB"), "953.674316 MiB");
+        assert_eq!(convert_query("8 bit to B"), "1 B");
+        assert_eq!(convert_query("100 Mb to MB"), "12.5 MB");
+        // Lowercase falls back to bytes
+        assert_eq!(convert_query("1 gb in mb"), "1000 MB");
+    }
+
+    #[test]
+    fn test_time_and_speed() {
+        assert_eq!(convert_query("90 min to h"), "1.5 h");
+        assert_eq!(convert_query("1 day in seconds"), "86400 s");
+        assert_eq!(convert_query("60 mph to m/s"), "26.8224 m/s");
+        assert_eq!(convert_query("100 km/h to mph"), "62.137119 mph");
+        assert_eq!(convert_query("10 knots in km/h"), "18.52 km/h");
+    }
+
+    #[test]
+    fn test_area() {
+        assert_eq!(convert_query("1 acre to m2"), "4046.856422 m²");
+        assert_eq!(convert_query("1 ha in acres"), "2.471054 ac");
+        assert_eq!(convert_query("100 sq ft to m²"), "9.290304 m²");
+    }
+
+    #[test]
+    fn test_rejects_invalid_queries() {
+        assert!(parse_conversion("12 km in kg").is_none()); // dimension mismatch
+        assert!(parse_conversion("km in mi").is_none()); // no value
+        assert!(parse_conversion("12 km in").is_none()); // no target
+        assert!(parse_conversion("12 parsecs to m").is_none()); // unknown unit
+        assert!(parse_conversion("hello world").is_none());
+        assert!(parse_conversion("2+2").is_none());
+    }
+
+    #[test]
+    fn test_unit_lookup() {
+        assert_eq!(find_unit("B").unwrap().symbol, "B");
+        assert_eq!(find_unit("b").unwrap().symbol, "bit");
+        assert_eq!(find_unit("mb").unwrap().symbol, "MB");
+        assert_eq!(find_unit("Mb").unwrap().symbol, "Mbit");
+        assert_eq!(find_unit("kb").unwrap().symbol, "KB");
+        assert_eq!(find_unit("KM").unwrap().symbol, "km");
+        assert!(find_unit("").is_none());
+    }
+
+    #[test]
+    fn test_format_number() {
+        assert_eq!(format_number(1.0), "1");
+        assert_eq!(format_number(0.1 + 0.2), "0.3");
+        assert_eq!(format_number(2.0 / 3.0), "0.666667");
+        assert_eq!(format_number(-0.0000001), "-1e-7");
+        assert_eq!(format_number(1.5e20), "1.5e20");
+        assert_eq!(format_number(0.0), "0");
+        assert_eq!(format_number(-0.0000000001 * 0.0), "0");
+    }
+}
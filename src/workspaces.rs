@@ -0,0 +1,604 @@
+//! Workspaces module
+//! Reads the recent projects of VS Code (and its forks) and JetBrains IDEs into the
+//! search catalog: `state.vscdb` or the older `storage.json` of a VS Code user data
+//! folder, and `recentProjects.xml` of a JetBrains IDE config folder. Editors are found
+//! automatically or set in the configuration, and re-read when their lists change.
+
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use crate::config::WorkspacesConfig;
+use crate::recent::{attribute, file_url_to_path, xml_unescape};
+use crate::{AppEntry, AppType};
+
+/// VS Code variants by user data folder: (folder, name, URL scheme)
+const VSCODE_VARIANTS: &[(&str, &str, &str)] = &[
+    ("Code", "Visual Studio Code", "vscode"),
+    ("Code - Insiders", "VS Code Insiders", "vscode-insiders"),
+    ("VSCodium", "VSCodium", "vscodium"),
+    ("Cursor", "Cursor", "cursor"),
+];
+
+/// JetBrains IDEs by config folder prefix: (prefix, name, launcher script). Longer
+/// prefixes come first, so "PyCharmCE2023.1" isn't taken for PyCharm Professional.
+const JETBRAINS_PRODUCTS: &[(&str, &str, &str)] = &[
+    ("IntelliJIdea", "IntelliJ IDEA", "idea"),
+    ("IdeaIC", "IntelliJ IDEA Community Edition", "idea"),
+    ("PyCharmCE", "PyCharm Community Edition", "pycharm"),
+    ("PyCharm", "PyCharm", "pycharm"),
+    ("WebStorm", "WebStorm", "webstorm"),
+    ("PhpStorm", "PhpStorm", "phpstorm"),
+    ("GoLand", "GoLand", "goland"),
+    ("CLion", "CLion", "clion"),
+    ("Rider", "Rider", "rider"),
+    ("RubyMine", "RubyMine", "rubymine"),
+    ("RustRover", "RustRover", "rustrover"),
+    ("DataGrip", "DataGrip", "datagrip"),
+    ("DataSpell", "DataSpell", "dataspell"),
+    ("AndroidStudio", "Android Studio", "studio"),
+];
+
+/// Key of the recent list in VS Code's `state.vscdb`
+const VSCODE_RECENT_KEY: &str = "history.recentlyOpenedPathsList";
+
+/// An editor that keeps a list of recent projects
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Editor {
+    /// VS Code or a fork, opened through its URL scheme
+    VsCode { name: &'static str, scheme: &'static str },
+    /// A JetBrains IDE, opened with its Start menu shortcut or Toolbox script
+    JetBrains { name: &'static str, script: &'static str },
+}
+
+impl Editor {
+    /// VS Code variant of a user data folder or a file inside it; VS Code when unknown
+    pub fn vscode_for(path: &Path) -> Self {
+        let (_, name, scheme) = path
+            .components()
+            .rev()
+            .find_map(|c| VSCODE_VARIANTS.iter().find(|(folder, ..)| c.as_os_str().eq_ignore_ascii_case(folder)))
+            .unwrap_or(&VSCODE_VARIANTS[0]);
+        Editor::VsCode { name, scheme }
+    }
+
+    /// JetBrains IDE of a config folder ("IntelliJIdea2024.1") or a file inside it;
+    /// IntelliJ IDEA when unknown
+    pub fn jetbrains_for(path: &Path) -> Self {
+        let (_, name, script) = path
+            .components()
+            .rev()
+            .find_map(|c| {
+                let folder = c.as_os_str().to_string_lossy();
+                JETBRAINS_PRODUCTS.iter().find(|(prefix, ..)| {
+                    folder
+                        .strip_prefix(prefix)
+                        .is_some_and(|version| version.is_empty() || version.starts_with(|ch: char| ch.is_ascii_digit()))
+                })
+            })
+            .unwrap_or(&JETBRAINS_PRODUCTS[0]);
+        Editor::JetBrains { name, script }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Editor::VsCode { name, .. } | Editor::JetBrains { name, .. } => name,
+        }
+    }
+
+    /// Open a project folder or workspace file in this editor
+    pub fn open(&self, project: &Path, apps: &[AppEntry]) -> Result<(), Box<dyn Error>> {
+        match self {
+            Editor::VsCode { scheme, .. } => open::that(vscode_url(scheme, project))?,
+            Editor::JetBrains { name, script } => {
+                // The installed IDE's shortcut ("IntelliJ IDEA 2024.1"), else the Toolbox script on PATH
+                let prefix = name.to_lowercase();
+                let shortcut = apps
+                    .iter()
+                    .find(|app| app.app_type == AppType::DesktopApp && app.name.to_lowercase().starts_with(&prefix));
+                match shortcut {
+                    Some(app) => launch_with_argument(&app.path, project)?,
+                    None => {
+                        let script = if cfg!(windows) { format!("{}.cmd", script) } else { script.to_string() };
+                        std::process::Command::new(&script)
+                            .arg(project)
+                            .spawn()
+                            .map_err(|e| format!("{} is not installed ({}: {})", name, script, e))?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// `vscode://file/c:/dev/my%20app` URL that opens a folder or workspace file
+pub fn vscode_url(scheme: &str, project: &Path) -> String {
+    let path = project.to_string_lossy().replace('\\', "/");
+    let encoded = path
+        .replace('%', "%25")
+        .replace(' ', "%20")
+        .replace('#', "%23")
+        .replace('?', "%3F");
+    format!("{}://file/{}", scheme, encoded.trim_start_matches('/'))
+}
+
+/// Start a program with a path argument, the way Explorer starts shortcuts
+#[cfg(windows)]
+fn launch_with_argument(program: &Path, argument: &Path) -> Result<(), Box<dyn Error>> {
+    use std::os::windows::ffi::OsStrExt;
+    use windows::core::{w, PCWSTR};
+    use windows::Win32::Foundation::HWND;
+    use windows::Win32::UI::Shell::ShellExecuteW;
+    use windows::Win32::UI::WindowsAndMessaging::SW_SHOWNORMAL;
+
+    let wide = |text: &std::ffi::OsStr| -> Vec<u16> { text.encode_wide().chain(std::iter::once(0)).collect() };
+    let program = wide(program.as_os_str());
+    let parameters = wide(format!("\"{}\"", argument.display()).as_ref());
+    let result = unsafe {
+        ShellExecuteW(
+            HWND::default(),
+            w!("open"),
+            PCWSTR(program.as_ptr()),
+            PCWSTR(parameters.as_ptr()),
+            PCWSTR::null(),
+            SW_SHOWNORMAL,
+        )
+    };
+
+    // ShellExecuteW reports success with a value greater than 32
+    if result.0 as usize > 32 {
+        Ok(())
+    } else {
+        Err(format!("ShellExecuteW failed with code {}", result.0 as usize).into())
+    }
+}
+
+#[cfg(not(windows))]
+fn launch_with_argument(program: &Path, argument: &Path) -> Result<(), Box<dyn Error>> {
+    std::process::Command::new(program).arg(argument).spawn()?;
+    Ok(())
+}
+
+/// A recently opened project
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Workspace {
+    /// Project folder, or `.code-workspace` file of a multi-root VS Code workspace
+    pub path: PathBuf,
+
+    /// Last opened, in milliseconds since the Unix epoch (0 when the editor doesn't say)
+    pub opened: i64,
+}
+
+/// A recent projects list of one editor
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum WorkspaceSource {
+    /// `state.vscdb` of current VS Code versions
+    VsCodeState(PathBuf),
+    /// `storage.json` of older VS Code versions
+    VsCodeStorage(PathBuf),
+    /// `recentProjects.xml` (`recentSolutions.xml` for Rider) of a JetBrains IDE
+    JetBrains(PathBuf),
+}
+
+impl WorkspaceSource {
+    /// Sources in an editor folder or for a recent list file
+    pub fn from_path(path: &Path) -> Vec<Self> {
+        let files: Vec<PathBuf> = if path.is_dir() {
+            [
+                &["User", "globalStorage", "state.vscdb"][..],
+                &["User", "globalStorage", "storage.json"],
+                &["storage.json"],
+                &["options", "recentProjects.xml"],
+                &["options", "recentSolutions.xml"],
+            ]
+            .iter()
+            .map(|parts| parts.iter().fold(path.to_path_buf(), |file, part| file.join(part)))
+            .filter(|file| file.is_file())
+            .collect()
+        } else {
+            vec![path.to_path_buf()]
+        };
+        files
+            .into_iter()
+            .filter_map(|file| match file.file_name()?.to_str()? {
+                "state.vscdb" => Some(WorkspaceSource::VsCodeState(file)),
+                "storage.json" => Some(WorkspaceSource::VsCodeStorage(file)),
+                "recentProjects.xml" | "recentSolutions.xml" => Some(WorkspaceSource::JetBrains(file)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    pub fn path(&self) -> &Path {
+        match self {
+            WorkspaceSource::VsCodeState(path)
+            | WorkspaceSource::VsCodeStorage(path)
+            | WorkspaceSource::JetBrains(path) => path,
+        }
+    }
+
+    /// Editor that opens the projects, guessed from the folder the list is in
+    pub fn editor(&self) -> Editor {
+        match self {
+            WorkspaceSource::VsCodeState(path) | WorkspaceSource::VsCodeStorage(path) => Editor::vscode_for(path),
+            WorkspaceSource::JetBrains(path) => Editor::jetbrains_for(path),
+        }
+    }
+
+    pub fn read(&self) -> Result<Vec<Workspace>, Box<dyn Error>> {
+        match self {
+            WorkspaceSource::VsCodeState(path) => read_vscode_state(path),
+            WorkspaceSource::VsCodeStorage(path) => parse_vscode_recent(&fs::read_to_string(path)?),
+            WorkspaceSource::JetBrains(path) => Ok(parse_jetbrains_recent(
+                &fs::read_to_string(path)?,
+                dirs::home_dir().as_deref(),
+            )),
+        }
+    }
+
+    fn modified(&self) -> Option<SystemTime> {
+        fs::metadata(self.path()).and_then(|m| m.modified()).ok()
+    }
+}
+
+/// Lists to read: the configured ones, or those of every installed VS Code variant and
+/// JetBrains IDE
+pub fn sources(config: &WorkspacesConfig) -> Vec<WorkspaceSource> {
+    if !config.enabled {
+        return Vec::new();
+    }
+    if !config.paths.is_empty() {
+        return config
+            .paths
+            .iter()
+            .flat_map(|path| {
+                let sources = WorkspaceSource::from_path(path);
+                if sources.is_empty() {
+                    log::warn!("No recent workspaces found in {:?}", path);
+                }
+                sources
+            })
+            .collect();
+    }
+
+    let Some(roaming) = dirs::config_dir() else {
+        return Vec::new();
+    };
+    let mut folders: Vec<PathBuf> = VSCODE_VARIANTS.iter().map(|(folder, ..)| roaming.join(folder)).collect();
+
+    // JetBrains IDEs keep one folder per version ("IntelliJIdea2024.1"); Android Studio is under Google
+    for vendor in ["JetBrains", "Google"] {
+        if let Ok(entries) = fs::read_dir(roaming.join(vendor)) {
+            folders.extend(entries.flatten().map(|entry| entry.path()));
+        }
+    }
+    folders.iter().flat_map(|folder| WorkspaceSource::from_path(folder)).collect()
+}
+
+/// Recent folders and workspaces of VS Code's `state.vscdb`
+pub fn read_vscode_state(path: &Path) -> Result<Vec<Workspace>, Box<dyn Error>> {
+    use rusqlite::{Connection, OpenFlags};
+
+    let connection = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+    // VS Code holds a write lock on the database for a moment whenever it saves state
+    connection.busy_timeout(std::time::Duration::from_secs(1))?;
+    let mut statement = connection.prepare(&format!(
+        "SELECT CAST(value AS TEXT) FROM ItemTable WHERE key = '{}'",
+        VSCODE_RECENT_KEY
+    ))?;
+    let rows = statement.query_map([], |row| {
+        let value: String = row.get(0)?;
+        Ok(value)
+    })?;
+    match rows.collect::<Result<Vec<_>, _>>()?.first() {
+        Some(json) => parse_vscode_recent(json),
+        None => Ok(Vec::new()),
+    }
+}
+
+/// Recent folders and workspaces of VS Code, newest first: the `state.vscdb` value
+/// (`{"entries": [...]}`) or a `storage.json` with `openedPathsList`. Recent files and
+/// remote folders are left out.
+pub fn parse_vscode_recent(json: &str) -> Result<Vec<Workspace>, Box<dyn Error>> {
+    let value: serde_json::Value = serde_json::from_str(json)?;
+    let list = value.get("openedPathsList").unwrap_or(&value);
+    let str_at = |entry: &serde_json::Value, pointer: &str| entry.pointer(pointer).and_then(|v| v.as_str()).map(str::to_string);
+
+    let mut locations = Vec::new();
+    for entry in list.get("entries").and_then(|e| e.as_array()).into_iter().flatten() {
+        locations.extend(str_at(entry, "/folderUri").or_else(|| str_at(entry, "/workspace/configPath")));
+    }
+    // Older versions listed folders apart from files, as URLs or plain paths
+    for key in ["workspaces3", "workspaces2", "workspaces"] {
+        for entry in list.get(key).and_then(|e| e.as_array()).into_iter().flatten() {
+            locations.extend(
+                entry
+                    .as_str()
+                    .map(str::to_string)
+                    .or_else(|| str_at(entry, "/configURIPath"))
+                    .or_else(|| str_at(entry, "/configPath")),
+            );
+        }
+    }
+
+    Ok(locations
+        .iter()
+        .filter_map(|location| {
+            if location.contains("://") {
+                file_url_to_path(location)
+            } else {
+                Some(PathBuf::from(location))
+            }
+        })
+        .map(|path| Workspace { path, opened: 0 })
+        .collect())
+}
+
+/// Recent projects of a JetBrains `recentProjects.xml`, newest first. Current versions
+/// keep `<entry key="path">` with an activation time; older ones a `recentPaths` list.
+/// `$USER_HOME$` is replaced with `home`.
+pub fn parse_jetbrains_recent(xml: &str, home: Option<&Path>) -> Vec<Workspace> {
+    let expand = |path: &str| -> PathBuf {
+        let path = xml_unescape(path);
+        let path = match (path.strip_prefix("$USER_HOME$"), home) {
+            (Some(rest), Some(home)) => format!("{}{}", home.display(), rest),
+            _ => path,
+        };
+        PathBuf::from(if cfg!(windows) { path.replace('/', "\\") } else { path })
+    };
+
+    let mut workspaces = Vec::new();
+    for chunk in xml.split("<entry ").skip(1) {
+        let tag = format!(" {}", &chunk[..chunk.find('>').unwrap_or(chunk.len())]);
+        let Some(key) = attribute(&tag, "key") else {
+            continue;
+        };
+        let body = &chunk[..chunk.find("</entry>").unwrap_or(chunk.len())];
+        let opened = body
+            .split("<option ")
+            .map(|option| format!(" {}", option))
+            .find(|option| attribute(option, "name") == Some("activationTimestamp"))
+            .and_then(|option| attribute(&option, "value")?.parse().ok())
+            .unwrap_or(0);
+        workspaces.push(Workspace { path: expand(key), opened });
+    }
+
+    if let Some(start) = xml.find("<option name=\"recentPaths\">") {
+        let list = &xml[start..];
+        let list = &list[..list.find("</list>").unwrap_or(list.len())];
+        for option in list.split("<option ").skip(2) {
+            if let Some(value) = attribute(&format!(" {}", option), "value") {
+                workspaces.push(Workspace { path: expand(value), opened: 0 });
+            }
+        }
+    }
+
+    // Stable, so undated entries keep the order of the file
+    workspaces.sort_by_key(|workspace| std::cmp::Reverse(workspace.opened));
+    workspaces
+}
+
+/// Catalog entries for recent projects, once per project and editor
+pub fn to_entries(workspaces: Vec<(Editor, Workspace)>) -> Vec<AppEntry> {
+    let mut seen = HashSet::new();
+    workspaces
+        .into_iter()
+        .filter(|(editor, workspace)| seen.insert((editor.clone(), workspace.path.clone())))
+        .filter_map(|(editor, workspace)| {
+            let is_workspace_file = workspace.path.extension().is_some_and(|ext| ext == "code-workspace");
+            let name = if is_workspace_file {
+                format!("{} (Workspace)", workspace.path.file_stem()?.to_string_lossy())
+            } else {
+                workspace.path.file_name()?.to_string_lossy().into_owned()
+            };
+            Some(AppEntry {
+                name,
+                description: format!("{} · {}", editor.name(), workspace.path.display()),
+                path: workspace.path,
+                app_type: AppType::Workspace(editor),
+            })
+        })
+        .collect()
+}
+
+/// Recent projects of a set of editors, re-read when any of their lists changes
+#[derive(Debug, Default)]
+pub struct WorkspaceIndex {
+    sources: Vec<WorkspaceSource>,
+    /// Modification time and projects of each list as last read successfully
+    read: HashMap<WorkspaceSource, (Option<SystemTime>, Vec<Workspace>)>,
+    refreshed: bool,
+}
+
+impl WorkspaceIndex {
+    pub fn new(sources: Vec<WorkspaceSource>) -> Self {
+        Self {
+            sources,
+            ..Default::default()
+        }
+    }
+
+    /// All recent projects when a list changed since the last call (always on the first call).
+    /// A list that fails to read keeps its previous projects and is read again next time.
+    pub fn refresh_if_changed(&mut self) -> Option<Vec<AppEntry>> {
+        let mut changed = !self.refreshed;
+        self.refreshed = true;
+
+        for source in &self.sources {
+            let modified = source.modified();
+            if self.read.get(source).is_some_and(|(read_at, _)| *read_at == modified) {
+                continue;
+            }
+            match source.read() {
+                Ok(workspaces) => {
+                    self.read.insert(source.clone(), (modified, workspaces));
+                    changed = true;
+                }
+                // A list that is gone has no projects left
+                Err(_) if modified.is_none() => {
+                    changed |= self.read.insert(source.clone(), (None, Vec::new())).is_some();
+                }
+                Err(e) => log::warn!("Failed to read recent workspaces from {:?}: {}", source.path(), e),
+            }
+        }
+        if !changed {
+            return None;
+        }
+
+        let mut workspaces = Vec::new();
+        for source in &self.sources {
+            if let Some((_, read)) = self.read.get(source) {
+                workspaces.extend(read.iter().map(|workspace| (source.editor(), workspace.clone())));
+            }
+        }
+        Some(to_entries(workspaces))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixture(name: &str) -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/workspaces").join(name)
+    }
+
+    fn paths(workspaces: &[Workspace]) -> Vec<PathBuf> {
+        workspaces.iter().map(|w| w.path.clone()).collect()
+    }
+
+    #[test]
+    fn test_read_vscode_state() {
+        let workspaces = read_vscode_state(&fixture("Code/User/globalStorage/state.vscdb")).unwrap();
+        assert_eq!(
+            paths(&workspaces),
+            [
+                PathBuf::from("c:/dev/nexus"),
+                PathBuf::from("c:/dev/platform/platform.code-workspace"),
+                PathBuf::from("/home/sam/dev/my site"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_vscode_storage() {
+        let json = fs::read_to_string(fixture("VSCodium/storage.json")).unwrap();
+        let workspaces = parse_vscode_recent(&json).unwrap();
+        assert_eq!(
+            paths(&workspaces),
+            [PathBuf::from("/home/sam/dev/api"), PathBuf::from("/home/sam/dev/all.code-workspace")]
+        );
+
+        let old = r#"{"openedPathsList": {"workspaces": ["C:\\dev\\legacy"], "files": ["C:\\a.txt"]}}"#;
+        assert_eq!(paths(&parse_vscode_recent(old).unwrap()), [PathBuf::from(r"C:\dev\legacy")]);
+        assert!(parse_vscode_recent("not json").is_err());
+    }
+
+    #[test]
+    fn test_parse_jetbrains_recent() {
+        let home = Path::new("/home/sam");
+        let xml = fs::read_to_string(fixture("JetBrains/IntelliJIdea2024.1/options/recentProjects.xml")).unwrap();
+        let workspaces = parse_jetbrains_recent(&xml, Some(home));
+        let native = |path: &str| PathBuf::from(if cfg!(windows) { path.replace('/', "\\") } else { path.to_string() });
+
+        // Newest first, whatever the order of the file
+        assert_eq!(paths(&workspaces), [native("C:/work/R&D tools"), native("/home/sam/IdeaProjects/billing")]);
+        assert_eq!(workspaces[0].opened, 1_760_500_000_000);
+
+        let xml = fs::read_to_string(fixture("JetBrains/PyCharmCE2021.3/options/recentProjects.xml")).unwrap();
+        let workspaces = parse_jetbrains_recent(&xml, Some(home));
+        assert_eq!(paths(&workspaces), [native("/home/sam/PycharmProjects/scraper"), native("/srv/notebooks")]);
+    }
+
+    #[test]
+    fn test_editors() {
+        let idea = WorkspaceSource::JetBrains(fixture("JetBrains/IntelliJIdea2024.1/options/recentProjects.xml"));
+        assert_eq!(idea.editor(), Editor::JetBrains { name: "IntelliJ IDEA", script: "idea" });
+        assert_eq!(Editor::jetbrains_for(Path::new("JetBrains/PyCharmCE2021.3")).name(), "PyCharm Community Edition");
+        assert_eq!(Editor::jetbrains_for(Path::new("JetBrains/PyCharm2023.2")).name(), "PyCharm");
+        assert_eq!(Editor::jetbrains_for(Path::new("Google/AndroidStudio2023.1")).name(), "Android Studio");
+        assert_eq!(Editor::vscode_for(&fixture("VSCodium/storage.json")).name(), "VSCodium");
+        assert_eq!(Editor::vscode_for(Path::new("code - insiders")).name(), "VS Code Insiders");
+        assert_eq!(Editor::vscode_for(Path::new("Other")).name(), "Visual Studio Code");
+
+        assert_eq!(vscode_url("vscode", Path::new(r"c:\dev\my app#2")), "vscode://file/c:/dev/my%20app%232");
+        assert_eq!(vscode_url("vscodium", Path::new("/home/sam/api")), "vscodium://file/home/sam/api");
+    }
+
+    #[test]
+    fn test_sources() {
+        assert_eq!(
+            WorkspaceSource::from_path(&fixture("Code")),
+            [WorkspaceSource::VsCodeState(fixture("Code").join("User").join("globalStorage").join("state.vscdb"))]
+        );
+        assert_eq!(
+            WorkspaceSource::from_path(&fixture("VSCodium")),
+            [WorkspaceSource::VsCodeStorage(fixture("VSCodium").join("storage.json"))]
+        );
+        assert!(WorkspaceSource::from_path(&fixture("missing.xml")).is_empty());
+
+        let config = WorkspacesConfig {
+            enabled: true,
+            paths: vec![
+                fixture("Code"),
+                fixture("VSCodium/storage.json"),
+                fixture("JetBrains/IntelliJIdea2024.1"),
+                fixture("JetBrains/PyCharmCE2021.3/options/recentProjects.xml"),
+            ],
+        };
+        assert_eq!(sources(&config).len(), 4);
+        assert!(sources(&WorkspacesConfig { enabled: false, ..config }).is_empty());
+    }
+
+    #[test]
+    fn test_index_dedupes_and_refreshes() {
+        let dir = std::env::temp_dir().join(format!("nexus_workspaces_{}", std::process::id()));
+        let options = dir.join("WebStorm2024.2").join("options");
+        fs::create_dir_all(&options).unwrap();
+        let xml = options.join("recentProjects.xml");
+        fs::copy(fixture("JetBrains/IntelliJIdea2024.1/options/recentProjects.xml"), &xml).unwrap();
+
+        let mut index = WorkspaceIndex::new(vec![
+            WorkspaceSource::VsCodeStorage(fixture("VSCodium/storage.json")),
+            WorkspaceSource::VsCodeStorage(fixture("VSCodium/storage.json")),
+            WorkspaceSource::JetBrains(xml.clone()),
+        ]);
+        let entries = index.refresh_if_changed().unwrap();
+
+        // The same list read twice is listed once
+        assert_eq!(entries.len(), 4);
+        assert_eq!(entries[0].name, "api");
+        assert_eq!(entries[0].description, format!("VSCodium · {}", Path::new("/home/sam/dev/api").display()));
+        assert_eq!(entries[1].name, "all (Workspace)");
+        assert_eq!(entries[2].name, "R&D tools");
+        assert_eq!(entries[2].app_type, AppType::Workspace(Editor::jetbrains_for(&xml)));
+        assert_eq!(entries[2].description.split(" · ").next(), Some("WebStorm"));
+        assert!(index.refresh_if_changed().is_none());
+
+        // Rewriting the list with a later timestamp triggers a reload
+        let text = fs::read_to_string(&xml).unwrap().replace("R&amp;D tools\"", "Research\"");
+        fs::write(&xml, text).unwrap();
+        let later = SystemTime::now() + std::time::Duration::from_secs(5);
+        fs::File::options().write(true).open(&xml).unwrap().set_modified(later).unwrap();
+        let entries = index.refresh_if_changed().unwrap();
+        assert_eq!(entries[2].name, "Research");
+
+        // A list that can't be read keeps its projects until it reads again
+        fs::remove_file(&xml).unwrap();
+        fs::create_dir(&xml).unwrap();
+        assert!(index.refresh_if_changed().is_none());
+        fs::remove_dir(&xml).unwrap();
+        fs::copy(fixture("JetBrains/IntelliJIdea2024.1/options/recentProjects.xml"), &xml).unwrap();
+        assert_eq!(index.refresh_if_changed().unwrap()[2].name, "R&D tools");
+
+        // A deleted list has no projects left
+        fs::remove_file(&xml).unwrap();
+        assert_eq!(index.refresh_if_changed().unwrap().len(), 2);
+        assert!(index.refresh_if_changed().is_none());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}